@@ -18,15 +18,22 @@
 
 //! Substrate chain configurations.
 
+use frame_support::traits::Get;
 use grandpa_primitives::AuthorityId as GrandpaId;
 use hex_literal::hex;
 use node_runtime::constants::currency::*;
 use node_runtime::Block;
+#[cfg(feature = "parachain")]
+use node_runtime::ParachainInfoConfig;
 use node_runtime::{
-    wasm_binary_unwrap, AuthorityDiscoveryConfig, BabeConfig, BalancesConfig, ContractsConfig,
-    CouncilConfig, DemocracyConfig, DummyConfig, ElectionsConfig, GrandpaConfig, ImOnlineConfig,
-    IndicesConfig, SessionConfig, SessionKeys, SocietyConfig, StakerStatus, StakingConfig,
-    SudoConfig, SystemConfig, TechnicalCommitteeConfig,
+    wasm_binary_unwrap, AssetTxPaymentConfig, AssetsConfig, BalancesConfig, ContractsConfig,
+    CouncilConfig, DemocracyConfig, DummyConfig, ElectionsConfig, IndicesConfig, KycConfig,
+    NativeAssetId, ParametersConfig, SocietyConfig, SystemConfig, TechnicalCommitteeConfig,
+};
+#[cfg(not(feature = "parachain"))]
+use node_runtime::{
+    AuthorityDiscoveryConfig, BabeConfig, GrandpaConfig, ImOnlineConfig, SessionConfig,
+    SessionKeys, StakerStatus, StakingConfig,
 };
 use pallet_im_online::sr25519::AuthorityId as ImOnlineId;
 use sc_chain_spec::ChainSpecExtension;
@@ -36,18 +43,20 @@ use serde::{Deserialize, Serialize};
 use sp_authority_discovery::AuthorityId as AuthorityDiscoveryId;
 use sp_consensus_babe::AuthorityId as BabeId;
 use sp_core::{crypto::UncheckedInto, sr25519, Pair, Public};
-use sp_runtime::{
-    traits::{IdentifyAccount, Verify},
-    Perbill,
-};
+use sp_runtime::traits::{IdentifyAccount, Verify};
+#[cfg(not(feature = "parachain"))]
+use sp_runtime::Perbill;
 
-pub use node_primitives::{AccountId, Balance, Signature};
+pub use node_primitives::{AccountId, Balance, BlockNumber, Signature};
 pub use node_runtime::GenesisConfig;
 
 type AccountPublic = <Signature as Verify>::Signer;
 
 const STAGING_TELEMETRY_URL: &str = "wss://telemetry.polkadot.io/submit/";
 
+/// The para ID of the built-in chain specs.
+pub const PARA_ID: u32 = 2000;
+
 /// Node `ChainSpec` extensions.
 ///
 /// Additional parameters for some Substrate core modules,
@@ -59,11 +68,33 @@ pub struct Extensions {
     pub fork_blocks: sc_client_api::ForkBlocks<Block>,
     /// Known bad block hashes.
     pub bad_blocks: sc_client_api::BadBlocks<Block>,
+    /// The relay chain of parachain builds.
+    pub relay_chain: Option<String>,
+    /// The para ID of parachain builds, which must match the genesis of `ParachainInfo`.
+    pub para_id: Option<u32>,
+}
+
+impl Extensions {
+    /// Try to get the extensions of the given chain spec.
+    pub fn try_get(chain_spec: &dyn sc_service::ChainSpec) -> Option<&Self> {
+        sc_chain_spec::get_extension(chain_spec.extensions())
+    }
+}
+
+/// The extensions of the built-in chain specs, which parachain builds register with `PARA_ID` on
+/// `relay_chain`. Standalone builds ignore them.
+fn extensions(relay_chain: &str) -> Extensions {
+    Extensions {
+        relay_chain: Some(relay_chain.into()),
+        para_id: Some(PARA_ID),
+        ..Default::default()
+    }
 }
 
 /// Specialized `ChainSpec`.
 pub type ChainSpec = sc_service::GenericChainSpec<GenesisConfig, Extensions>;
 
+#[cfg(not(feature = "parachain"))]
 fn session_keys(
     grandpa: GrandpaId,
     babe: BabeId,
@@ -89,16 +120,17 @@ pub fn properties() -> Properties {
     properties
 }
 
-fn staging_testnet_config_genesis() -> GenesisConfig {
+/// The authorities of the live networks.
+fn live_authorities() -> Vec<(
+    AccountId,
+    AccountId,
+    GrandpaId,
+    BabeId,
+    ImOnlineId,
+    AuthorityDiscoveryId,
+)> {
     // use scripts/init.sh to generate keys
-    let initial_authorities: Vec<(
-        AccountId,
-        AccountId,
-        GrandpaId,
-        BabeId,
-        ImOnlineId,
-        AuthorityDiscoveryId,
-    )> = vec![
+    vec![
         (
             // 5E6txmNZmmsMWJX3cEAUioez5FgLge5honKxMGXPpacHMDL7
             hex!["5a2457447e5481413eb173475f56f1654f13aaed5f89a88b7e474cb86a884945"].into(),
@@ -171,18 +203,27 @@ fn staging_testnet_config_genesis() -> GenesisConfig {
             hex!["f6a2da176b324fa3d8f8726d65ad712964d4cf5c8c50649e292500e7cf683012"]
                 .unchecked_into(),
         ),
-    ];
+    ]
+}
 
+/// The administrative account of the live networks.
+fn live_admin_key() -> AccountId {
     // generated with secret
-    let root_key: AccountId = hex![
+    hex![
         // 5F9rKX5AzK2NQNGyEnz2HphY4Sk9wwmtDyZ7uND4xwxWxqaA
         "88a16a8aa1aa0f639df1583c9123001bb44e4c69f89df5d85a87db4423cea85e"
     ]
-    .into();
+    .into()
+}
 
-    let endowed_accounts: Vec<AccountId> = vec![root_key.clone()];
+fn staging_testnet_config_genesis() -> GenesisConfig {
+    // The administrative account holds every role of the testnet alone: it is the only member
+    // of the council and of the technical committee, and so holds every governance origin, the
+    // only relayer of the bridge, which mints alone, and the only KYC provider.
+    let admin_key = live_admin_key();
+    let endowed_accounts: Vec<AccountId> = vec![admin_key.clone()];
 
-    testnet_genesis(initial_authorities, root_key, Some(endowed_accounts), false)
+    testnet_genesis(live_authorities(), admin_key, Some(endowed_accounts), false)
 }
 
 /// Staging testnet config.
@@ -200,7 +241,49 @@ pub fn staging_testnet_config() -> ChainSpec {
         ),
         None,
         Some(properties()),
-        Default::default(),
+        extensions("rococo"),
+    )
+}
+
+/// The relayers of the bridge of the production network which must vote for a mint.
+const PRODUCTION_RELAYER_THRESHOLD: u32 = 3;
+
+fn production_config_genesis() -> GenesisConfig {
+    let authorities = live_authorities();
+    // The controllers of the operators of the authorities fill every role: they are the members
+    // of the council and of the technical committee, the relayers of the bridge and the KYC
+    // providers. Most council and committee origins and the mints of the bridge need three of the
+    // four of them, but two of them propose referenda and reject treasury proposals, and each of
+    // them alone vetoes proposals as a member of the technical committee and whitelists investors
+    // as a KYC provider.
+    let operators: Vec<AccountId> = authorities.iter().map(|x| x.1.clone()).collect();
+
+    genesis(
+        authorities,
+        operators.clone(),
+        operators.clone(),
+        operators.clone(),
+        PRODUCTION_RELAYER_THRESHOLD,
+        operators,
+        false,
+    )
+}
+
+/// Production config, without a sudo key.
+pub fn production_config() -> ChainSpec {
+    ChainSpec::from_genesis(
+        "Curio",
+        "curio",
+        ChainType::Live,
+        production_config_genesis,
+        vec![],
+        Some(
+            TelemetryEndpoints::new(vec![(STAGING_TELEMETRY_URL.to_string(), 0)])
+                .expect("Staging telemetry url is valid; qed"),
+        ),
+        Some("curio"),
+        Some(properties()),
+        extensions("rococo"),
     )
 }
 
@@ -241,6 +324,10 @@ pub fn authority_keys_from_seed(
 }
 
 /// Helper function to create GenesisConfig for testing
+///
+/// There is no sudo key: the first half of the endowed accounts are members of the council and
+/// of the technical committee, and hold the governance origins. `admin_key` is the relayer of the
+/// bridge and the KYC provider.
 pub fn testnet_genesis(
     initial_authorities: Vec<(
         AccountId,
@@ -250,7 +337,7 @@ pub fn testnet_genesis(
         ImOnlineId,
         AuthorityDiscoveryId,
     )>,
-    root_key: AccountId,
+    admin_key: AccountId,
     endowed_accounts: Option<Vec<AccountId>>,
     enable_println: bool,
 ) -> GenesisConfig {
@@ -270,8 +357,46 @@ pub fn testnet_genesis(
             get_account_id_from_seed::<sr25519::Public>("Ferdie//stash"),
         ]
     });
-    let num_endowed_accounts = endowed_accounts.len();
+    let council = endowed_accounts
+        .iter()
+        .take((endowed_accounts.len() + 1) / 2)
+        .cloned()
+        .collect();
+
+    genesis(
+        initial_authorities,
+        endowed_accounts,
+        council,
+        vec![admin_key.clone()],
+        1,
+        vec![admin_key],
+        enable_println,
+    )
+}
 
+/// Create the GenesisConfig of a chain without a sudo key.
+///
+/// The `council` members are the members of the council, of the technical committee and of the
+/// society. `relayer_threshold` of the `relayers` of the bridge vote for a mint. The endowed
+/// accounts are verified investors, and the stashes of the `initial_authorities` are endowed as
+/// well. Only standalone builds stake the stashes, in parachain builds the relay chain validates
+/// and finalizes the blocks of the authorities.
+fn genesis(
+    initial_authorities: Vec<(
+        AccountId,
+        AccountId,
+        GrandpaId,
+        BabeId,
+        ImOnlineId,
+        AuthorityDiscoveryId,
+    )>,
+    endowed_accounts: Vec<AccountId>,
+    council: Vec<AccountId>,
+    relayers: Vec<AccountId>,
+    relayer_threshold: u32,
+    kyc_providers: Vec<AccountId>,
+    enable_println: bool,
+) -> GenesisConfig {
     const ENDOWMENT: Balance = 10_000_000 * DOLLARS;
     const STASH: Balance = 500_000 * DOLLARS;
     const MINT: Balance = 2_000_000 * DOLLARS;
@@ -290,6 +415,7 @@ pub fn testnet_genesis(
                 .collect(),
         }),
         pallet_indices: Some(IndicesConfig { indices: vec![] }),
+        #[cfg(not(feature = "parachain"))]
         pallet_session: Some(SessionConfig {
             keys: initial_authorities
                 .iter()
@@ -302,6 +428,7 @@ pub fn testnet_genesis(
                 })
                 .collect::<Vec<_>>(),
         }),
+        #[cfg(not(feature = "parachain"))]
         pallet_staking: Some(StakingConfig {
             validator_count: initial_authorities.len() as u32 * 2,
             minimum_validator_count: initial_authorities.len() as u32,
@@ -315,20 +442,15 @@ pub fn testnet_genesis(
         }),
         pallet_democracy: Some(DemocracyConfig::default()),
         pallet_elections_phragmen: Some(ElectionsConfig {
-            members: endowed_accounts
+            members: council
                 .iter()
-                .take((num_endowed_accounts + 1) / 2)
                 .cloned()
                 .map(|member| (member, STASH))
                 .collect(),
         }),
         pallet_collective_Instance1: Some(CouncilConfig::default()),
         pallet_collective_Instance2: Some(TechnicalCommitteeConfig {
-            members: endowed_accounts
-                .iter()
-                .take((num_endowed_accounts + 1) / 2)
-                .cloned()
-                .collect(),
+            members: council.clone(),
             phantom: Default::default(),
         }),
         pallet_contracts: Some(ContractsConfig {
@@ -337,28 +459,59 @@ pub fn testnet_genesis(
                 ..Default::default()
             },
         }),
-        pallet_sudo: Some(SudoConfig { key: root_key }),
+        #[cfg(not(feature = "parachain"))]
         pallet_babe: Some(BabeConfig {
             authorities: vec![],
         }),
+        #[cfg(not(feature = "parachain"))]
         pallet_im_online: Some(ImOnlineConfig { keys: vec![] }),
+        #[cfg(not(feature = "parachain"))]
         pallet_authority_discovery: Some(AuthorityDiscoveryConfig { keys: vec![] }),
+        #[cfg(not(feature = "parachain"))]
         pallet_grandpa: Some(GrandpaConfig {
             authorities: vec![],
         }),
         pallet_membership_Instance1: Some(Default::default()),
         pallet_treasury: Some(Default::default()),
         pallet_society: Some(SocietyConfig {
-            members: endowed_accounts
-                .iter()
-                .take((num_endowed_accounts + 1) / 2)
-                .cloned()
-                .collect(),
+            members: council,
             pot: 0,
             max_members: 999,
         }),
         pallet_vesting: Some(Default::default()),
-        pallet_dummy: Some(DummyConfig { balance: MINT }),
+        pallet_dummy: Some(DummyConfig {
+            balance: MINT,
+            relayers,
+            relayer_threshold,
+            // The native token is not deployed on test networks, its contract address is zero.
+            assets: vec![(NativeAssetId::get(), Default::default())],
+        }),
+        pallet_eth_light_client: Some(Default::default()),
+        pallet_kyc: Some(KycConfig {
+            providers: kyc_providers,
+            // The endowed accounts of test networks never need to renew their verification.
+            investors: endowed_accounts
+                .iter()
+                .cloned()
+                .map(|k| (k, BlockNumber::max_value()))
+                .collect(),
+            restrict_transfers: false,
+        }),
+        pallet_multi_asset: Some(AssetsConfig {
+            // Identifier 0 stands for the native currency on the bridge.
+            next_asset_id: 1,
+            assets: vec![],
+            balances: vec![],
+        }),
+        pallet_asset_tx_payment: Some(AssetTxPaymentConfig { fee_rates: vec![] }),
+        pallet_parameters: Some(ParametersConfig {
+            fee_split: Default::default(),
+            tip_split: Default::default(),
+        }),
+        #[cfg(feature = "parachain")]
+        parachain_info: Some(ParachainInfoConfig {
+            parachain_id: PARA_ID.into(),
+        }),
     }
 }
 
@@ -382,7 +535,7 @@ pub fn development_config() -> ChainSpec {
         None,
         None,
         None,
-        Default::default(),
+        extensions("rococo-local"),
     )
 }
 
@@ -409,16 +562,18 @@ pub fn local_testnet_config() -> ChainSpec {
         None,
         None,
         None,
-        Default::default(),
+        extensions("rococo-local"),
     )
 }
 
 #[cfg(test)]
 pub(crate) mod tests {
     use super::*;
+    #[cfg(not(feature = "parachain"))]
     use crate::service::{new_full_base, new_light_base, NewFullBase};
     use sp_runtime::BuildStorage;
 
+    #[cfg(not(feature = "parachain"))]
     fn local_testnet_genesis_instant_single() -> GenesisConfig {
         testnet_genesis(
             vec![authority_keys_from_seed("Alice")],
@@ -429,6 +584,7 @@ pub(crate) mod tests {
     }
 
     /// Local testnet config (single validator - Alice)
+    #[cfg(not(feature = "parachain"))]
     pub fn integration_test_config_with_single_authority() -> ChainSpec {
         ChainSpec::from_genesis(
             "Integration Test",
@@ -444,6 +600,7 @@ pub(crate) mod tests {
     }
 
     /// Local testnet config (multivalidator Alice + Bob)
+    #[cfg(not(feature = "parachain"))]
     pub fn integration_test_config_with_two_authorities() -> ChainSpec {
         ChainSpec::from_genesis(
             "Integration Test",
@@ -460,6 +617,7 @@ pub(crate) mod tests {
 
     #[test]
     #[ignore]
+    #[cfg(not(feature = "parachain"))]
     fn test_connectivity() {
         sc_service_test::connectivity(
             integration_test_config_with_two_authorities(),
@@ -504,4 +662,45 @@ pub(crate) mod tests {
     fn test_staging_test_net_chain_spec() {
         staging_testnet_config().build_storage().unwrap();
     }
+
+    #[test]
+    fn test_built_in_chain_specs_have_a_para_id() {
+        for spec in vec![
+            development_config(),
+            local_testnet_config(),
+            staging_testnet_config(),
+            production_config(),
+        ] {
+            let extensions = Extensions::try_get(&spec).unwrap();
+            assert_eq!(extensions.para_id, Some(PARA_ID));
+        }
+    }
+
+    #[test]
+    fn test_production_chain_spec_has_no_sudo_key() {
+        let storage = production_config().build_storage().unwrap();
+        let sudo = sp_core::twox_128(b"Sudo");
+        assert!(!storage.top.keys().any(|key| key.starts_with(&sudo)));
+    }
+
+    #[test]
+    fn test_production_governance_is_not_held_by_a_single_account() {
+        let genesis = production_config_genesis();
+        let council = genesis.pallet_elections_phragmen.unwrap().members;
+        let technical_committee = genesis.pallet_collective_Instance2.unwrap().members;
+        let dummy = genesis.pallet_dummy.unwrap();
+        let kyc_providers = genesis.pallet_kyc.unwrap().providers;
+
+        assert!(council.len() > 1);
+        assert!(technical_committee.len() > 1);
+        assert!(dummy.relayer_threshold > 1);
+        assert!(dummy.relayer_threshold as usize <= dummy.relayers.len());
+        assert!(kyc_providers.len() > 1);
+        assert!(!council
+            .iter()
+            .any(|(member, _)| *member == live_admin_key()));
+        assert!(!technical_committee.contains(&live_admin_key()));
+        assert!(!dummy.relayers.contains(&live_admin_key()));
+        assert!(!kyc_providers.contains(&live_admin_key()));
+    }
 }
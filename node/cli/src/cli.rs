@@ -17,6 +17,7 @@
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
 use sc_cli::{KeySubcommand, RunCmd, SignCmd, VanityCmd, VerifyCmd};
+use std::path::PathBuf;
 use structopt::StructOpt;
 
 /// An overarching CLI command definition.
@@ -28,6 +29,11 @@ pub struct Cli {
     #[allow(missing_docs)]
     #[structopt(flatten)]
     pub run: RunCmd,
+
+    /// Arguments of the embedded relay chain node of parachain builds, after `--`.
+    #[cfg(feature = "parachain")]
+    #[structopt(raw = true)]
+    pub relaychain_args: Vec<String>,
 }
 
 /// Possible subcommands of the main binary.
@@ -47,6 +53,13 @@ pub enum Subcommand {
     #[structopt(name = "benchmark", about = "Benchmark runtime pallets.")]
     Benchmark(frame_benchmarking_cli::BenchmarkCmd),
 
+    /// The custom subcommand rehearsing a runtime upgrade on a state snapshot.
+    #[structopt(
+        name = "rehearse-upgrade",
+        about = "Run the upgrade of a new runtime and the checks of its migrations offline."
+    )]
+    RehearseUpgrade(crate::RehearseUpgradeCmd),
+
     /// Verify a signature for a message, provided on STDIN, with a given (public or secret) key.
     Verify(VerifyCmd),
 
@@ -60,6 +73,7 @@ pub enum Subcommand {
     BuildSpec(sc_cli::BuildSpecCmd),
 
     /// Build a chain specification with a light client sync state.
+    #[cfg(not(feature = "parachain"))]
     BuildSyncSpec(sc_cli::BuildSyncSpecCmd),
 
     /// Validate blocks.
@@ -79,4 +93,61 @@ pub enum Subcommand {
 
     /// Revert the chain to a previous state.
     Revert(sc_cli::RevertCmd),
+
+    /// Export the genesis state of the parachain, to register it on the relay chain.
+    #[structopt(name = "export-genesis-state")]
+    ExportGenesisState(ExportGenesisCmd),
+
+    /// Export the genesis wasm of the parachain, to register it on the relay chain.
+    #[structopt(name = "export-genesis-wasm")]
+    ExportGenesisWasm(ExportGenesisCmd),
+}
+
+/// The `export-genesis-state` and `export-genesis-wasm` commands.
+///
+/// Standalone builds export the genesis of the standalone runtime, which cannot run as a
+/// parachain.
+#[derive(Debug, StructOpt)]
+pub struct ExportGenesisCmd {
+    /// Output file name or stdout if unspecified.
+    #[structopt(parse(from_os_str))]
+    pub output: Option<PathBuf>,
+
+    /// Write output in binary. Default is to write in hex.
+    #[structopt(short, long)]
+    pub raw: bool,
+
+    /// Specify the chain specification of the parachain.
+    #[structopt(long, value_name = "CHAIN_SPEC", default_value = "dev")]
+    pub chain: String,
+}
+
+/// The relay chain node embedded in a parachain collator.
+#[cfg(feature = "parachain")]
+#[derive(Debug)]
+pub struct RelayChainCli {
+    /// Arguments of the relay chain node.
+    pub base: polkadot_cli::RunCmd,
+
+    /// The relay chain of the chain spec of the parachain.
+    pub chain_id: Option<String>,
+
+    /// The base path of the relay chain node, inside the one of the collator.
+    pub base_path: Option<PathBuf>,
+}
+
+#[cfg(feature = "parachain")]
+impl RelayChainCli {
+    /// Parse the arguments of the relay chain node.
+    pub fn new<'a>(
+        base_path: Option<PathBuf>,
+        chain_id: Option<String>,
+        relay_chain_args: impl Iterator<Item = &'a String>,
+    ) -> Self {
+        Self {
+            base: polkadot_cli::RunCmd::from_iter(relay_chain_args),
+            chain_id,
+            base_path,
+        }
+    }
 }
@@ -16,12 +16,36 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::service::{new_full_base, new_partial, NewFullBase};
+#[cfg(feature = "parachain")]
+use crate::service::new_collator_partial as new_partial;
+#[cfg(not(feature = "parachain"))]
+use crate::service::new_partial;
+#[cfg(not(feature = "parachain"))]
+use crate::service::{new_full_base, NewFullBase};
+use crate::ExportGenesisCmd;
+#[cfg(feature = "parachain")]
+use crate::RelayChainCli;
 use crate::{chain_spec, service, Cli, Subcommand};
+use codec::Encode;
 use node_executor::Executor;
 use node_runtime::{Block, RuntimeApi};
-use sc_cli::{ChainSpec, Result, Role, RuntimeVersion, SubstrateCli};
+#[cfg(not(feature = "parachain"))]
+use sc_cli::Role;
+use sc_cli::{ChainSpec, Result, RuntimeVersion, SubstrateCli};
+#[cfg(feature = "parachain")]
+use sc_cli::{
+    CliConfiguration, DefaultConfigurationValues, ImportParams, KeystoreParams, NetworkParams,
+    SharedParams,
+};
 use sc_service::PartialComponents;
+use sp_core::hexdisplay::HexDisplay;
+use sp_runtime::{
+    traits::{Block as BlockT, Hash as HashT, Header as HeaderT, Zero},
+    BuildStorage,
+};
+use std::io::Write;
+#[cfg(feature = "parachain")]
+use std::net::SocketAddr;
 
 impl SubstrateCli for Cli {
     fn impl_name() -> String {
@@ -53,6 +77,7 @@ impl SubstrateCli for Cli {
             "dev" => Box::new(chain_spec::development_config()),
             "local" => Box::new(chain_spec::local_testnet_config()),
             "staging" => Box::new(chain_spec::staging_testnet_config()),
+            "production" => Box::new(chain_spec::production_config()),
             path => Box::new(chain_spec::ChainSpec::from_json_file(
                 std::path::PathBuf::from(path),
             )?),
@@ -64,11 +89,235 @@ impl SubstrateCli for Cli {
     }
 }
 
+#[cfg(feature = "parachain")]
+impl SubstrateCli for RelayChainCli {
+    fn impl_name() -> String {
+        "Curio Collator".into()
+    }
+
+    fn impl_version() -> String {
+        env!("SUBSTRATE_CLI_IMPL_VERSION").into()
+    }
+
+    fn description() -> String {
+        "The relay chain node embedded in the Curio collator, configured by the arguments \
+        after `--`."
+            .into()
+    }
+
+    fn author() -> String {
+        env!("CARGO_PKG_AUTHORS").into()
+    }
+
+    fn support_url() -> String {
+        "https://github.com/paritytech/substrate/issues/new".into()
+    }
+
+    fn copyright_start_year() -> i32 {
+        2021
+    }
+
+    fn load_spec(&self, id: &str) -> std::result::Result<Box<dyn sc_service::ChainSpec>, String> {
+        polkadot_cli::Cli::from_iter([RelayChainCli::executable_name()].iter()).load_spec(id)
+    }
+
+    fn native_runtime_version(chain_spec: &Box<dyn ChainSpec>) -> &'static RuntimeVersion {
+        polkadot_cli::Cli::native_runtime_version(chain_spec)
+    }
+}
+
+/// The relay chain node listens next to the default ports of the collator.
+#[cfg(feature = "parachain")]
+impl DefaultConfigurationValues for RelayChainCli {
+    fn p2p_listen_port() -> u16 {
+        30334
+    }
+
+    fn rpc_ws_listen_port() -> u16 {
+        9945
+    }
+
+    fn rpc_http_listen_port() -> u16 {
+        9934
+    }
+
+    fn prometheus_listen_port() -> u16 {
+        9616
+    }
+}
+
+#[cfg(feature = "parachain")]
+impl CliConfiguration<Self> for RelayChainCli {
+    fn shared_params(&self) -> &SharedParams {
+        self.base.base.shared_params()
+    }
+
+    fn import_params(&self) -> Option<&ImportParams> {
+        self.base.base.import_params()
+    }
+
+    fn network_params(&self) -> Option<&NetworkParams> {
+        self.base.base.network_params()
+    }
+
+    fn keystore_params(&self) -> Option<&KeystoreParams> {
+        self.base.base.keystore_params()
+    }
+
+    fn base_path(&self) -> Result<Option<sc_service::BasePath>> {
+        Ok(self
+            .shared_params()
+            .base_path()
+            .or_else(|| self.base_path.clone().map(Into::into)))
+    }
+
+    fn rpc_http(&self, default_listen_port: u16) -> Result<Option<SocketAddr>> {
+        self.base.base.rpc_http(default_listen_port)
+    }
+
+    fn rpc_ws(&self, default_listen_port: u16) -> Result<Option<SocketAddr>> {
+        self.base.base.rpc_ws(default_listen_port)
+    }
+
+    fn prometheus_config(
+        &self,
+        default_listen_port: u16,
+    ) -> Result<Option<sc_service::config::PrometheusConfig>> {
+        self.base.base.prometheus_config(default_listen_port)
+    }
+
+    /// The logger is set up by the collator, there is nothing left to set up.
+    fn init<C: SubstrateCli>(&self) -> Result<()> {
+        Ok(())
+    }
+
+    fn chain_id(&self, is_dev: bool) -> Result<String> {
+        let chain_id = self.base.base.chain_id(is_dev)?;
+        Ok(if chain_id.is_empty() {
+            self.chain_id.clone().unwrap_or_default()
+        } else {
+            chain_id
+        })
+    }
+
+    fn role(&self, is_dev: bool) -> Result<sc_service::Role> {
+        self.base.base.role(is_dev)
+    }
+}
+
+/// Build the genesis block of a chain spec, as the client does.
+fn genesis_block(chain_spec: &dyn ChainSpec) -> Result<Block> {
+    type Hashing = <<Block as BlockT>::Header as HeaderT>::Hashing;
+
+    let storage = chain_spec.build_storage()?;
+    let child_roots = storage.children_default.values().map(|child| {
+        let root = <Hashing as HashT>::trie_root(child.data.clone().into_iter().collect());
+        (
+            child.child_info.prefixed_storage_key().into_inner(),
+            root.encode(),
+        )
+    });
+    let state_root =
+        <Hashing as HashT>::trie_root(storage.top.clone().into_iter().chain(child_roots).collect());
+    let extrinsics_root = <Hashing as HashT>::trie_root(Vec::new());
+
+    Ok(Block::new(
+        HeaderT::new(
+            Zero::zero(),
+            extrinsics_root,
+            state_root,
+            Default::default(),
+            Default::default(),
+        ),
+        Default::default(),
+    ))
+}
+
+impl ExportGenesisCmd {
+    /// The encoded header of the genesis block of the chain.
+    fn genesis_state(&self, cli: &Cli) -> Result<Vec<u8>> {
+        let block = genesis_block(&*cli.load_spec(&self.chain)?)?;
+        Ok(block.header().encode())
+    }
+
+    /// The wasm of the runtime of the genesis block of the chain.
+    fn genesis_wasm(&self, cli: &Cli) -> Result<Vec<u8>> {
+        let mut storage = cli.load_spec(&self.chain)?.build_storage()?;
+        Ok(storage
+            .top
+            .remove(sp_core::storage::well_known_keys::CODE)
+            .ok_or("The genesis state has no wasm")?)
+    }
+
+    /// Write `data` to the output, in hex unless `--raw` is given.
+    fn write(&self, data: Vec<u8>) -> Result<()> {
+        let data = if self.raw {
+            data
+        } else {
+            format!("0x{:?}", HexDisplay::from(&data)).into_bytes()
+        };
+        match &self.output {
+            Some(path) => std::fs::write(path, data)?,
+            None => std::io::stdout().write_all(&data)?,
+        }
+        Ok(())
+    }
+}
+
+/// Run the collator, or the full node of the parachain when it does not collate.
+#[cfg(feature = "parachain")]
+fn run_collator(cli: &Cli) -> Result<()> {
+    use sp_core::Pair;
+
+    let runner = cli.create_runner(&cli.run)?;
+    runner.run_node_until_exit(|config| {
+        let extensions = chain_spec::Extensions::try_get(&*config.chain_spec);
+        let para_id = extensions
+            .and_then(|e| e.para_id)
+            .ok_or("The chain spec has no para ID")?;
+        let relay_chain = RelayChainCli::new(
+            config
+                .base_path
+                .as_ref()
+                .map(|path| path.path().join("polkadot")),
+            extensions.and_then(|e| e.relay_chain.clone()),
+            [RelayChainCli::executable_name()]
+                .iter()
+                .chain(cli.relaychain_args.iter()),
+        );
+        let relay_chain_config = SubstrateCli::create_configuration(
+            &relay_chain,
+            &relay_chain,
+            config.task_executor.clone(),
+        )
+        .map_err(|e| format!("Relay chain argument error: {}", e))?;
+
+        // The collator key only signs the collations, a new one is generated on each start.
+        let collator_key = polkadot_primitives::v1::CollatorPair::generate().0;
+        let collate = config.role.is_authority();
+        log::info!("Parachain id: {}", para_id);
+        log::info!("Is collating: {}", collate);
+
+        service::start_collator(
+            config,
+            collator_key,
+            relay_chain_config,
+            para_id.into(),
+            collate,
+        )
+    })
+}
+
 /// Parse command line arguments into service configuration.
 pub fn run() -> Result<()> {
     let cli = Cli::from_args();
 
     match &cli.subcommand {
+        #[cfg(feature = "parachain")]
+        None => run_collator(&cli),
+        Some(Subcommand::ExportGenesisState(cmd)) => cmd.write(cmd.genesis_state(&cli)?),
+        Some(Subcommand::ExportGenesisWasm(cmd)) => cmd.write(cmd.genesis_wasm(&cli)?),
+        #[cfg(not(feature = "parachain"))]
         None => {
             let runner = cli.create_runner(&cli.run)?;
             runner.run_node_until_exit(|config| match config.role {
@@ -92,6 +341,19 @@ pub fn run() -> Result<()> {
                     .into())
             }
         }
+        Some(Subcommand::RehearseUpgrade(cmd)) => {
+            if cfg!(feature = "try-runtime") {
+                let runner = cli.create_runner(cmd)?;
+
+                runner.sync_run(|config| cmd.run(config))
+            } else {
+                Err(
+                    "Upgrade rehearsals weren't enabled when building the node. \
+				You can enable them with `--features try-runtime`."
+                        .into(),
+                )
+            }
+        }
         Some(Subcommand::Key(cmd)) => cmd.run(),
         Some(Subcommand::Sign(cmd)) => cmd.run(),
         Some(Subcommand::Verify(cmd)) => cmd.run(),
@@ -100,6 +362,7 @@ pub fn run() -> Result<()> {
             let runner = cli.create_runner(cmd)?;
             runner.sync_run(|config| cmd.run(config.chain_spec, config.network))
         }
+        #[cfg(not(feature = "parachain"))]
         Some(Subcommand::BuildSyncSpec(cmd)) => {
             let runner = cli.create_runner(cmd)?;
             runner.async_run(|config| {
@@ -182,3 +445,125 @@ pub fn run() -> Result<()> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codec::Decode;
+    use node_runtime::Header;
+    use sc_service::config::WasmExecutionMethod;
+    use sp_core::traits::{CallInWasm, MissingHostFunctions};
+    use std::{collections::BTreeMap, path::Path};
+
+    /// Stands in for a local relay chain, on which the sudo registers a para with the files
+    /// exported by the collator, as `parasSudoWrapper.sudoScheduleParaInitialize` does: the
+    /// validators decode the genesis head of the para and run its wasm to check its blocks.
+    #[derive(Default)]
+    struct RelayChain {
+        paras: BTreeMap<u32, (Header, Vec<u8>)>,
+    }
+
+    impl RelayChain {
+        /// Register `para_id` with the hex encoded genesis `head` and `wasm`, returning the
+        /// version of the runtime of the para.
+        fn register(
+            &mut self,
+            para_id: u32,
+            head: &[u8],
+            wasm: &[u8],
+        ) -> std::result::Result<RuntimeVersion, String> {
+            if self.paras.contains_key(&para_id) {
+                return Err("The para is already registered".into());
+            }
+            let from_hex = |data: &[u8]| {
+                sp_core::bytes::from_hex(&String::from_utf8_lossy(data))
+                    .map_err(|e| format!("Invalid hex: {}", e))
+            };
+            let (head, wasm) = (from_hex(head)?, from_hex(wasm)?);
+            let head = Header::decode(&mut &head[..]).map_err(|e| e.to_string())?;
+
+            let executor = node_executor::NativeExecutor::<Executor>::new(
+                WasmExecutionMethod::Interpreted,
+                None,
+                1,
+            );
+            let mut ext = sp_io::TestExternalities::default();
+            let version = executor.call_in_wasm(
+                &wasm,
+                None,
+                "Core_version",
+                &[],
+                &mut ext.ext(),
+                MissingHostFunctions::Disallow,
+            )?;
+            let version = RuntimeVersion::decode(&mut &version[..]).map_err(|e| e.to_string())?;
+
+            self.paras.insert(para_id, (head, wasm));
+            Ok(version)
+        }
+    }
+
+    /// Run the export `subcommand` of the local chain into `output`, returning what it wrote.
+    fn export(subcommand: &str, output: &Path) -> Vec<u8> {
+        let output = output.to_str().unwrap();
+        let cli = Cli::from_iter(&["curio", subcommand, "--chain", "local", output]);
+        match &cli.subcommand {
+            Some(Subcommand::ExportGenesisState(cmd)) => {
+                cmd.write(cmd.genesis_state(&cli).unwrap())
+            }
+            Some(Subcommand::ExportGenesisWasm(cmd)) => cmd.write(cmd.genesis_wasm(&cli).unwrap()),
+            _ => panic!("{} is not an export subcommand", subcommand),
+        }
+        .unwrap();
+        std::fs::read(output).unwrap()
+    }
+
+    #[test]
+    fn the_exported_genesis_registers_on_a_local_relay_chain() {
+        let dir = tempfile::tempdir().unwrap();
+        let head = export(
+            "export-genesis-state",
+            &dir.path().join("para-2000-genesis"),
+        );
+        let wasm = export("export-genesis-wasm", &dir.path().join("para-2000-wasm"));
+
+        let mut relay_chain = RelayChain::default();
+        let version = relay_chain
+            .register(chain_spec::PARA_ID, &head, &wasm)
+            .unwrap();
+        assert_eq!(version.spec_name, node_runtime::VERSION.spec_name);
+        assert!(relay_chain
+            .register(chain_spec::PARA_ID, &head, &wasm)
+            .is_err());
+
+        let spec: Box<dyn ChainSpec> = Box::new(chain_spec::local_testnet_config());
+        let (registered_head, registered_wasm) = &relay_chain.paras[&chain_spec::PARA_ID];
+        assert_eq!(registered_head, genesis_block(&*spec).unwrap().header());
+        let mut storage = spec.build_storage().unwrap();
+        assert_eq!(
+            storage
+                .top
+                .remove(sp_core::storage::well_known_keys::CODE)
+                .as_ref(),
+            Some(registered_wasm)
+        );
+        let extensions = chain_spec::Extensions::try_get(&*spec).unwrap();
+        assert_eq!(extensions.para_id, Some(chain_spec::PARA_ID));
+    }
+
+    #[cfg(feature = "parachain")]
+    #[test]
+    fn the_parachain_leaves_consensus_to_the_relay_chain() {
+        let spec = chain_spec::local_testnet_config();
+        let storage = spec.build_storage().unwrap();
+
+        // The collators author the blocks of the parachain, the relay chain finalizes them.
+        for module in &["Babe", "GrandpaFinality", "Session", "Staking"] {
+            let prefix = sp_core::twox_128(module.as_bytes());
+            assert!(!storage.top.keys().any(|key| key.starts_with(&prefix)));
+        }
+        let para_id = sp_io::TestExternalities::new(storage)
+            .execute_with(node_runtime::ParachainInfo::parachain_id);
+        assert_eq!(para_id, chain_spec::PARA_ID.into());
+    }
+}
@@ -40,6 +40,8 @@ mod browser;
 mod cli;
 #[cfg(feature = "cli")]
 mod command;
+#[cfg(feature = "cli")]
+mod rehearse;
 
 #[cfg(feature = "browser")]
 pub use browser::*;
@@ -47,3 +49,5 @@ pub use browser::*;
 pub use cli::*;
 #[cfg(feature = "cli")]
 pub use command::*;
+#[cfg(feature = "cli")]
+pub use rehearse::*;
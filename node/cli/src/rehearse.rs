@@ -0,0 +1,178 @@
+// This file is part of Curio project.
+
+// Copyright (C) 2021 Mixbytes.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The `rehearse-upgrade` subcommand.
+//!
+//! Runs the upgrade hooks of a new runtime on a copy of the state of a chain, offline, between
+//! the checks of its migrations. The runtime must be built with the `try-runtime` feature.
+
+use codec::Decode;
+use frame_support::{traits::Get, weights::Weight};
+use sc_cli::{CliConfiguration, ImportParams, Result, SharedParams};
+use sc_service::Configuration;
+use serde::Deserialize;
+use sp_core::{
+    storage::{well_known_keys, StorageMap},
+    traits::{CallInWasm, MissingHostFunctions},
+};
+use sp_runtime::{BuildStorage, RuntimeString};
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+use structopt::StructOpt;
+
+/// The runtime API function run by the rehearsal.
+const REHEARSE_UPGRADE: &str = "UpgradeRehearsalApi_rehearse_upgrade";
+
+/// The `rehearse-upgrade` command used to check a runtime upgrade against the state of a chain.
+///
+/// The state is the genesis of `--chain`, which can be a chain spec written by `export-state`.
+/// A `--snapshot` replaces its main trie, keeping its child tries.
+#[derive(Debug, StructOpt)]
+pub struct RehearseUpgradeCmd {
+    /// Path to the WASM blob of the new runtime.
+    #[structopt(long, value_name = "PATH", parse(from_os_str))]
+    pub wasm: PathBuf,
+
+    /// Path to a snapshot of the main trie of the state: a JSON list of pairs of hex encoded
+    /// keys and values, as returned by the `state_getPairs` RPC, or an object of them.
+    #[structopt(long, value_name = "PATH", parse(from_os_str))]
+    pub snapshot: Option<PathBuf>,
+
+    #[allow(missing_docs)]
+    #[structopt(flatten)]
+    pub shared_params: SharedParams,
+
+    #[allow(missing_docs)]
+    #[structopt(flatten)]
+    pub import_params: ImportParams,
+}
+
+impl RehearseUpgradeCmd {
+    /// Run the rehearsal, reporting the weight of the upgrade or the check which failed.
+    pub fn run(&self, config: Configuration) -> Result<()> {
+        let wasm = fs::read(&self.wasm)?;
+        let mut storage = config.chain_spec.as_storage_builder().build_storage()?;
+        if let Some(path) = &self.snapshot {
+            storage.top = load_snapshot(path)?;
+        }
+        storage
+            .top
+            .insert(well_known_keys::CODE.to_vec(), wasm.clone());
+
+        let executor = node_executor::NativeExecutor::<node_executor::Executor>::new(
+            config.wasm_method,
+            config.default_heap_pages,
+            1,
+        );
+        let mut ext = sp_io::TestExternalities::new(storage);
+        let output = executor
+            .call_in_wasm(
+                &wasm,
+                None,
+                REHEARSE_UPGRADE,
+                &[],
+                &mut ext.ext(),
+                MissingHostFunctions::Disallow,
+            )
+            .map_err(|e| {
+                format!(
+                    "{}. Was the runtime built with `--features try-runtime`?",
+                    e
+                )
+            })?;
+        let weight = <std::result::Result<Weight, RuntimeString>>::decode(&mut &output[..])
+            .map_err(|e| format!("Invalid output of the runtime: {}", e))?
+            .map_err(|e| format!("The upgrade checks failed: {}", e))?;
+
+        let max_weight = node_runtime::MaximumBlockWeight::get();
+        println!(
+            "The upgrade passed its checks and weighs {} ({}% of the maximum block weight).",
+            weight,
+            weight.saturating_mul(100) / max_weight,
+        );
+        if weight > max_weight {
+            return Err("The upgrade does not fit in a block.".into());
+        }
+        Ok(())
+    }
+}
+
+/// A snapshot of the main trie of the state, with hex encoded keys and values.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Snapshot {
+    /// The list of pairs returned by the `state_getPairs` RPC.
+    Pairs(Vec<(String, String)>),
+    /// An object of keys and values.
+    Object(BTreeMap<String, String>),
+}
+
+/// Read a snapshot of the main trie of the state.
+fn load_snapshot(path: &Path) -> Result<StorageMap> {
+    parse_snapshot(&fs::read(path)?)
+        .map_err(|e| format!("Invalid snapshot {}: {}", path.display(), e).into())
+}
+
+/// Parse a snapshot of the main trie of the state from its JSON.
+fn parse_snapshot(json: &[u8]) -> std::result::Result<StorageMap, String> {
+    let pairs = match serde_json::from_slice(json).map_err(|e| e.to_string())? {
+        Snapshot::Pairs(pairs) => pairs,
+        Snapshot::Object(pairs) => pairs.into_iter().collect(),
+    };
+    let decode = |hex: &str| {
+        sp_core::bytes::from_hex(hex).map_err(|e| format!("invalid entry {}: {}", hex, e))
+    };
+    pairs
+        .iter()
+        .map(|(key, value)| Ok((decode(key)?, decode(value)?)))
+        .collect()
+}
+
+impl CliConfiguration for RehearseUpgradeCmd {
+    fn shared_params(&self) -> &SharedParams {
+        &self.shared_params
+    }
+
+    fn import_params(&self) -> Option<&ImportParams> {
+        Some(&self.import_params)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snapshots_are_parsed() {
+        let expected: StorageMap = vec![(vec![0x01, 0x02], vec![0x03]), (vec![0x04], vec![])]
+            .into_iter()
+            .collect();
+        assert_eq!(
+            parse_snapshot(br#"[["0x0102", "0x03"], ["0x04", "0x"]]"#),
+            Ok(expected.clone())
+        );
+        assert_eq!(
+            parse_snapshot(br#"{"0x0102": "0x03", "0x04": "0x"}"#),
+            Ok(expected)
+        );
+        assert!(parse_snapshot(br#"[["0x0102", "0xzz"]]"#).is_err());
+        assert!(parse_snapshot(br#"[["0x0102"]]"#).is_err());
+    }
+}
@@ -20,30 +20,45 @@
 #![allow(clippy::type_complexity)]
 //! Service implementation. Specialized wrapper over substrate service.
 
+#[cfg(not(feature = "parachain"))]
 use futures::prelude::*;
+#[cfg(not(feature = "parachain"))]
 use grandpa::{self, FinalityProofProvider as GrandpaFinalityProofProvider};
 use node_executor::Executor;
 use node_primitives::Block;
 use node_runtime::RuntimeApi;
+#[cfg(not(feature = "parachain"))]
 use sc_client_api::{ExecutorProvider, RemoteBackend};
+#[cfg(not(feature = "parachain"))]
 use sc_network::{Event, NetworkService};
+#[cfg(not(feature = "parachain"))]
+use sc_service::RpcHandlers;
 use sc_service::{
     config::{Configuration, Role},
     error::Error as ServiceError,
-    RpcHandlers, TaskManager,
+    TaskManager,
 };
+#[cfg(not(feature = "parachain"))]
 use sp_core::traits::BareCryptoStorePtr;
+#[cfg(not(feature = "parachain"))]
 use sp_inherents::InherentDataProviders;
+#[cfg(not(feature = "parachain"))]
 use sp_runtime::traits::Block as BlockT;
 use std::sync::Arc;
 
+// The services of standalone builds author and finalize the blocks with BABE and GRANDPA, the
+// collators of parachain builds author them on the relay chain, which finalizes them.
 type FullClient = sc_service::TFullClient<Block, RuntimeApi, Executor>;
 type FullBackend = sc_service::TFullBackend<Block>;
+#[cfg(not(feature = "parachain"))]
 type FullSelectChain = sc_consensus::LongestChain<FullBackend, Block>;
+#[cfg(not(feature = "parachain"))]
 type FullGrandpaBlockImport =
     grandpa::GrandpaBlockImport<FullBackend, Block, FullClient, FullSelectChain>;
+#[cfg(not(feature = "parachain"))]
 type LightClient = sc_service::TLightClient<Block, RuntimeApi, Executor>;
 
+#[cfg(not(feature = "parachain"))]
 pub fn new_partial(
     config: &Configuration,
 ) -> Result<
@@ -169,6 +184,7 @@ pub fn new_partial(
     })
 }
 
+#[cfg(not(feature = "parachain"))]
 pub struct NewFullBase {
     pub task_manager: TaskManager,
     pub inherent_data_providers: InherentDataProviders,
@@ -179,6 +195,7 @@ pub struct NewFullBase {
 }
 
 /// Creates a full service from the configuration.
+#[cfg(not(feature = "parachain"))]
 pub fn new_full_base(
     config: Configuration,
     with_startup_data: impl FnOnce(
@@ -372,10 +389,190 @@ pub fn new_full_base(
 }
 
 /// Builds a new service for a full client.
+#[cfg(not(feature = "parachain"))]
 pub fn new_full(config: Configuration) -> Result<TaskManager, ServiceError> {
     new_full_base(config, |_, _| ()).map(|NewFullBase { task_manager, .. }| task_manager)
 }
 
+/// Creates the components of a parachain node: the blocks are imported without BABE nor GRANDPA,
+/// the relay chain validators check and finalize them.
+#[cfg(feature = "parachain")]
+pub fn new_collator_partial(
+    config: &Configuration,
+) -> Result<
+    sc_service::PartialComponents<
+        FullClient,
+        FullBackend,
+        (),
+        sp_consensus::DefaultImportQueue<Block, FullClient>,
+        sc_transaction_pool::FullPool<Block, FullClient>,
+        (),
+    >,
+    ServiceError,
+> {
+    let (client, backend, keystore, task_manager) =
+        sc_service::new_full_parts::<Block, RuntimeApi, Executor>(&config)?;
+    let client = Arc::new(client);
+
+    let transaction_pool = sc_transaction_pool::BasicPool::new_full(
+        config.transaction_pool.clone(),
+        config.prometheus_registry(),
+        task_manager.spawn_handle(),
+        client.clone(),
+    );
+
+    let inherent_data_providers = sp_inherents::InherentDataProviders::new();
+    inherent_data_providers
+        .register_provider(sp_timestamp::InherentDataProvider)
+        .map_err(|e| ServiceError::Other(e.to_string()))?;
+
+    let import_queue = cumulus_consensus::import_queue::import_queue(
+        client.clone(),
+        client.clone(),
+        inherent_data_providers.clone(),
+        &task_manager.spawn_handle(),
+        config.prometheus_registry(),
+    )?;
+
+    Ok(sc_service::PartialComponents {
+        client,
+        backend,
+        task_manager,
+        keystore,
+        select_chain: (),
+        import_queue,
+        transaction_pool,
+        inherent_data_providers,
+        other: (),
+    })
+}
+
+/// Starts a parachain node with an embedded relay chain node, configured by `relay_chain_config`.
+/// It collates the blocks of `para_id` when `collate` is set, and only follows them otherwise.
+#[cfg(feature = "parachain")]
+pub fn start_collator(
+    config: Configuration,
+    collator_key: polkadot_primitives::v1::CollatorPair,
+    relay_chain_config: Configuration,
+    para_id: cumulus_primitives::ParaId,
+    collate: bool,
+) -> Result<TaskManager, ServiceError> {
+    use sp_core::Pair;
+
+    if matches!(config.role, Role::Light) {
+        return Err("Light clients are not supported by parachains.".into());
+    }
+
+    let config = cumulus_service::prepare_node_config(config);
+    let sc_service::PartialComponents {
+        client,
+        backend,
+        mut task_manager,
+        import_queue,
+        keystore,
+        transaction_pool,
+        inherent_data_providers,
+        ..
+    } = new_collator_partial(&config)?;
+
+    let relay_chain_node =
+        cumulus_service::build_polkadot_full_node(relay_chain_config, collator_key.public())
+            .map_err(|e| ServiceError::Other(e.to_string()))?;
+    let block_announce_validator = cumulus_network::build_block_announce_validator(
+        relay_chain_node.client.clone(),
+        para_id,
+        Box::new(relay_chain_node.network.clone()),
+        relay_chain_node.backend.clone(),
+    );
+
+    let prometheus_registry = config.prometheus_registry().cloned();
+    let (network, network_status_sinks, system_rpc_tx, network_starter) =
+        sc_service::build_network(sc_service::BuildNetworkParams {
+            config: &config,
+            client: client.clone(),
+            transaction_pool: transaction_pool.clone(),
+            spawn_handle: task_manager.spawn_handle(),
+            import_queue,
+            on_demand: None,
+            block_announce_validator_builder: Some(Box::new(|_| block_announce_validator)),
+            finality_proof_request_builder: None,
+            finality_proof_provider: None,
+        })?;
+
+    let rpc_extensions_builder = {
+        let client = client.clone();
+        let pool = transaction_pool.clone();
+
+        move |deny_unsafe, _| {
+            node_rpc::create_parachain(node_rpc::ParachainDeps {
+                client: client.clone(),
+                pool: pool.clone(),
+                deny_unsafe,
+            })
+        }
+    };
+
+    sc_service::spawn_tasks(sc_service::SpawnTasksParams {
+        config,
+        backend: backend.clone(),
+        client: client.clone(),
+        keystore,
+        network: network.clone(),
+        rpc_extensions_builder: Box::new(rpc_extensions_builder),
+        transaction_pool: transaction_pool.clone(),
+        task_manager: &mut task_manager,
+        on_demand: None,
+        remote_blockchain: None,
+        telemetry_connection_sinks: sc_service::TelemetryConnectionSinks::default(),
+        network_status_sinks,
+        system_rpc_tx,
+    })?;
+
+    let announce_block = {
+        let network = network.clone();
+        Arc::new(move |hash, data| network.announce_block(hash, data))
+    };
+
+    if collate {
+        let proposer_factory = sc_basic_authorship::ProposerFactory::new(
+            client.clone(),
+            transaction_pool,
+            prometheus_registry.as_ref(),
+        );
+        let spawner = task_manager.spawn_handle();
+        let relay_chain_backend = relay_chain_node.backend.clone();
+
+        let collator = cumulus_service::start_collator(cumulus_service::StartCollatorParams {
+            para_id,
+            proposer_factory,
+            inherent_data_providers,
+            backend,
+            block_import: client.clone(),
+            block_status: client.clone(),
+            client,
+            announce_block,
+            spawner,
+            collator_key,
+            polkadot_full_node: relay_chain_node,
+            task_manager: &mut task_manager,
+            polkadot_backend: relay_chain_backend,
+        });
+        futures::executor::block_on(collator)?;
+    } else {
+        cumulus_service::start_full_node(cumulus_service::StartFullNodeParams {
+            para_id,
+            client,
+            announce_block,
+            polkadot_full_node: relay_chain_node,
+            task_manager: &mut task_manager,
+        })?;
+    }
+
+    network_starter.start_network();
+    Ok(task_manager)
+}
+
+#[cfg(not(feature = "parachain"))]
 pub fn new_light_base(
     config: Configuration,
 ) -> Result<
@@ -497,11 +694,12 @@ pub fn new_light_base(
 }
 
 /// Builds a new service for a light client.
+#[cfg(not(feature = "parachain"))]
 pub fn new_light(config: Configuration) -> Result<TaskManager, ServiceError> {
     new_light_base(config).map(|(task_manager, _, _, _, _)| task_manager)
 }
 
-#[cfg(test)]
+#[cfg(all(test, not(feature = "parachain")))]
 mod tests {
     use crate::service::{new_full_base, new_light_base, NewFullBase};
     use codec::Encode;
@@ -707,7 +905,9 @@ mod tests {
                 let check_era = frame_system::CheckEra::from(Era::Immortal);
                 let check_nonce = frame_system::CheckNonce::from(index);
                 let check_weight = frame_system::CheckWeight::new();
-                let payment = pallet_transaction_payment::ChargeTransactionPayment::from(0);
+                let payment = pallet_asset_tx_payment::ChargeAssetTxPayment::from(0, None);
+                let relayer_vote = pallet_dummy::CheckRelayerVote::new();
+                let kyc = pallet_kyc::CheckKyc::new();
                 let extra = (
                     check_spec_version,
                     check_tx_version,
@@ -716,6 +916,8 @@ mod tests {
                     check_nonce,
                     check_weight,
                     payment,
+                    relayer_vote,
+                    kyc,
                 );
                 let raw_payload = SignedPayload::from_raw(
                     function,
@@ -728,6 +930,8 @@ mod tests {
                         (),
                         (),
                         (),
+                        (),
+                        (),
                     ),
                 );
                 let signature = raw_payload.using_encoded(|payload| signer.sign(payload));
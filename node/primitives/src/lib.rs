@@ -42,6 +42,9 @@ pub type AccountIndex = u32;
 /// Balance of an account.
 pub type Balance = u128;
 
+/// Identifier of an asset, other than the native currency, held on the chain.
+pub type AssetId = u32;
+
 /// Type used for expressing timestamp.
 pub type Moment = u64;
 
@@ -17,16 +17,234 @@
 
 //! Some configurable implementations as associated type for the substrate runtime.
 
-use crate::{Authorship, Balances, NegativeImbalance};
-use frame_support::traits::{Currency, OnUnbalanced};
-use node_primitives::Balance;
-use sp_runtime::traits::Convert;
+#[cfg(not(feature = "parachain"))]
+use crate::Authorship;
+use crate::{
+    Assets, Balances, Call, Indices, Kyc, Multisig, NegativeImbalance, Runtime, Treasury, Utility,
+};
+use codec::Decode;
+use frame_support::traits::{Currency, Get, OnUnbalanced};
+use node_primitives::{AccountId, AssetId, Balance};
+use pallet_dummy::{DepositId, MultiAsset, RecipientFilter, VerifyDeposit, WrappedVotes};
+use pallet_kyc::KycGate;
+use sp_runtime::{
+    traits::{Convert, StaticLookup, Zero},
+    DispatchResult,
+};
+use sp_std::prelude::*;
 
 pub struct Author;
 impl OnUnbalanced<NegativeImbalance> for Author {
+    #[cfg(not(feature = "parachain"))]
     fn on_nonzero_unbalanced(amount: NegativeImbalance) {
         Balances::resolve_creating(&Authorship::author(), amount);
     }
+
+    // The runtime of a parachain does not know the collator of a block, the share of the author
+    // goes to the treasury.
+    #[cfg(feature = "parachain")]
+    fn on_nonzero_unbalanced(amount: NegativeImbalance) {
+        Treasury::on_unbalanced(amount);
+    }
+}
+
+/// The account of the treasury.
+pub struct TreasuryAccount;
+impl Get<AccountId> for TreasuryAccount {
+    fn get() -> AccountId {
+        Treasury::account_id()
+    }
+}
+
+/// Bridged assets besides the native currency, held in the multi-asset pallet.
+pub struct BridgedAssets;
+impl MultiAsset<AccountId, AssetId, Balance> for BridgedAssets {
+    fn free_balance(asset: AssetId, who: &AccountId) -> Balance {
+        Assets::balance(asset, who)
+    }
+
+    fn transfer(
+        asset: AssetId,
+        source: &AccountId,
+        dest: &AccountId,
+        amount: Balance,
+    ) -> DispatchResult {
+        // The bridge accounts for exact amounts, so a remainder below the minimum balance may
+        // only be left by moving the whole balance.
+        let keep_alive = Assets::balance(asset, source) != amount;
+        Assets::do_transfer(asset, source, dest, amount, keep_alive).map(|_| ())
+    }
+}
+
+/// Lets the bridge mint to the verified investors only.
+pub struct VerifiedInvestors;
+impl RecipientFilter<AccountId> for VerifiedInvestors {
+    fn allows(who: &AccountId) -> bool {
+        Kyc::is_verified(who)
+    }
+
+    #[cfg(feature = "runtime-benchmarks")]
+    fn allow(who: &AccountId) {
+        use pallet_kyc::{Investor, KycStatus};
+        use sp_runtime::traits::Bounded;
+        let expiry = Bounded::max_value();
+        pallet_kyc::Investors::<Runtime>::insert(
+            who,
+            Investor {
+                status: KycStatus::Verified,
+                expiry,
+            },
+        );
+    }
+}
+
+/// Tells which accounts of the bridge calls and of the transfers must be whitelisted.
+///
+/// Wrapped calls are checked as if submitted by the account they are dispatched from: the
+/// proxied account of a proxy, the derivative account of `as_derivative`, the multisig account
+/// of a multisig and the lost account of `as_recovered`. The calls of a batch are checked as
+/// if submitted by the sender of the batch.
+///
+/// Value sent to a contract, by a call or as the endowment of a new contract, is a transfer as
+/// well. Transfers to an account index which is not assigned are rejected.
+pub struct InvestorGate;
+impl KycGate<AccountId, Call> for InvestorGate {
+    fn required(who: &AccountId, call: &Call) -> Option<Vec<AccountId>> {
+        match call {
+            // Relayers vote on behalf of the recipient of the mint.
+            Call::Dummy(pallet_dummy::Call::vote_mint(_, _, account, _)) => {
+                Some(vec![account.clone()])
+            }
+            Call::Dummy(pallet_dummy::Call::mint_with_proof(proof)) => Some(
+                <<Runtime as pallet_dummy::Trait>::DepositVerifier as VerifyDeposit<
+                    AccountId,
+                    Balance,
+                >>::verify(proof)
+                .map(|deposit| vec![deposit.account])
+                .unwrap_or_default(),
+            ),
+            Call::Dummy(pallet_dummy::Call::burn(..)) => Some(vec![who.clone()]),
+            _ => Self::nested(who, call, Self::required),
+        }
+    }
+
+    fn required_for_transfer(who: &AccountId, call: &Call) -> Option<Vec<AccountId>> {
+        match call {
+            Call::Balances(pallet_balances::Call::transfer(dest, _))
+            | Call::Balances(pallet_balances::Call::transfer_keep_alive(dest, _))
+            | Call::Vesting(pallet_vesting::Call::vested_transfer(dest, _))
+            | Call::Assets(pallet_multi_asset::Call::transfer(_, dest, _))
+            | Call::Assets(pallet_multi_asset::Call::transfer_keep_alive(_, dest, _)) => {
+                Self::sender_and(who, dest)
+            }
+            Call::Contracts(pallet_contracts::Call::call(dest, value, ..)) if !value.is_zero() => {
+                Self::sender_and(who, dest)
+            }
+            Call::Contracts(pallet_contracts::Call::instantiate(endowment, ..))
+                if !endowment.is_zero() =>
+            {
+                Some(vec![who.clone()])
+            }
+            _ => Self::nested(who, call, Self::required_for_transfer),
+        }
+    }
+}
+
+impl InvestorGate {
+    /// `who` and the account of `dest`, or `None` if `dest` is an index which is not assigned.
+    fn sender_and(
+        who: &AccountId,
+        dest: &<Indices as StaticLookup>::Source,
+    ) -> Option<Vec<AccountId>> {
+        let dest = Indices::lookup(dest.clone()).ok()?;
+        Some(vec![who.clone(), dest])
+    }
+
+    /// The accounts `required` by the calls wrapped in `call`.
+    fn nested(
+        who: &AccountId,
+        call: &Call,
+        required: fn(&AccountId, &Call) -> Option<Vec<AccountId>>,
+    ) -> Option<Vec<AccountId>> {
+        match call {
+            Call::Proxy(pallet_proxy::Call::proxy(real, _, call))
+            | Call::Proxy(pallet_proxy::Call::proxy_announced(_, real, _, call)) => {
+                required(real, call)
+            }
+            Call::Utility(pallet_utility::Call::batch(calls))
+            | Call::Utility(pallet_utility::Call::batch_all(calls)) => {
+                let required = calls
+                    .iter()
+                    .map(|call| required(who, call))
+                    .collect::<Option<Vec<_>>>()?;
+                Some(required.into_iter().flatten().collect())
+            }
+            Call::Utility(pallet_utility::Call::as_derivative(index, call)) => {
+                required(&Utility::derivative_account_id(who.clone(), *index), call)
+            }
+            Call::Multisig(pallet_multisig::Call::as_multi_threshold_1(others, call)) => {
+                required(&Self::multisig(who, others, 1), call)
+            }
+            Call::Multisig(pallet_multisig::Call::as_multi(threshold, others, _, call, ..)) => {
+                Call::decode(&mut &call[..])
+                    .ok()
+                    .map_or(Some(Vec::new()), |call| {
+                        required(&Self::multisig(who, others, *threshold), &call)
+                    })
+            }
+            // The final approval dispatches the call stored by an earlier `as_multi`.
+            Call::Multisig(pallet_multisig::Call::approve_as_multi(
+                threshold,
+                others,
+                _,
+                call_hash,
+                _,
+            )) => pallet_multisig::Calls::<Runtime>::get(call_hash)
+                .and_then(|(call, _, _)| Call::decode(&mut &call[..]).ok())
+                .map_or(Some(Vec::new()), |call| {
+                    required(&Self::multisig(who, others, *threshold), &call)
+                }),
+            Call::Recovery(pallet_recovery::Call::as_recovered(account, call)) => {
+                required(account, call)
+            }
+            _ => Some(Vec::new()),
+        }
+    }
+
+    /// The multisig account of `who` and `others` with `threshold`.
+    fn multisig(who: &AccountId, others: &[AccountId], threshold: u16) -> AccountId {
+        let mut signatories = others.to_vec();
+        signatories.push(who.clone());
+        signatories.sort();
+        Multisig::multi_account_id(&signatories, threshold)
+    }
+}
+
+/// Finds the relayer votes made through a proxy or a batch, cast by the proxied account.
+pub struct ProxiedVotes;
+impl WrappedVotes<AccountId, Call> for ProxiedVotes {
+    fn wrapped_votes(who: &AccountId, call: &Call) -> Vec<(AccountId, DepositId)> {
+        let wrapped: Vec<(&AccountId, &Call)> = match call {
+            Call::Proxy(pallet_proxy::Call::proxy(real, _, call))
+            | Call::Proxy(pallet_proxy::Call::proxy_announced(_, real, _, call)) => {
+                vec![(real, call)]
+            }
+            Call::Utility(pallet_utility::Call::batch(calls))
+            | Call::Utility(pallet_utility::Call::batch_all(calls)) => {
+                calls.iter().map(|call| (who, call)).collect()
+            }
+            _ => Vec::new(),
+        };
+        wrapped
+            .into_iter()
+            .flat_map(|(voter, call)| match call {
+                Call::Dummy(pallet_dummy::Call::vote_mint(_, deposit_id, _, _)) => {
+                    vec![(voter.clone(), *deposit_id)]
+                }
+                _ => Self::wrapped_votes(voter, call),
+            })
+            .collect()
+    }
 }
 
 /// Struct that handles the conversion of Balance -> `u64`. This is used for staking's election
@@ -26,9 +26,11 @@ use sp_std::prelude::*;
 
 use codec::{Decode, Encode};
 use frame_support::traits::InstanceFilter;
+#[cfg(not(feature = "parachain"))]
+use frame_support::traits::KeyOwnerProofSystem;
 use frame_support::{
     construct_runtime, debug, parameter_types,
-    traits::{Currency, Imbalance, KeyOwnerProofSystem, LockIdentifier, OnUnbalanced, Randomness},
+    traits::{Currency, Imbalance, LockIdentifier, OnUnbalanced, Randomness},
     weights::{
         constants::{BlockExecutionWeight, ExtrinsicBaseWeight, RocksDbWeight, WEIGHT_PER_SECOND},
         IdentityFee, Weight,
@@ -37,27 +39,34 @@ use frame_support::{
 };
 use frame_system::{EnsureOneOf, EnsureRoot};
 pub use node_primitives::{AccountId, Signature};
-use node_primitives::{AccountIndex, Balance, BlockNumber, Hash, Index, Moment};
+use node_primitives::{AccountIndex, AssetId, Balance, BlockNumber, Hash, Index, Moment};
 use pallet_contracts_rpc_runtime_api::ContractExecResult;
+#[cfg(not(feature = "parachain"))]
 use pallet_grandpa::fg_primitives;
+#[cfg(not(feature = "parachain"))]
 use pallet_grandpa::{AuthorityId as GrandpaId, AuthorityList as GrandpaAuthorityList};
+#[cfg(not(feature = "parachain"))]
 use pallet_im_online::sr25519::AuthorityId as ImOnlineId;
+#[cfg(not(feature = "parachain"))]
 use pallet_session::historical as pallet_session_historical;
 pub use pallet_transaction_payment::{Multiplier, TargetedFeeAdjustment};
 use pallet_transaction_payment_rpc_runtime_api::RuntimeDispatchInfo;
 use sp_api::impl_runtime_apis;
+#[cfg(not(feature = "parachain"))]
 use sp_authority_discovery::AuthorityId as AuthorityDiscoveryId;
 use sp_core::{
     crypto::KeyTypeId,
     u32_trait::{_1, _2, _3, _4},
-    OpaqueMetadata,
+    OpaqueMetadata, U256,
 };
 use sp_inherents::{CheckInherentsResult, InherentData};
+#[cfg(not(feature = "parachain"))]
 use sp_runtime::curve::PiecewiseLinear;
 use sp_runtime::traits::{
-    self, BlakeTwo256, Block as BlockT, ConvertInto, NumberFor, OpaqueKeys, SaturatedConversion,
-    Saturating, StaticLookup,
+    self, BlakeTwo256, Block as BlockT, ConvertInto, SaturatedConversion, Saturating, StaticLookup,
 };
+#[cfg(not(feature = "parachain"))]
+use sp_runtime::traits::{NumberFor, OpaqueKeys};
 use sp_runtime::transaction_validity::{
     TransactionPriority, TransactionSource, TransactionValidity,
 };
@@ -81,7 +90,10 @@ pub use sp_runtime::BuildStorage;
 
 /// Implementations of some helper traits passed into runtime modules as associated types.
 pub mod impls;
-use impls::{Author, CurrencyToVoteHandler};
+use impls::{
+    Author, BridgedAssets, CurrencyToVoteHandler, InvestorGate, ProxiedVotes, TreasuryAccount,
+    VerifiedInvestors,
+};
 
 /// Constant values used within the runtime.
 pub mod constants;
@@ -91,6 +103,12 @@ use sp_runtime::generic::Era;
 /// Weights for pallets used in the runtime.
 mod weights;
 
+/// Storage migrations run on runtime upgrades.
+pub mod migrations;
+
+/// Placeholder keeping the call indices of the modules declared after the removed sudo module.
+pub mod removed_sudo;
+
 // Make the WASM binary available.
 #[cfg(feature = "std")]
 include!(concat!(env!("OUT_DIR"), "/wasm_binary.rs"));
@@ -114,10 +132,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
     // and set impl_version to 0. If only runtime
     // implementation changes and behavior does not, then leave spec_version as
     // is and increment impl_version.
-    spec_version: 1,
-    impl_version: 1,
+    spec_version: 2,
+    impl_version: 0,
     apis: RUNTIME_API_VERSIONS,
-    transaction_version: 1,
+    transaction_version: 2,
 };
 
 /// Native version.
@@ -135,14 +153,16 @@ pub struct DealWithFees;
 impl OnUnbalanced<NegativeImbalance> for DealWithFees {
     fn on_unbalanceds<B>(mut fees_then_tips: impl Iterator<Item = NegativeImbalance>) {
         if let Some(fees) = fees_then_tips.next() {
-            // for fees, 80% to treasury, 20% to author
-            let mut split = fees.ration(80, 20);
+            // for fees and tips, the shares set by governance in the parameters pallet;
+            // the burned shares are dropped
+            let (mut treasury, mut author, _) = Parameters::fee_split().apply(fees);
             if let Some(tips) = fees_then_tips.next() {
-                // for tips, if any, 80% to treasury, 20% to author (though this can be anything)
-                tips.ration_merge_into(80, 20, &mut split);
+                let (tips_treasury, tips_author, _) = Parameters::tip_split().apply(tips);
+                treasury.subsume(tips_treasury);
+                author.subsume(tips_author);
             }
-            Treasury::on_unbalanced(split.0);
-            Author::on_unbalanced(split.1);
+            Treasury::on_unbalanced(treasury);
+            Author::on_unbalanced(author);
         }
     }
 }
@@ -235,6 +255,7 @@ pub enum ProxyType {
     NonTransfer,
     Governance,
     Staking,
+    Bridge,
 }
 impl Default for ProxyType {
     fn default() -> Self {
@@ -250,6 +271,7 @@ impl InstanceFilter<Call> for ProxyType {
                 Call::Balances(..)
                     | Call::Vesting(pallet_vesting::Call::vested_transfer(..))
                     | Call::Indices(pallet_indices::Call::transfer(..))
+                    | Call::Dummy(pallet_dummy::Call::burn(..))
             ),
             ProxyType::Governance => matches!(
                 c,
@@ -260,7 +282,17 @@ impl InstanceFilter<Call> for ProxyType {
                     | Call::Elections(..)
                     | Call::Treasury(..)
             ),
+            #[cfg(not(feature = "parachain"))]
             ProxyType::Staking => matches!(c, Call::Staking(..)),
+            // Parachains have no staking, the variant keeps the encoding of the other proxy types.
+            #[cfg(feature = "parachain")]
+            ProxyType::Staking => false,
+            ProxyType::Bridge => matches!(
+                c,
+                Call::Dummy(pallet_dummy::Call::vote_mint(..))
+                    | Call::Dummy(pallet_dummy::Call::mint_with_proof(..))
+                    | Call::Dummy(pallet_dummy::Call::confirm_release(..))
+            ),
         }
     }
     fn is_superset(&self, o: &Self) -> bool {
@@ -305,11 +337,13 @@ impl pallet_scheduler::Trait for Runtime {
     type WeightInfo = weights::pallet_scheduler::WeightInfo;
 }
 
+#[cfg(not(feature = "parachain"))]
 parameter_types! {
     pub const EpochDuration: u64 = EPOCH_DURATION_IN_SLOTS;
     pub const ExpectedBlockTime: Moment = MILLISECS_PER_BLOCK;
 }
 
+#[cfg(not(feature = "parachain"))]
 impl pallet_babe::Trait for Runtime {
     type EpochDuration = EpochDuration;
     type ExpectedBlockTime = ExpectedBlockTime;
@@ -384,15 +418,21 @@ parameter_types! {
 
 impl pallet_timestamp::Trait for Runtime {
     type Moment = Moment;
+    #[cfg(not(feature = "parachain"))]
     type OnTimestampSet = Babe;
+    // The blocks of a parachain have no BABE slot, the relay chain validators check them.
+    #[cfg(feature = "parachain")]
+    type OnTimestampSet = ();
     type MinimumPeriod = MinimumPeriod;
     type WeightInfo = weights::pallet_timestamp::WeightInfo;
 }
 
+#[cfg(not(feature = "parachain"))]
 parameter_types! {
     pub const UncleGenerations: BlockNumber = 5;
 }
 
+#[cfg(not(feature = "parachain"))]
 impl pallet_authorship::Trait for Runtime {
     type FindAuthor = pallet_session::FindAccountFromAuthorIndex<Self, Babe>;
     type UncleGenerations = UncleGenerations;
@@ -400,6 +440,7 @@ impl pallet_authorship::Trait for Runtime {
     type EventHandler = (Staking, ImOnline);
 }
 
+#[cfg(not(feature = "parachain"))]
 impl_opaque_keys! {
     pub struct SessionKeys {
         pub grandpa: Grandpa,
@@ -409,10 +450,19 @@ impl_opaque_keys! {
     }
 }
 
+// The collators of a parachain have no session keys, the relay chain validators check and
+// finalize its blocks.
+#[cfg(feature = "parachain")]
+impl_opaque_keys! {
+    pub struct SessionKeys {}
+}
+
+#[cfg(not(feature = "parachain"))]
 parameter_types! {
     pub const DisabledValidatorsThreshold: Perbill = Perbill::from_percent(17);
 }
 
+#[cfg(not(feature = "parachain"))]
 impl pallet_session::Trait for Runtime {
     type Event = Event;
     type ValidatorId = <Self as frame_system::Trait>::AccountId;
@@ -426,11 +476,13 @@ impl pallet_session::Trait for Runtime {
     type WeightInfo = weights::pallet_session::WeightInfo;
 }
 
+#[cfg(not(feature = "parachain"))]
 impl pallet_session::historical::Trait for Runtime {
     type FullIdentification = pallet_staking::Exposure<AccountId, Balance>;
     type FullIdentificationOf = pallet_staking::ExposureOf<Runtime>;
 }
 
+#[cfg(not(feature = "parachain"))]
 pallet_staking_reward_curve::build! {
     const REWARD_CURVE: PiecewiseLinear<'static> = curve!(
         min_inflation: 0_020_000,
@@ -443,6 +495,7 @@ pallet_staking_reward_curve::build! {
     );
 }
 
+#[cfg(not(feature = "parachain"))]
 parameter_types! {
     pub const SessionsPerEra: sp_staking::SessionIndex = 6;
     pub const BondingDuration: pallet_staking::EraIndex = 24 * 28;
@@ -455,6 +508,7 @@ parameter_types! {
     pub MinSolutionScoreBump: Perbill = Perbill::from_rational_approximation(5u32, 10_000);
 }
 
+#[cfg(not(feature = "parachain"))]
 impl pallet_staking::Trait for Runtime {
     type Currency = Balances;
     type UnixTime = Timestamp;
@@ -612,6 +666,13 @@ type EnsureRootOrHalfCouncil = EnsureOneOf<
     EnsureRoot<AccountId>,
     pallet_collective::EnsureProportionMoreThan<_1, _2, AccountId, CouncilCollective>,
 >;
+/// The emergency path, acting without waiting for a referendum: the two thirds of the technical
+/// committee which may fast-track one.
+type EnsureRootOrTwoThirdsTechnicalCommittee = EnsureOneOf<
+    AccountId,
+    EnsureRoot<AccountId>,
+    pallet_collective::EnsureProportionAtLeast<_2, _3, AccountId, TechnicalCollective>,
+>;
 impl pallet_membership::Trait<pallet_membership::Instance1> for Runtime {
     type Event = Event;
     type AddOrigin = EnsureRootOrHalfCouncil;
@@ -701,11 +762,9 @@ impl pallet_contracts::Trait for Runtime {
     type WeightPrice = pallet_transaction_payment::Module<Self>;
 }
 
-impl pallet_sudo::Trait for Runtime {
-    type Event = Event;
-    type Call = Call;
-}
+impl removed_sudo::Trait for Runtime {}
 
+#[cfg(not(feature = "parachain"))]
 parameter_types! {
     pub const SessionDuration: BlockNumber = EPOCH_DURATION_IN_SLOTS as _;
     pub const ImOnlineUnsignedPriority: TransactionPriority = TransactionPriority::max_value();
@@ -745,7 +804,9 @@ where
             frame_system::CheckEra::<Runtime>::from(era),
             frame_system::CheckNonce::<Runtime>::from(nonce),
             frame_system::CheckWeight::<Runtime>::new(),
-            pallet_transaction_payment::ChargeTransactionPayment::<Runtime>::from(tip),
+            pallet_asset_tx_payment::ChargeAssetTxPayment::<Runtime>::from(tip, None),
+            pallet_dummy::CheckRelayerVote::<Runtime>::new(),
+            pallet_kyc::CheckKyc::<Runtime>::new(),
         );
         let raw_payload = SignedPayload::new(call, extra)
             .map_err(|e| {
@@ -772,6 +833,7 @@ where
     type OverarchingCall = Call;
 }
 
+#[cfg(not(feature = "parachain"))]
 impl pallet_im_online::Trait for Runtime {
     type AuthorityId = ImOnlineId;
     type Event = Event;
@@ -781,10 +843,12 @@ impl pallet_im_online::Trait for Runtime {
     type WeightInfo = weights::pallet_im_online::WeightInfo;
 }
 
+#[cfg(not(feature = "parachain"))]
 parameter_types! {
     pub OffencesWeightSoftLimit: Weight = Perbill::from_percent(60) * MaximumBlockWeight::get();
 }
 
+#[cfg(not(feature = "parachain"))]
 impl pallet_offences::Trait for Runtime {
     type Event = Event;
     type IdentificationTuple = pallet_session::historical::IdentificationTuple<Self>;
@@ -792,8 +856,10 @@ impl pallet_offences::Trait for Runtime {
     type WeightSoftLimit = OffencesWeightSoftLimit;
 }
 
+#[cfg(not(feature = "parachain"))]
 impl pallet_authority_discovery::Trait for Runtime {}
 
+#[cfg(not(feature = "parachain"))]
 impl pallet_grandpa::Trait for Runtime {
     type Event = Event;
     type Call = Call;
@@ -814,11 +880,13 @@ impl pallet_grandpa::Trait for Runtime {
     type WeightInfo = ();
 }
 
+#[cfg(not(feature = "parachain"))]
 parameter_types! {
     pub const WindowSize: BlockNumber = 101;
     pub const ReportLatency: BlockNumber = 1000;
 }
 
+#[cfg(not(feature = "parachain"))]
 impl pallet_finality_tracker::Trait for Runtime {
     type OnFinalizationStalled = ();
     type WindowSize = WindowSize;
@@ -909,15 +977,124 @@ impl pallet_vesting::Trait for Runtime {
 
 parameter_types! {
      pub const BridgeModuleId: ModuleId = ModuleId(*b"br/curio");
+     pub const NativeAssetId: AssetId = 0;
+     pub const MaxBridgeRelayers: u32 = 32;
+     pub const BridgeProposalLifetime: BlockNumber = 1 * DAYS;
+     pub const BridgeVotePriority: TransactionPriority = TransactionPriority::max_value() / 2;
+     pub const BridgeTransferHistoryDepth: BlockNumber = 30 * DAYS;
+     pub const MaxBridgeTransfersPerAccount: u32 = 50;
 }
 impl pallet_dummy::Trait for Runtime {
     type Event = Event;
+    type AuthorityId = pallet_dummy::crypto::RelayerAuthId;
     type Currency = Balances;
-    type WeightInfo = ();
-    type MintOrigin = EnsureRoot<AccountId>; // EnsureRootOrHalfCouncil;
+    type AssetId = AssetId;
+    type NativeAssetId = NativeAssetId;
+    type Assets = BridgedAssets;
+    type DepositVerifier = EthLightClient;
+    type Recipients = VerifiedInvestors;
+    type OnFee = Treasury;
+    type AssetFeeDestination = TreasuryAccount;
+    type WeightInfo = weights::pallet_dummy::WeightInfo;
+    type MintOrigin = EnsureRootOrHalfCouncil;
+    type AdminOrigin = EnsureRootOrHalfCouncil;
+    type PauseOrigin = EnsureRootOrTwoThirdsTechnicalCommittee;
+    type MaxRelayers = MaxBridgeRelayers;
+    type ProposalLifetime = BridgeProposalLifetime;
+    type RelayerVotePriority = BridgeVotePriority;
+    type WrappedVotes = ProxiedVotes;
+    type ScheduledCall = Call;
+    type PalletsOrigin = OriginCaller;
+    type Scheduler = Scheduler;
+    type CancelOrigin = EnsureRootOrTwoThirdsTechnicalCommittee;
+    type PotOrigin = EnsureOneOf<
+        AccountId,
+        EnsureRoot<AccountId>,
+        pallet_collective::EnsureMembers<_4, AccountId, CouncilCollective>,
+    >;
+    type RefillSource = TreasuryAccount;
+    type TransferHistoryDepth = BridgeTransferHistoryDepth;
+    type MaxAccountTransfers = MaxBridgeTransfersPerAccount;
     type ModuleId = BridgeModuleId;
 }
 
+parameter_types! {
+    pub EthMinimumDifficulty: U256 = U256::from(131_072);
+    // Set by Muir Glacier. Raise it with each Ethereum fork delaying the bomb.
+    pub const EthDifficultyBombDelay: u64 = 9_000_000;
+    pub const EthConfirmationDepth: u32 = 64;
+    // About a day of Ethereum blocks.
+    pub const EthHeadersToKeep: u32 = 8192;
+}
+
+impl pallet_eth_light_client::Trait for Runtime {
+    type Event = Event;
+    // The relayers keep the light client up to date, headers are verified on import.
+    type Importers = pallet_dummy::RelayerSet<Runtime>;
+    type MinimumDifficulty = EthMinimumDifficulty;
+    type DifficultyBombDelay = EthDifficultyBombDelay;
+    type GovernanceOrigin = EnsureRootOrHalfCouncil;
+    type ConfirmationDepth = EthConfirmationDepth;
+    type HeadersToKeep = EthHeadersToKeep;
+    type WeightInfo = ();
+}
+
+parameter_types! {
+    pub const MaxKycProviders: u32 = 16;
+}
+
+impl pallet_kyc::Trait for Runtime {
+    type Event = Event;
+    type ManagerOrigin = EnsureRootOrHalfCouncil;
+    type MaxProviders = MaxKycProviders;
+    type Gate = InvestorGate;
+    type WeightInfo = ();
+}
+
+parameter_types! {
+    pub const AssetStringLimit: u32 = 50;
+}
+
+impl pallet_multi_asset::Trait for Runtime {
+    type Event = Event;
+    type Balance = Balance;
+    type AssetId = AssetId;
+    type CreateOrigin = EnsureRootOrHalfCouncil;
+    type StringLimit = AssetStringLimit;
+    type WeightInfo = weights::pallet_multi_asset::WeightInfo;
+}
+
+parameter_types! {
+    pub const AssetFeePotId: ModuleId = ModuleId(*b"py/asset");
+}
+
+impl pallet_asset_tx_payment::Trait for Runtime {
+    type Event = Event;
+    type RateOrigin = EnsureRootOrHalfCouncil;
+    type Oracle = ();
+    type ModuleId = AssetFeePotId;
+    type WeightInfo = ();
+}
+
+impl pallet_parameters::Trait for Runtime {
+    type Event = Event;
+    type ParameterOrigin = EnsureRootOrHalfCouncil;
+    type WeightInfo = ();
+}
+
+#[cfg(feature = "parachain")]
+impl cumulus_parachain_system::Trait for Runtime {
+    type Event = Event;
+    type OnValidationData = ();
+    type SelfParaId = parachain_info::Module<Runtime>;
+    type DownwardMessageHandlers = ();
+    type HrmpMessageHandlers = ();
+}
+
+#[cfg(feature = "parachain")]
+impl parachain_info::Trait for Runtime {}
+
+#[cfg(not(feature = "parachain"))]
 construct_runtime!(
     pub enum Runtime where
         Block = Block,
@@ -943,7 +1120,8 @@ construct_runtime!(
         Grandpa: pallet_grandpa::{Module, Call, Storage, Config, Event, ValidateUnsigned},
         Treasury: pallet_treasury::{Module, Call, Storage, Config, Event<T>},
         Contracts: pallet_contracts::{Module, Call, Config, Storage, Event<T>},
-        Sudo: pallet_sudo::{Module, Call, Config<T>, Storage, Event<T>},
+        // Keeps the call indices `Sudo` was declared before.
+        RemovedSudo: removed_sudo::{Module, Call},
         ImOnline: pallet_im_online::{Module, Call, Storage, Event<T>, ValidateUnsigned, Config<T>},
         AuthorityDiscovery: pallet_authority_discovery::{Module, Call, Config},
         Offences: pallet_offences::{Module, Call, Storage, Event},
@@ -957,6 +1135,53 @@ construct_runtime!(
         Proxy: pallet_proxy::{Module, Call, Storage, Event<T>},
         Multisig: pallet_multisig::{Module, Call, Storage, Event<T>},
         Dummy: pallet_dummy::{Module, Call, Storage, Event<T>, Config<T>},
+        EthLightClient: pallet_eth_light_client::{Module, Call, Storage, Event<T>, Config},
+        Kyc: pallet_kyc::{Module, Call, Storage, Event<T>, Config<T>},
+        Assets: pallet_multi_asset::{Module, Call, Storage, Event<T>, Config<T>},
+        AssetTxPayment: pallet_asset_tx_payment::{Module, Call, Storage, Event<T>, Config<T>},
+        Parameters: pallet_parameters::{Module, Call, Storage, Event, Config},
+    }
+);
+
+// The blocks of a parachain are checked and finalized by the validators of the relay chain, its
+// runtime has no consensus, staking nor session modules. It is the runtime of a new chain, so its
+// call indices do not follow the ones of the standalone runtime.
+#[cfg(feature = "parachain")]
+construct_runtime!(
+    pub enum Runtime where
+        Block = Block,
+        NodeBlock = node_primitives::Block,
+        UncheckedExtrinsic = UncheckedExtrinsic
+    {
+        System: frame_system::{Module, Call, Config, Storage, Event<T>},
+        Utility: pallet_utility::{Module, Call, Event},
+        Timestamp: pallet_timestamp::{Module, Call, Storage, Inherent},
+        Indices: pallet_indices::{Module, Call, Storage, Config<T>, Event<T>},
+        Balances: pallet_balances::{Module, Call, Storage, Config<T>, Event<T>},
+        TransactionPayment: pallet_transaction_payment::{Module, Storage},
+        Democracy: pallet_democracy::{Module, Call, Storage, Config, Event<T>},
+        Council: pallet_collective::<Instance1>::{Module, Call, Storage, Origin<T>, Event<T>, Config<T>},
+        TechnicalCommittee: pallet_collective::<Instance2>::{Module, Call, Storage, Origin<T>, Event<T>, Config<T>},
+        Elections: pallet_elections_phragmen::{Module, Call, Storage, Event<T>, Config<T>},
+        TechnicalMembership: pallet_membership::<Instance1>::{Module, Call, Storage, Event<T>, Config<T>},
+        Treasury: pallet_treasury::{Module, Call, Storage, Config, Event<T>},
+        Contracts: pallet_contracts::{Module, Call, Config, Storage, Event<T>},
+        RandomnessCollectiveFlip: pallet_randomness_collective_flip::{Module, Call, Storage},
+        Identity: pallet_identity::{Module, Call, Storage, Event<T>},
+        Society: pallet_society::{Module, Call, Storage, Event<T>, Config<T>},
+        Recovery: pallet_recovery::{Module, Call, Storage, Event<T>},
+        Vesting: pallet_vesting::{Module, Call, Storage, Event<T>, Config<T>},
+        Scheduler: pallet_scheduler::{Module, Call, Storage, Event<T>},
+        Proxy: pallet_proxy::{Module, Call, Storage, Event<T>},
+        Multisig: pallet_multisig::{Module, Call, Storage, Event<T>},
+        Dummy: pallet_dummy::{Module, Call, Storage, Event<T>, Config<T>},
+        EthLightClient: pallet_eth_light_client::{Module, Call, Storage, Event<T>, Config},
+        Kyc: pallet_kyc::{Module, Call, Storage, Event<T>, Config<T>},
+        Assets: pallet_multi_asset::{Module, Call, Storage, Event<T>, Config<T>},
+        AssetTxPayment: pallet_asset_tx_payment::{Module, Call, Storage, Event<T>, Config<T>},
+        Parameters: pallet_parameters::{Module, Call, Storage, Event, Config},
+        ParachainSystem: cumulus_parachain_system::{Module, Call, Storage, Inherent, Event},
+        ParachainInfo: parachain_info::{Module, Storage, Config},
     }
 );
 
@@ -982,7 +1207,9 @@ pub type SignedExtra = (
     frame_system::CheckEra<Runtime>,
     frame_system::CheckNonce<Runtime>,
     frame_system::CheckWeight<Runtime>,
-    pallet_transaction_payment::ChargeTransactionPayment<Runtime>,
+    pallet_asset_tx_payment::ChargeAssetTxPayment<Runtime>,
+    pallet_dummy::CheckRelayerVote<Runtime>,
+    pallet_kyc::CheckKyc<Runtime>,
 );
 /// Unchecked extrinsic type as expected by this runtime.
 pub type UncheckedExtrinsic = generic::UncheckedExtrinsic<Address, Call, Signature, SignedExtra>;
@@ -997,8 +1224,12 @@ pub type Executive = frame_executive::Executive<
     frame_system::ChainContext<Runtime>,
     Runtime,
     AllModules,
+    migrations::Migrations,
 >;
 
+#[cfg(feature = "parachain")]
+cumulus_runtime::register_validate_block!(Block, Executive);
+
 impl_runtime_apis! {
     impl sp_api::Core<Block> for Runtime {
         fn version() -> RuntimeVersion {
@@ -1057,6 +1288,7 @@ impl_runtime_apis! {
         }
     }
 
+    #[cfg(not(feature = "parachain"))]
     impl fg_primitives::GrandpaApi<Block> for Runtime {
         fn grandpa_authorities() -> GrandpaAuthorityList {
             Grandpa::grandpa_authorities()
@@ -1089,6 +1321,7 @@ impl_runtime_apis! {
         }
     }
 
+    #[cfg(not(feature = "parachain"))]
     impl sp_consensus_babe::BabeApi<Block> for Runtime {
         fn configuration() -> sp_consensus_babe::BabeGenesisConfiguration {
             // The choice of `c` parameter (where `1 - c` represents the
@@ -1134,6 +1367,7 @@ impl_runtime_apis! {
         }
     }
 
+    #[cfg(not(feature = "parachain"))]
     impl sp_authority_discovery::AuthorityDiscoveryApi<Block> for Runtime {
         fn authorities() -> Vec<AuthorityDiscoveryId> {
             AuthorityDiscovery::authorities()
@@ -1191,6 +1425,59 @@ impl_runtime_apis! {
         }
     }
 
+    impl pallet_dummy_rpc_runtime_api::DummyApi<
+        Block,
+        AccountId,
+        AssetId,
+        Balance,
+        BlockNumber,
+    > for Runtime {
+        fn pot_balance(asset_id: AssetId) -> Balance {
+            Dummy::free_balance(asset_id, &Dummy::pot_account(asset_id))
+        }
+
+        fn processed_at(deposit_id: pallet_dummy::DepositId) -> Option<BlockNumber> {
+            Dummy::processed_deposits(deposit_id)
+        }
+
+        fn pending_proposals() -> Vec<(
+            pallet_dummy::DepositId,
+            pallet_dummy::MintProposal<AssetId, AccountId, Balance, BlockNumber>,
+        )> {
+            Dummy::pending_proposals()
+        }
+
+        fn relayer_threshold() -> u32 {
+            Dummy::relayer_threshold()
+        }
+
+        fn is_paused() -> bool {
+            Dummy::is_paused()
+        }
+
+        fn mint_limits(
+            asset_id: AssetId,
+        ) -> (pallet_dummy::MintLimits<Balance, BlockNumber>, Balance) {
+            (Dummy::mint_limits(asset_id), Dummy::minted_in_current_window(asset_id))
+        }
+
+        fn account_transfers(
+            account: AccountId,
+        ) -> Vec<(u64, pallet_dummy::TransferRecord<AssetId, AccountId, Balance, BlockNumber>)> {
+            Dummy::account_history(&account)
+        }
+    }
+
+    impl pallet_kyc_runtime_api::KycApi<Block, AccountId, BlockNumber> for Runtime {
+        fn investor(account: AccountId) -> Option<pallet_kyc::Investor<BlockNumber>> {
+            Kyc::investor(account)
+        }
+
+        fn is_verified(account: AccountId) -> bool {
+            Kyc::is_verified(&account)
+        }
+    }
+
     impl sp_session::SessionKeys<Block> for Runtime {
         fn generate_session_keys(seed: Option<Vec<u8>>) -> Vec<u8> {
             SessionKeys::generate(seed)
@@ -1212,11 +1499,15 @@ impl_runtime_apis! {
             // Trying to add benchmarks directly to the Session Pallet caused cyclic dependency issues.
             // To get around that, we separated the Session benchmarks into its own crate, which is why
             // we need these two lines below.
+            #[cfg(not(feature = "parachain"))]
             use pallet_session_benchmarking::Module as SessionBench;
+            #[cfg(not(feature = "parachain"))]
             use pallet_offences_benchmarking::Module as OffencesBench;
             use frame_system_benchmarking::Module as SystemBench;
 
+            #[cfg(not(feature = "parachain"))]
             impl pallet_session_benchmarking::Trait for Runtime {}
+            #[cfg(not(feature = "parachain"))]
             impl pallet_offences_benchmarking::Trait for Runtime {}
             impl frame_system_benchmarking::Trait for Runtime {}
 
@@ -1238,21 +1529,29 @@ impl_runtime_apis! {
             let mut batches = Vec::<BenchmarkBatch>::new();
             let params = (&config, &whitelist);
 
+            #[cfg(not(feature = "parachain"))]
             add_benchmark!(params, batches, pallet_babe, Babe);
             add_benchmark!(params, batches, pallet_balances, Balances);
             add_benchmark!(params, batches, pallet_collective, Council);
             add_benchmark!(params, batches, pallet_contracts, Contracts);
             add_benchmark!(params, batches, pallet_democracy, Democracy);
+            add_benchmark!(params, batches, pallet_dummy, Dummy);
             add_benchmark!(params, batches, pallet_elections_phragmen, Elections);
+            #[cfg(not(feature = "parachain"))]
             add_benchmark!(params, batches, pallet_grandpa, Grandpa);
             add_benchmark!(params, batches, pallet_identity, Identity);
+            #[cfg(not(feature = "parachain"))]
             add_benchmark!(params, batches, pallet_im_online, ImOnline);
             add_benchmark!(params, batches, pallet_indices, Indices);
+            add_benchmark!(params, batches, pallet_multi_asset, Assets);
             add_benchmark!(params, batches, pallet_multisig, Multisig);
+            #[cfg(not(feature = "parachain"))]
             add_benchmark!(params, batches, pallet_offences, OffencesBench::<Runtime>);
             add_benchmark!(params, batches, pallet_proxy, Proxy);
             add_benchmark!(params, batches, pallet_scheduler, Scheduler);
+            #[cfg(not(feature = "parachain"))]
             add_benchmark!(params, batches, pallet_session, SessionBench::<Runtime>);
+            #[cfg(not(feature = "parachain"))]
             add_benchmark!(params, batches, pallet_staking, Staking);
             add_benchmark!(params, batches, frame_system, SystemBench::<Runtime>);
             add_benchmark!(params, batches, pallet_timestamp, Timestamp);
@@ -1264,6 +1563,13 @@ impl_runtime_apis! {
             Ok(batches)
         }
     }
+
+    #[cfg(feature = "try-runtime")]
+    impl migrations::UpgradeRehearsalApi<Block> for Runtime {
+        fn rehearse_upgrade() -> Result<Weight, sp_runtime::RuntimeString> {
+            migrations::rehearse_upgrade().map_err(Into::into)
+        }
+    }
 }
 
 #[cfg(test)]
@@ -1283,6 +1589,265 @@ mod tests {
     }
 
     #[test]
+    fn bridge_proxy_is_limited_to_relayer_calls() {
+        let vote = Call::Dummy(pallet_dummy::Call::vote_mint(
+            0,
+            Default::default(),
+            Default::default(),
+            1,
+        ));
+        let mint = Call::Dummy(pallet_dummy::Call::mint_with_proof(
+            pallet_eth_light_client::DepositProof {
+                block_hash: Default::default(),
+                tx_index: 0,
+                log_index: 0,
+                transaction_proof: Vec::new(),
+                receipt_proof: Vec::new(),
+            },
+        ));
+        let confirm = Call::Dummy(pallet_dummy::Call::confirm_release(0, Default::default()));
+        let burn = Call::Dummy(pallet_dummy::Call::burn(0, Default::default(), 1));
+        let refill = Call::Dummy(pallet_dummy::Call::refill(0, 1));
+        let withdraw = Call::Dummy(pallet_dummy::Call::withdraw(0, Default::default(), 1));
+        let transfer = Call::Balances(pallet_balances::Call::transfer(
+            Indices::unlookup(Default::default()),
+            1,
+        ));
+
+        assert!(ProxyType::Bridge.filter(&vote));
+        assert!(ProxyType::Bridge.filter(&mint));
+        assert!(ProxyType::Bridge.filter(&confirm));
+        assert!(!ProxyType::Bridge.filter(&burn));
+        assert!(!ProxyType::Bridge.filter(&refill));
+        assert!(!ProxyType::Bridge.filter(&withdraw));
+        assert!(!ProxyType::Bridge.filter(&transfer));
+        #[cfg(not(feature = "parachain"))]
+        {
+            let bond_extra = Call::Staking(pallet_staking::Call::bond_extra(1));
+            assert!(!ProxyType::Bridge.filter(&bond_extra));
+            assert!(ProxyType::Staking.filter(&bond_extra));
+        }
+
+        assert!(ProxyType::Any.filter(&burn));
+        assert!(ProxyType::NonTransfer.filter(&vote));
+        assert!(!ProxyType::NonTransfer.filter(&burn));
+        assert!(!ProxyType::Staking.filter(&vote));
+        assert!(!ProxyType::Governance.filter(&vote));
+    }
+
+    #[test]
+    fn investor_gate_looks_into_wrapped_calls() {
+        use pallet_kyc::KycGate;
+
+        let who = AccountId::from([1; 32]);
+        let real = AccountId::from([2; 32]);
+        let dest = AccountId::from([3; 32]);
+        let vote = Call::Dummy(pallet_dummy::Call::vote_mint(
+            0,
+            Default::default(),
+            dest.clone(),
+            1,
+        ));
+        let burn = Call::Dummy(pallet_dummy::Call::burn(0, Default::default(), 1));
+        let transfer = Call::Balances(pallet_balances::Call::transfer(
+            Indices::unlookup(dest.clone()),
+            1,
+        ));
+
+        assert_eq!(
+            InvestorGate::required(&who, &vote),
+            Some(vec![dest.clone()])
+        );
+        assert_eq!(InvestorGate::required(&who, &burn), Some(vec![who.clone()]));
+        assert_eq!(InvestorGate::required(&who, &transfer), Some(vec![]));
+        assert_eq!(
+            InvestorGate::required_for_transfer(&who, &transfer),
+            Some(vec![who.clone(), dest.clone()])
+        );
+
+        let proxied = Call::Proxy(pallet_proxy::Call::proxy(
+            real.clone(),
+            None,
+            Box::new(burn.clone()),
+        ));
+        assert_eq!(InvestorGate::required(&who, &proxied), Some(vec![real]));
+        let batch = Call::Utility(pallet_utility::Call::batch(vec![vote, burn, transfer]));
+        assert_eq!(
+            InvestorGate::required(&who, &batch),
+            Some(vec![dest.clone(), who.clone()])
+        );
+        assert_eq!(
+            InvestorGate::required_for_transfer(&who, &batch),
+            Some(vec![who.clone(), dest.clone()])
+        );
+
+        let vested_transfer = Call::Vesting(pallet_vesting::Call::vested_transfer(
+            Indices::unlookup(dest.clone()),
+            pallet_vesting::VestingInfo {
+                locked: 10,
+                per_block: 1,
+                starting_block: 0,
+            },
+        ));
+        let asset_transfer = Call::Assets(pallet_multi_asset::Call::transfer(
+            1,
+            Indices::unlookup(dest.clone()),
+            1,
+        ));
+        let contract_call = |value| {
+            Call::Contracts(pallet_contracts::Call::call(
+                Indices::unlookup(dest.clone()),
+                value,
+                0,
+                vec![],
+            ))
+        };
+        let instantiate = |endowment| {
+            Call::Contracts(pallet_contracts::Call::instantiate(
+                endowment,
+                0,
+                Default::default(),
+                vec![],
+            ))
+        };
+        assert_eq!(
+            InvestorGate::required_for_transfer(&who, &contract_call(0)),
+            Some(vec![])
+        );
+        assert_eq!(
+            InvestorGate::required_for_transfer(&who, &instantiate(0)),
+            Some(vec![])
+        );
+        assert_eq!(
+            InvestorGate::required_for_transfer(&who, &instantiate(1)),
+            Some(vec![who.clone()])
+        );
+        for transfer in &[vested_transfer, asset_transfer, contract_call(1)] {
+            assert_eq!(
+                InvestorGate::required_for_transfer(&who, transfer),
+                Some(vec![who.clone(), dest.clone()])
+            );
+        }
+
+        let derivative = Utility::derivative_account_id(who.clone(), 0);
+        let as_derivative = Call::Utility(pallet_utility::Call::as_derivative(
+            0,
+            Box::new(burn.clone()),
+        ));
+        assert_eq!(
+            InvestorGate::required(&who, &as_derivative),
+            Some(vec![derivative])
+        );
+        let multisig = |threshold| {
+            let mut signatories = vec![who.clone(), dest.clone()];
+            signatories.sort();
+            Multisig::multi_account_id(&signatories, threshold)
+        };
+        let as_multi_threshold_1 = Call::Multisig(pallet_multisig::Call::as_multi_threshold_1(
+            vec![dest.clone()],
+            Box::new(burn.clone()),
+        ));
+        assert_eq!(
+            InvestorGate::required(&who, &as_multi_threshold_1),
+            Some(vec![multisig(1)])
+        );
+        let as_multi = Call::Multisig(pallet_multisig::Call::as_multi(
+            2,
+            vec![dest.clone()],
+            None,
+            burn.encode(),
+            false,
+            0,
+        ));
+        assert_eq!(
+            InvestorGate::required(&who, &as_multi),
+            Some(vec![multisig(2)])
+        );
+        let as_recovered = Call::Recovery(pallet_recovery::Call::as_recovered(
+            dest.clone(),
+            Box::new(burn),
+        ));
+        assert_eq!(
+            InvestorGate::required(&who, &as_recovered),
+            Some(vec![dest])
+        );
+
+        // The recipient behind an index which is not assigned cannot be told.
+        sp_io::TestExternalities::default().execute_with(|| {
+            let unknown = Call::Balances(pallet_balances::Call::transfer(Address::Index(7), 1));
+            assert_eq!(InvestorGate::required_for_transfer(&who, &unknown), None);
+            let batch = Call::Utility(pallet_utility::Call::batch(vec![as_derivative, unknown]));
+            assert_eq!(InvestorGate::required_for_transfer(&who, &batch), None);
+        });
+    }
+
+    #[test]
+    fn proxied_votes_are_found() {
+        use pallet_dummy::{DepositId, WrappedVotes};
+
+        let who = AccountId::from([1; 32]);
+        let real = AccountId::from([2; 32]);
+        let vote = |n| {
+            Call::Dummy(pallet_dummy::Call::vote_mint(
+                0,
+                DepositId::repeat_byte(n),
+                Default::default(),
+                1,
+            ))
+        };
+        let proxied = Call::Proxy(pallet_proxy::Call::proxy(
+            real.clone(),
+            None,
+            Box::new(vote(1)),
+        ));
+
+        assert!(ProxiedVotes::wrapped_votes(&who, &vote(1)).is_empty());
+        assert_eq!(
+            ProxiedVotes::wrapped_votes(&who, &proxied),
+            vec![(real.clone(), DepositId::repeat_byte(1))]
+        );
+        let batch = Call::Utility(pallet_utility::Call::batch(vec![vote(2), proxied]));
+        assert_eq!(
+            ProxiedVotes::wrapped_votes(&who, &batch),
+            vec![
+                (who.clone(), DepositId::repeat_byte(2)),
+                (real, DepositId::repeat_byte(1))
+            ]
+        );
+    }
+
+    #[test]
+    #[cfg(not(feature = "parachain"))]
+    fn call_indices_are_stable() {
+        // Calls encoded by earlier runtimes, e.g. in the scheduler agenda, must decode the same.
+        let cancel = Call::Scheduler(pallet_scheduler::Call::cancel(0, 0));
+        assert_eq!(cancel.encode()[0], 27);
+        let pause = Call::Dummy(pallet_dummy::Call::pause());
+        assert_eq!(pause.encode()[0], 30);
+    }
+
+    #[test]
+    fn proxy_type_supersets() {
+        use ProxyType::*;
+        let all = [Any, NonTransfer, Governance, Staking, Bridge];
+        for x in &all {
+            assert!(x.is_superset(x));
+            assert!(Any.is_superset(x));
+        }
+        for x in &all[1..] {
+            assert!(!x.is_superset(&Any));
+        }
+        assert!(NonTransfer.is_superset(&Governance));
+        assert!(NonTransfer.is_superset(&Staking));
+        assert!(NonTransfer.is_superset(&Bridge));
+        assert!(!Bridge.is_superset(&NonTransfer));
+        assert!(!Bridge.is_superset(&Staking));
+        assert!(!Staking.is_superset(&Bridge));
+        assert!(!Governance.is_superset(&Bridge));
+    }
+
+    #[test]
+    #[cfg(not(feature = "parachain"))]
     fn reward_curve() {
         let curve: &'static PiecewiseLinear<'static> = &REWARD_CURVE;
 
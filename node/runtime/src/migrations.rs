@@ -0,0 +1,470 @@
+// This file is part of Curio project.
+
+// Copyright (C) 2021 Mixbytes.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Storage migrations of the runtime.
+//!
+//! A migration upgrades the storage of a pallet from one storage version to the next. The
+//! migrations of `Migrations` run in order on the first block executed by a new runtime, each
+//! one only while its pallet is at the version it upgrades from. Chains which already went
+//! through a migration, or which started from the genesis of a newer runtime, skip it.
+//!
+//! To add a migration, implement `Migration` for it and append it to `Migrations`.
+//!
+//! Runtimes built with the `try-runtime` feature expose `UpgradeRehearsalApi`, which runs the
+//! upgrade against a copy of the state of a chain between the checks of the migrations. The
+//! `rehearse-upgrade` subcommand of the node calls it on a state snapshot.
+
+use crate::{AllModules, Dummy, NativeAssetId, Runtime};
+use codec::Decode;
+use frame_support::{
+    debug,
+    storage::{migration, IterableStorageDoubleMap, IterableStorageMap, StorageValue},
+    traits::{Get, OnRuntimeUpgrade},
+    weights::Weight,
+};
+use node_primitives::{AccountId, AssetId, Balance, BlockNumber};
+use pallet_dummy::{BridgeFee, MintWindow, Releases};
+use sp_runtime::{traits::Zero, RuntimeString};
+use sp_std::{marker::PhantomData, prelude::*};
+
+/// The migrations run on runtime upgrades, in order.
+pub type Migrations = (Migrate<DummyV2>, Migrate<RemoveSudo>, Migrate<DummyV3>);
+
+sp_api::decl_runtime_apis! {
+    /// Rehearsal of a runtime upgrade, only provided by `try-runtime` builds.
+    pub trait UpgradeRehearsalApi {
+        /// Run the upgrade hooks of the runtime on the current state between the checks of the
+        /// migrations, returning the weight of the hooks.
+        fn rehearse_upgrade() -> Result<Weight, RuntimeString>;
+    }
+}
+
+/// A storage migration.
+pub trait Migration {
+    /// Name of the migration, for the logs.
+    const NAME: &'static str;
+
+    /// Whether the storage is at the version the migration upgrades from.
+    fn needed() -> bool;
+
+    /// Upgrade the storage and its version, returning the weight consumed.
+    fn migrate() -> Weight;
+
+    /// Check the storage before the migration, when it is needed.
+    #[cfg(any(feature = "std", feature = "try-runtime"))]
+    fn pre_upgrade() -> Result<(), &'static str> {
+        Ok(())
+    }
+
+    /// Check the storage after the migrations.
+    #[cfg(any(feature = "std", feature = "try-runtime"))]
+    fn post_upgrade() -> Result<(), &'static str> {
+        Ok(())
+    }
+}
+
+/// Runs the migration `M` on runtime upgrades, if it is needed.
+pub struct Migrate<M>(PhantomData<M>);
+
+impl<M: Migration> OnRuntimeUpgrade for Migrate<M> {
+    fn on_runtime_upgrade() -> Weight {
+        let check = <Runtime as frame_system::Trait>::DbWeight::get().reads(1);
+        if !M::needed() {
+            return check;
+        }
+        debug::info!("Running migration {}", M::NAME);
+        M::migrate().saturating_add(check)
+    }
+}
+
+/// Checks of the storage run around the migrations off-chain, in the tests and in the upgrade
+/// rehearsal. They never run on-chain.
+///
+/// All the checks before the migrations see the storage before any of them ran.
+#[cfg(any(feature = "std", feature = "try-runtime"))]
+pub trait UpgradeChecks {
+    /// Check the storage before the migrations.
+    fn pre_upgrade() -> Result<(), &'static str>;
+
+    /// Check the storage after the migrations.
+    fn post_upgrade() -> Result<(), &'static str>;
+}
+
+#[cfg(any(feature = "std", feature = "try-runtime"))]
+impl<M: Migration> UpgradeChecks for Migrate<M> {
+    fn pre_upgrade() -> Result<(), &'static str> {
+        if !M::needed() {
+            return Ok(());
+        }
+        M::pre_upgrade()
+    }
+
+    fn post_upgrade() -> Result<(), &'static str> {
+        if M::needed() {
+            debug::error!("Migration {} did not upgrade the storage version", M::NAME);
+            return Err("a migration is still needed after the upgrade");
+        }
+        M::post_upgrade()
+    }
+}
+
+macro_rules! impl_upgrade_checks_for_tuples {
+    ($($m:ident),+) => {
+        #[cfg(any(feature = "std", feature = "try-runtime"))]
+        impl<$($m: UpgradeChecks),+> UpgradeChecks for ($($m,)+) {
+            fn pre_upgrade() -> Result<(), &'static str> {
+                $($m::pre_upgrade()?;)+
+                Ok(())
+            }
+
+            fn post_upgrade() -> Result<(), &'static str> {
+                $($m::post_upgrade()?;)+
+                Ok(())
+            }
+        }
+    };
+}
+
+impl_upgrade_checks_for_tuples!(A);
+impl_upgrade_checks_for_tuples!(A, B);
+impl_upgrade_checks_for_tuples!(A, B, C);
+impl_upgrade_checks_for_tuples!(A, B, C, D);
+
+/// Run the upgrade hooks in the order `Executive` runs them on the first block of a new runtime,
+/// between the checks of `Migrations`, returning the weight of the hooks.
+#[cfg(any(feature = "std", feature = "try-runtime"))]
+pub fn rehearse_upgrade() -> Result<Weight, &'static str> {
+    <Migrations as UpgradeChecks>::pre_upgrade()?;
+    let weight = <frame_system::Module<Runtime> as OnRuntimeUpgrade>::on_runtime_upgrade()
+        .saturating_add(<Migrations as OnRuntimeUpgrade>::on_runtime_upgrade())
+        .saturating_add(<AllModules as OnRuntimeUpgrade>::on_runtime_upgrade());
+    <Migrations as UpgradeChecks>::post_upgrade()?;
+    Ok(weight)
+}
+
+/// Opens the pot ledgers of the assets bridged before the ledgers were kept, and counts the
+/// bridged assets.
+pub struct DummyV2;
+
+impl DummyV2 {
+    fn bridged_assets() -> Vec<AssetId> {
+        pallet_dummy::BridgedAssets::<Runtime>::iter()
+            .map(|(asset_id, _)| asset_id)
+            .collect()
+    }
+}
+
+impl Migration for DummyV2 {
+    const NAME: &'static str = "pallet_dummy::V2";
+
+    fn needed() -> bool {
+        Dummy::storage_version() == Releases::V1
+    }
+
+    fn migrate() -> Weight {
+        let assets = Self::bridged_assets();
+        for asset_id in &assets {
+            Dummy::open_ledger(*asset_id);
+        }
+        pallet_dummy::BridgedAssetCount::put(assets.len() as u32);
+        pallet_dummy::StorageVersion::put(Releases::V2);
+
+        // Each asset is read, its ledger and its pot are read and its ledger may be written.
+        let assets = assets.len() as Weight;
+        <Runtime as frame_system::Trait>::DbWeight::get()
+            .reads_writes(4 * assets, assets.saturating_add(2))
+    }
+
+    #[cfg(any(feature = "std", feature = "try-runtime"))]
+    fn pre_upgrade() -> Result<(), &'static str> {
+        let consistent = pallet_dummy::BridgedAssets::<Runtime>::iter()
+            .all(|(asset_id, token)| Dummy::asset_by_token(token) == Some(asset_id));
+        if !consistent {
+            return Err("the registry of the bridged assets is inconsistent");
+        }
+        Ok(())
+    }
+
+    #[cfg(any(feature = "std", feature = "try-runtime"))]
+    fn post_upgrade() -> Result<(), &'static str> {
+        let assets = Self::bridged_assets();
+        if Dummy::bridged_asset_count() as usize != assets.len() {
+            return Err("BridgedAssetCount does not count the bridged assets");
+        }
+        let unaccounted = assets.iter().any(|asset_id| {
+            Dummy::total_locked(asset_id).is_zero()
+                && Dummy::total_minted(asset_id).is_zero()
+                && !Dummy::free_balance(*asset_id, &Dummy::pot_account(*asset_id)).is_zero()
+        });
+        if unaccounted {
+            return Err("a bridge pot holds funds outside of its ledger");
+        }
+        Ok(())
+    }
+}
+
+/// Moves the amounts minted in the tumbling windows of the mint caps into the buckets of the
+/// rolling windows, and the bridge fees of the native currency to its entry of the per-asset
+/// fees. Queues the open mint proposals for pruning.
+pub struct DummyV3;
+
+/// A window of the mint caps before `V3`.
+#[derive(Decode)]
+struct TumblingWindow {
+    start: BlockNumber,
+    minted: Balance,
+}
+
+impl DummyV3 {
+    /// The amount minted in `old` counts for a period after the window started.
+    fn roll(old: TumblingWindow) -> Option<MintWindow<Balance, BlockNumber>> {
+        if old.minted.is_zero() {
+            return None;
+        }
+        Some(MintWindow {
+            buckets: vec![(old.start, old.minted)],
+        })
+    }
+
+    /// Take the fee stored under `item` before the fees were set per asset.
+    fn take_fee(item: &[u8]) -> Option<BridgeFee<Balance>> {
+        migration::take_storage_value(b"Dummy", item, &[])
+    }
+}
+
+impl Migration for DummyV3 {
+    const NAME: &'static str = "pallet_dummy::V3";
+
+    fn needed() -> bool {
+        Dummy::storage_version() == Releases::V2
+    }
+
+    fn migrate() -> Weight {
+        let windows = pallet_dummy::GlobalMinted::<Runtime>::iter().count()
+            + pallet_dummy::AccountMinted::<Runtime>::iter().count();
+        pallet_dummy::GlobalMinted::<Runtime>::translate(|_, old| Self::roll(old));
+        pallet_dummy::AccountMinted::<Runtime>::translate(|_, _, old| Self::roll(old));
+        let native = NativeAssetId::get();
+        if let Some(fee) = Self::take_fee(b"MintFee") {
+            pallet_dummy::MintFee::<Runtime>::insert(native, fee);
+        }
+        if let Some(fee) = Self::take_fee(b"BurnFee") {
+            pallet_dummy::BurnFee::<Runtime>::insert(native, fee);
+        }
+        let mut proposals: Vec<_> = pallet_dummy::MintProposals::<Runtime>::iter()
+            .map(|(deposit_id, proposal)| (proposal.expiry, deposit_id))
+            .collect();
+        proposals.sort();
+        for (index, entry) in proposals.iter().enumerate() {
+            pallet_dummy::ProposalExpiries::<Runtime>::insert(index as u64, entry);
+        }
+        pallet_dummy::NextProposalIndex::put(proposals.len() as u64);
+        pallet_dummy::StorageVersion::put(Releases::V3);
+
+        // Each window is read twice and written, each fee read and moved, each proposal read
+        // and queued.
+        let windows = windows as Weight;
+        let proposals = proposals.len() as Weight;
+        <Runtime as frame_system::Trait>::DbWeight::get().reads_writes(
+            2 * windows + proposals + 2,
+            windows.saturating_add(proposals).saturating_add(6),
+        )
+    }
+}
+
+/// Deletes the key of the sudo module, removed from the runtime in favour of the governance
+/// origins.
+pub struct RemoveSudo;
+
+impl RemoveSudo {
+    fn key() -> Option<AccountId> {
+        migration::get_storage_value(b"Sudo", b"Key", &[])
+    }
+}
+
+impl Migration for RemoveSudo {
+    const NAME: &'static str = "pallet_sudo::remove";
+
+    fn needed() -> bool {
+        Self::key().is_some()
+    }
+
+    fn migrate() -> Weight {
+        migration::take_storage_value::<AccountId>(b"Sudo", b"Key", &[]);
+        <Runtime as frame_system::Trait>::DbWeight::get().writes(1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::currency::DOLLARS;
+    use frame_support::storage::{unhashed, StorageDoubleMap, StorageMap};
+    use sp_core::H160;
+
+    /// Genesis with the native currency and asset 1 bridged, and the native pot funded.
+    fn new_test_ext() -> sp_io::TestExternalities {
+        let mut t = frame_system::GenesisConfig::default()
+            .build_storage::<Runtime>()
+            .unwrap();
+        pallet_dummy::GenesisConfig::<Runtime> {
+            balance: 1_000 * DOLLARS,
+            relayers: vec![],
+            relayer_threshold: 1,
+            assets: vec![(0, H160::repeat_byte(0xcc)), (1, H160::repeat_byte(0xaa))],
+        }
+        .assimilate_storage(&mut t)
+        .unwrap();
+        t.into()
+    }
+
+    /// Run the migrations between their checks.
+    fn upgrade() -> Weight {
+        <Migrations as UpgradeChecks>::pre_upgrade().unwrap();
+        let weight = <Migrations as OnRuntimeUpgrade>::on_runtime_upgrade();
+        <Migrations as UpgradeChecks>::post_upgrade().unwrap();
+        weight
+    }
+
+    #[test]
+    fn new_chains_skip_the_migrations() {
+        new_test_ext().execute_with(|| {
+            assert_eq!(Dummy::storage_version(), Releases::V3);
+            let root = sp_io::storage::root();
+            upgrade();
+            assert_eq!(sp_io::storage::root(), root);
+        });
+    }
+
+    #[test]
+    fn dummy_v2_opens_ledgers_and_counts_assets() {
+        new_test_ext().execute_with(|| {
+            // Roll the storage back to V1.
+            pallet_dummy::StorageVersion::kill();
+            pallet_dummy::BridgedAssetCount::kill();
+            pallet_dummy::TotalLocked::<Runtime>::remove(0);
+            assert!(DummyV2::needed());
+            assert_eq!(
+                <Migrations as UpgradeChecks>::post_upgrade(),
+                Err("a migration is still needed after the upgrade")
+            );
+
+            assert!(upgrade() > 0);
+            assert_eq!(Dummy::storage_version(), Releases::V3);
+            assert_eq!(Dummy::bridged_asset_count(), 2);
+            assert_eq!(Dummy::total_locked(0), 1_000 * DOLLARS);
+            assert_eq!(Dummy::pot_discrepancy(0), None);
+
+            // Upgrading again changes nothing.
+            let root = sp_io::storage::root();
+            upgrade();
+            assert_eq!(sp_io::storage::root(), root);
+        });
+    }
+
+    #[test]
+    fn rehearsal_runs_the_migrations_between_their_checks() {
+        new_test_ext().execute_with(|| {
+            pallet_dummy::StorageVersion::kill();
+            assert!(rehearse_upgrade().unwrap() > 0);
+            assert_eq!(Dummy::storage_version(), Releases::V3);
+
+            pallet_dummy::StorageVersion::kill();
+            pallet_dummy::AssetByToken::<Runtime>::remove(H160::repeat_byte(0xaa));
+            assert_eq!(
+                rehearse_upgrade(),
+                Err("the registry of the bridged assets is inconsistent")
+            );
+            assert_eq!(Dummy::storage_version(), Releases::V1);
+        });
+    }
+
+    #[test]
+    fn dummy_v3_rolls_the_mint_windows() {
+        new_test_ext().execute_with(|| {
+            let who = AccountId::from([1; 32]);
+            let global = pallet_dummy::GlobalMinted::<Runtime>::hashed_key_for(0);
+            let account = pallet_dummy::AccountMinted::<Runtime>::hashed_key_for(0, &who);
+            unhashed::put(&global, &(5 as BlockNumber, 300 as Balance));
+            unhashed::put(&account, &(5 as BlockNumber, 100 as Balance));
+            unhashed::put(
+                &pallet_dummy::GlobalMinted::<Runtime>::hashed_key_for(1),
+                &(5 as BlockNumber, 0 as Balance),
+            );
+            migration::put_storage_value(b"Dummy", b"MintFee", &[], BridgeFee::<Balance>::Flat(5));
+            let deposit_id = pallet_dummy::DepositId::repeat_byte(1);
+            pallet_dummy::MintProposals::<Runtime>::insert(
+                deposit_id,
+                pallet_dummy::MintProposal {
+                    asset_id: 0,
+                    account: who.clone(),
+                    amount: 100,
+                    votes: vec![who.clone()],
+                    expiry: 20,
+                },
+            );
+            pallet_dummy::StorageVersion::put(Releases::V2);
+            assert!(DummyV3::needed());
+
+            assert!(upgrade() > 0);
+            assert_eq!(Dummy::storage_version(), Releases::V3);
+            assert_eq!(
+                Dummy::global_minted(0),
+                MintWindow {
+                    buckets: vec![(5, 300)]
+                }
+            );
+            assert_eq!(
+                Dummy::account_minted(0, &who),
+                MintWindow {
+                    buckets: vec![(5, 100)]
+                }
+            );
+            assert!(!pallet_dummy::GlobalMinted::<Runtime>::contains_key(1));
+            assert_eq!(Dummy::mint_fee(0), BridgeFee::Flat(5));
+            assert_eq!(Dummy::burn_fee(0), BridgeFee::Free);
+            assert_eq!(DummyV3::take_fee(b"MintFee"), None);
+            assert_eq!(Dummy::proposal_expiries(0), Some((20, deposit_id)));
+            assert_eq!(Dummy::next_proposal_index(), 1);
+        });
+    }
+
+    #[test]
+    fn remove_sudo_deletes_the_sudo_key() {
+        new_test_ext().execute_with(|| {
+            let key = AccountId::from([1; 32]);
+            migration::put_storage_value(b"Sudo", b"Key", &[], key);
+            assert!(RemoveSudo::needed());
+
+            assert!(upgrade() > 0);
+            assert_eq!(RemoveSudo::key(), None);
+            assert!(!RemoveSudo::needed());
+        });
+    }
+
+    #[test]
+    fn dummy_v2_checks_the_asset_registry() {
+        new_test_ext().execute_with(|| {
+            pallet_dummy::StorageVersion::kill();
+            pallet_dummy::AssetByToken::<Runtime>::remove(H160::repeat_byte(0xaa));
+            assert_eq!(
+                <Migrations as UpgradeChecks>::pre_upgrade(),
+                Err("the registry of the bridged assets is inconsistent")
+            );
+        });
+    }
+}
@@ -0,0 +1,31 @@
+// This file is part of Curio project.
+
+// Copyright (C) 2021 Mixbytes.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Placeholder of the sudo module in `construct_runtime!`.
+//!
+//! The outer `Call` is encoded with the position of each module among the modules with calls.
+//! This module takes the position `Sudo` had, so that the calls of the modules declared after
+//! it keep their encoding, e.g. in the scheduler agenda or in the democracy preimages. It has
+//! no calls and no storage.
+
+use frame_support::decl_module;
+
+pub trait Trait: frame_system::Trait {}
+
+decl_module! {
+    pub struct Module<T: Trait> for enum Call where origin: T::Origin {}
+}
@@ -19,14 +19,19 @@ pub mod frame_system;
 pub mod pallet_balances;
 pub mod pallet_collective;
 pub mod pallet_democracy;
+pub mod pallet_dummy;
 pub mod pallet_elections_phragmen;
 pub mod pallet_identity;
+#[cfg(not(feature = "parachain"))]
 pub mod pallet_im_online;
 pub mod pallet_indices;
+pub mod pallet_multi_asset;
 pub mod pallet_multisig;
 pub mod pallet_proxy;
 pub mod pallet_scheduler;
+#[cfg(not(feature = "parachain"))]
 pub mod pallet_session;
+#[cfg(not(feature = "parachain"))]
 pub mod pallet_staking;
 pub mod pallet_timestamp;
 pub mod pallet_treasury;
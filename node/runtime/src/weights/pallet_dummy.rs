@@ -0,0 +1,147 @@
+// This file is part of Curio project.
+
+// Copyright (C) 2021 Mixbytes.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Weights for pallet_dummy.
+//!
+//! These are estimates, not benchmark results. Replace them with the output of
+//! `benchmark --pallet pallet_dummy --extrinsic '*'` on reference hardware, which
+//! `scripts/benchmark.sh pallet_dummy` runs.
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::weights::{constants::RocksDbWeight as DbWeight, Weight};
+
+pub struct WeightInfo;
+impl pallet_dummy::WeightInfo for WeightInfo {
+    fn mint() -> Weight {
+        (51782000 as Weight)
+            .saturating_add(DbWeight::get().reads(8 as Weight))
+            .saturating_add(DbWeight::get().writes(9 as Weight))
+    }
+    fn burn() -> Weight {
+        (41218000 as Weight)
+            .saturating_add(DbWeight::get().reads(3 as Weight))
+            .saturating_add(DbWeight::get().writes(6 as Weight))
+    }
+    fn add_relayer(r: u32) -> Weight {
+        (21437000 as Weight)
+            .saturating_add((143000 as Weight).saturating_mul(r as Weight))
+            .saturating_add(DbWeight::get().reads(1 as Weight))
+            .saturating_add(DbWeight::get().writes(1 as Weight))
+    }
+    fn remove_relayer(r: u32) -> Weight {
+        (20862000 as Weight)
+            .saturating_add((139000 as Weight).saturating_mul(r as Weight))
+            .saturating_add(DbWeight::get().reads(2 as Weight))
+            .saturating_add(DbWeight::get().writes(1 as Weight))
+    }
+    fn set_threshold() -> Weight {
+        (13205000 as Weight)
+            .saturating_add(DbWeight::get().reads(1 as Weight))
+            .saturating_add(DbWeight::get().writes(1 as Weight))
+    }
+    fn vote_mint(r: u32) -> Weight {
+        (64891000 as Weight)
+            .saturating_add((412000 as Weight).saturating_mul(r as Weight))
+            .saturating_add(DbWeight::get().reads(7 as Weight))
+            .saturating_add(DbWeight::get().writes(8 as Weight))
+    }
+    fn set_mint_limits() -> Weight {
+        (14019000 as Weight).saturating_add(DbWeight::get().writes(1 as Weight))
+    }
+    fn pause() -> Weight {
+        (12331000 as Weight).saturating_add(DbWeight::get().writes(1 as Weight))
+    }
+    fn unpause() -> Weight {
+        (12104000 as Weight).saturating_add(DbWeight::get().writes(1 as Weight))
+    }
+    fn register_asset() -> Weight {
+        (19882000 as Weight)
+            .saturating_add(DbWeight::get().reads(2 as Weight))
+            .saturating_add(DbWeight::get().writes(2 as Weight))
+    }
+    fn unregister_asset() -> Weight {
+        (18437000 as Weight)
+            .saturating_add(DbWeight::get().reads(1 as Weight))
+            .saturating_add(DbWeight::get().writes(2 as Weight))
+    }
+    fn set_fees() -> Weight {
+        (15246000 as Weight).saturating_add(DbWeight::get().writes(2 as Weight))
+    }
+    fn mint_with_proof() -> Weight {
+        (71650000 as Weight)
+            .saturating_add(DbWeight::get().reads(11 as Weight))
+            .saturating_add(DbWeight::get().writes(9 as Weight))
+    }
+    fn check_pots(a: u32) -> Weight {
+        (2916000 as Weight)
+            .saturating_add((9847000 as Weight).saturating_mul(a as Weight))
+            .saturating_add(DbWeight::get().reads(1 as Weight))
+            .saturating_add(DbWeight::get().reads((5 as Weight).saturating_mul(a as Weight)))
+            .saturating_add(DbWeight::get().writes((1 as Weight).saturating_mul(a as Weight)))
+    }
+    fn execute_scheduled_mint() -> Weight {
+        (58374000 as Weight)
+            .saturating_add(DbWeight::get().reads(9 as Weight))
+            .saturating_add(DbWeight::get().writes(10 as Weight))
+    }
+    fn cancel_mint() -> Weight {
+        (31906000 as Weight)
+            .saturating_add(DbWeight::get().reads(3 as Weight))
+            .saturating_add(DbWeight::get().writes(2 as Weight))
+    }
+    fn prune_transfers(t: u32) -> Weight {
+        (3512000 as Weight)
+            .saturating_add((7986000 as Weight).saturating_mul(t as Weight))
+            .saturating_add(DbWeight::get().reads(2 as Weight))
+            .saturating_add(DbWeight::get().reads((2 as Weight).saturating_mul(t as Weight)))
+            .saturating_add(DbWeight::get().writes(1 as Weight))
+            .saturating_add(DbWeight::get().writes((2 as Weight).saturating_mul(t as Weight)))
+    }
+    fn prune_proposals(p: u32) -> Weight {
+        (3104000 as Weight)
+            .saturating_add((9215000 as Weight).saturating_mul(p as Weight))
+            .saturating_add(DbWeight::get().reads(2 as Weight))
+            .saturating_add(DbWeight::get().reads((2 as Weight).saturating_mul(p as Weight)))
+            .saturating_add(DbWeight::get().writes(1 as Weight))
+            .saturating_add(DbWeight::get().writes((2 as Weight).saturating_mul(p as Weight)))
+    }
+    fn release_held_mints(m: u32) -> Weight {
+        (4127000 as Weight)
+            .saturating_add((24512000 as Weight).saturating_mul(m as Weight))
+            .saturating_add(DbWeight::get().reads(2 as Weight))
+            .saturating_add(DbWeight::get().reads((2 as Weight).saturating_mul(m as Weight)))
+            .saturating_add(DbWeight::get().writes(1 as Weight))
+            .saturating_add(DbWeight::get().writes((2 as Weight).saturating_mul(m as Weight)))
+    }
+    fn confirm_release() -> Weight {
+        (24391000 as Weight)
+            .saturating_add(DbWeight::get().reads(2 as Weight))
+            .saturating_add(DbWeight::get().writes(1 as Weight))
+    }
+    fn refill() -> Weight {
+        (62915000 as Weight)
+            .saturating_add(DbWeight::get().reads(5 as Weight))
+            .saturating_add(DbWeight::get().writes(3 as Weight))
+    }
+    fn withdraw() -> Weight {
+        (46905000 as Weight)
+            .saturating_add(DbWeight::get().reads(5 as Weight))
+            .saturating_add(DbWeight::get().writes(2 as Weight))
+    }
+}
@@ -0,0 +1,443 @@
+// This file is part of Curio project.
+
+// Copyright (C) 2021 Mixbytes.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Asset transaction payment
+//!
+//! Lets the transactions pay their fees in an asset of the multi-asset pallet.
+//!
+//! `ChargeAssetTxPayment` takes the place of `ChargeTransactionPayment` in the signed extensions.
+//! Without an asset it charges the native currency, exactly as `ChargeTransactionPayment` does.
+//! With an asset it converts the fee at the rate of the asset and takes the converted amount
+//! from the signer into the fee pot. The pot pays the fee in the native currency in exchange,
+//! so the fee reaches `OnTransactionPayment` as any other fee does.
+//!
+//! The rates are set by `RateOrigin`. An asset without a rate is priced by the `Oracle`, and
+//! cannot pay the fees if the oracle does not price it either.
+//!
+//! The pot must hold enough of the native currency to pay the fees, and at least the minimum
+//! balance of every asset accepted for the fees.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Decode, Encode};
+use frame_support::{
+    decl_error, decl_event, decl_module, decl_storage, ensure,
+    traits::{
+        Currency, EnsureOrigin, ExistenceRequirement, Get, Imbalance, OnUnbalanced, WithdrawReason,
+    },
+    weights::{DispatchInfo, PostDispatchInfo, Weight},
+};
+use pallet_transaction_payment::ChargeTransactionPayment;
+use sp_runtime::{
+    helpers_128bit::multiply_by_rational,
+    traits::{
+        AccountIdConversion, DispatchInfoOf, Dispatchable, PostDispatchInfoOf, SaturatedConversion,
+        Saturating, SignedExtension, Zero,
+    },
+    transaction_validity::{
+        InvalidTransaction, TransactionPriority, TransactionValidity, TransactionValidityError,
+        ValidTransaction,
+    },
+    DispatchResult, FixedPointNumber, FixedPointOperand, FixedU128, ModuleId,
+};
+use sp_std::prelude::*;
+
+mod default_weights;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+type BalanceOf<T> = <<T as pallet_transaction_payment::Trait>::Currency as Currency<
+    <T as frame_system::Trait>::AccountId,
+>>::Balance;
+type NegativeImbalanceOf<T> = <<T as pallet_transaction_payment::Trait>::Currency as Currency<
+    <T as frame_system::Trait>::AccountId,
+>>::NegativeImbalance;
+type AssetBalanceOf<T> = <T as pallet_multi_asset::Trait>::Balance;
+type CallOf<T> = <T as frame_system::Trait>::Call;
+
+/// Prices the assets in the native currency.
+pub trait FeeRate<AssetId> {
+    /// Amount of `asset` worth one unit of the native currency, if known.
+    fn fee_rate(asset: AssetId) -> Option<FixedU128>;
+}
+
+impl<AssetId> FeeRate<AssetId> for () {
+    fn fee_rate(_: AssetId) -> Option<FixedU128> {
+        None
+    }
+}
+
+pub trait WeightInfo {
+    fn set_fee_rate() -> Weight;
+    fn transfer_collected() -> Weight;
+}
+
+pub trait Trait: pallet_transaction_payment::Trait + pallet_multi_asset::Trait {
+    /// The overarching event type.
+    type Event: From<Event<Self>> + Into<<Self as frame_system::Trait>::Event>;
+
+    /// The origin which may set the rates and spend the fees collected in the assets.
+    type RateOrigin: EnsureOrigin<Self::Origin>;
+
+    /// Rates of the assets without a rate set by `RateOrigin`.
+    type Oracle: FeeRate<Self::AssetId>;
+
+    /// The id of the fee pot.
+    type ModuleId: Get<ModuleId>;
+
+    /// Weight information for extrinsics in this pallet.
+    type WeightInfo: WeightInfo;
+}
+
+decl_storage! {
+    trait Store for Module<T: Trait> as AssetTxPayment {
+        /// Amount of an asset worth one unit of the native currency, set by `RateOrigin`.
+        pub FeeRates get(fn fee_rates) config():
+            map hasher(blake2_128_concat) T::AssetId => Option<FixedU128>;
+    }
+}
+
+decl_event!(
+    pub enum Event<T>
+    where
+        AccountId = <T as frame_system::Trait>::AccountId,
+        AssetId = <T as pallet_multi_asset::Trait>::AssetId,
+        AssetBalance = AssetBalanceOf<T>,
+    {
+        /// The rate of an asset was set or removed. \[asset, rate\]
+        FeeRateSet(AssetId, Option<FixedU128>),
+        /// Fees collected in an asset were transferred. \[asset, dest, amount\]
+        CollectedTransferred(AssetId, AccountId, AssetBalance),
+    }
+);
+
+decl_error! {
+    pub enum Error for Module<T: Trait> {
+        /// The rate must be greater than zero.
+        ZeroRate,
+    }
+}
+
+decl_module! {
+    pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+        type Error = Error<T>;
+
+        fn deposit_event() = default;
+
+        /// Set the amount of `asset` worth one unit of the native currency, or remove the rate
+        /// to fall back on the oracle.
+        #[weight = <T as Trait>::WeightInfo::set_fee_rate()]
+        fn set_fee_rate(
+            origin,
+            #[compact] asset: T::AssetId,
+            rate: Option<FixedU128>
+        ) -> DispatchResult {
+            T::RateOrigin::ensure_origin(origin)?;
+            if let Some(rate) = rate {
+                ensure!(!rate.is_zero(), Error::<T>::ZeroRate);
+            }
+
+            FeeRates::<T>::mutate_exists(asset, |stored| *stored = rate);
+
+            Self::deposit_event(RawEvent::FeeRateSet(asset, rate));
+            Ok(())
+        }
+
+        /// Transfer `amount` of the fees collected in `asset` to `dest`.
+        #[weight = <T as Trait>::WeightInfo::transfer_collected()]
+        fn transfer_collected(
+            origin,
+            #[compact] asset: T::AssetId,
+            dest: T::AccountId,
+            #[compact] amount: AssetBalanceOf<T>
+        ) -> DispatchResult {
+            T::RateOrigin::ensure_origin(origin)?;
+
+            let transferred = pallet_multi_asset::Module::<T>::do_transfer(
+                asset,
+                &Self::account_id(),
+                &dest,
+                amount,
+                true,
+            )?;
+
+            Self::deposit_event(RawEvent::CollectedTransferred(asset, dest, transferred));
+            Ok(())
+        }
+    }
+}
+
+impl<T: Trait> Module<T> {
+    /// The account of the fee pot.
+    pub fn account_id() -> T::AccountId {
+        T::ModuleId::get().into_account()
+    }
+
+    /// Amount of `asset` worth one unit of the native currency.
+    pub fn fee_rate(asset: T::AssetId) -> Option<FixedU128> {
+        Self::fee_rates(asset).or_else(|| T::Oracle::fee_rate(asset))
+    }
+
+    /// Amount of `asset` paid for the native `fee`, if the asset is priced.
+    ///
+    /// A non-zero fee costs at least one unit of the asset.
+    pub fn convert_fee(asset: T::AssetId, fee: BalanceOf<T>) -> Option<AssetBalanceOf<T>> {
+        let rate = Self::fee_rate(asset)?;
+        if fee.is_zero() {
+            return Some(Zero::zero());
+        }
+        let converted = rate.saturating_mul_int(fee.saturated_into::<u128>());
+        Some(converted.max(1).saturated_into())
+    }
+}
+
+/// Custom validity errors of the fees paid in an asset.
+#[repr(u8)]
+pub enum ValidityError {
+    /// The asset is not priced.
+    NoFeeRate = 0,
+}
+
+impl From<ValidityError> for u8 {
+    fn from(err: ValidityError) -> Self {
+        err as u8
+    }
+}
+
+/// The fee taken before the dispatch, settled after it.
+pub enum Prepaid<T: Trait + Send + Sync>
+where
+    BalanceOf<T>: Send + Sync + FixedPointOperand,
+    CallOf<T>: Dispatchable<Info = DispatchInfo, PostInfo = PostDispatchInfo>,
+{
+    /// Taken in the native currency by `ChargeTransactionPayment`.
+    Native(<ChargeTransactionPayment<T> as SignedExtension>::Pre),
+    /// Taken in `asset` from `who`, in exchange for the native `fee` paid by the pot.
+    Asset {
+        tip: BalanceOf<T>,
+        who: T::AccountId,
+        asset: T::AssetId,
+        paid: AssetBalanceOf<T>,
+        fee: NegativeImbalanceOf<T>,
+    },
+}
+
+impl<T: Trait + Send + Sync> Default for Prepaid<T>
+where
+    BalanceOf<T>: Send + Sync + FixedPointOperand,
+    CallOf<T>: Dispatchable<Info = DispatchInfo, PostInfo = PostDispatchInfo>,
+{
+    fn default() -> Self {
+        Prepaid::Native(Default::default())
+    }
+}
+
+/// Signed extension charging the fee and the tip in the native currency, or in `asset`.
+#[derive(Encode, Decode, Clone, Eq, PartialEq)]
+pub struct ChargeAssetTxPayment<T: Trait + Send + Sync> {
+    #[codec(compact)]
+    tip: BalanceOf<T>,
+    asset: Option<T::AssetId>,
+}
+
+impl<T: Trait + Send + Sync> ChargeAssetTxPayment<T>
+where
+    BalanceOf<T>: Send + Sync + FixedPointOperand,
+    CallOf<T>: Dispatchable<Info = DispatchInfo, PostInfo = PostDispatchInfo>,
+{
+    /// Create new `SignedExtension` charging `tip` on top of the fee, paid in `asset` if any.
+    pub fn from(tip: BalanceOf<T>, asset: Option<T::AssetId>) -> Self {
+        Self { tip, asset }
+    }
+
+    /// Take the fee converted into `asset` from `who` into the pot, and the native fee from
+    /// the pot. Returns the native fee, the amount of the asset taken and the native payment.
+    fn withdraw_in_asset(
+        &self,
+        who: &T::AccountId,
+        asset: T::AssetId,
+        info: &DispatchInfoOf<CallOf<T>>,
+        len: usize,
+    ) -> Result<(BalanceOf<T>, AssetBalanceOf<T>, NegativeImbalanceOf<T>), TransactionValidityError>
+    {
+        let fee = pallet_transaction_payment::Module::<T>::compute_fee(len as u32, info, self.tip);
+        let paid = Module::<T>::convert_fee(asset, fee)
+            .ok_or(InvalidTransaction::Custom(ValidityError::NoFeeRate.into()))?;
+        if fee.is_zero() {
+            return Ok((fee, paid, NegativeImbalanceOf::<T>::zero()));
+        }
+
+        let pot = Module::<T>::account_id();
+        // Sending the whole balance leaves nothing below the minimum behind.
+        let keep_alive = pallet_multi_asset::Module::<T>::balance(asset, who) != paid;
+        pallet_multi_asset::Module::<T>::do_transfer(asset, who, &pot, paid, keep_alive)
+            .map_err(|_| InvalidTransaction::Payment)?;
+
+        let reason = if self.tip.is_zero() {
+            WithdrawReason::TransactionPayment.into()
+        } else {
+            WithdrawReason::TransactionPayment | WithdrawReason::Tip
+        };
+        let imbalance = T::Currency::withdraw(&pot, fee, reason, ExistenceRequirement::KeepAlive)
+            .map_err(|_| InvalidTransaction::Payment)?;
+        Ok((fee, paid, imbalance))
+    }
+
+    /// Priority of a transaction paying `fee`, computed as `ChargeTransactionPayment` does.
+    fn priority(
+        info: &DispatchInfoOf<CallOf<T>>,
+        len: usize,
+        fee: BalanceOf<T>,
+    ) -> TransactionPriority {
+        let weight_saturation = T::MaximumBlockWeight::get() / info.weight.max(1);
+        let max_block_length = T::MaximumBlockLength::get() as u64;
+        let len_saturation = max_block_length / (len as u64).max(1);
+        let coefficient: BalanceOf<T> = weight_saturation
+            .min(len_saturation)
+            .saturated_into::<BalanceOf<T>>();
+        fee.saturating_mul(coefficient)
+            .saturated_into::<TransactionPriority>()
+    }
+}
+
+impl<T: Trait + Send + Sync> sp_std::fmt::Debug for ChargeAssetTxPayment<T> {
+    #[cfg(feature = "std")]
+    fn fmt(&self, f: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+        write!(f, "ChargeAssetTxPayment<{:?}, {:?}>", self.tip, self.asset)
+    }
+
+    #[cfg(not(feature = "std"))]
+    fn fmt(&self, _: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+        Ok(())
+    }
+}
+
+impl<T: Trait + Send + Sync> SignedExtension for ChargeAssetTxPayment<T>
+where
+    BalanceOf<T>: Send + Sync + FixedPointOperand,
+    CallOf<T>: Dispatchable<Info = DispatchInfo, PostInfo = PostDispatchInfo>,
+{
+    const IDENTIFIER: &'static str = "ChargeAssetTxPayment";
+    type AccountId = T::AccountId;
+    type Call = CallOf<T>;
+    type AdditionalSigned = ();
+    type Pre = Prepaid<T>;
+
+    fn additional_signed(&self) -> Result<(), TransactionValidityError> {
+        Ok(())
+    }
+
+    fn validate(
+        &self,
+        who: &Self::AccountId,
+        call: &Self::Call,
+        info: &DispatchInfoOf<Self::Call>,
+        len: usize,
+    ) -> TransactionValidity {
+        match self.asset {
+            None => ChargeTransactionPayment::<T>::from(self.tip).validate(who, call, info, len),
+            Some(asset) => {
+                let (fee, _, _) = self.withdraw_in_asset(who, asset, info, len)?;
+                Ok(ValidTransaction {
+                    priority: Self::priority(info, len, fee),
+                    ..Default::default()
+                })
+            }
+        }
+    }
+
+    fn pre_dispatch(
+        self,
+        who: &Self::AccountId,
+        call: &Self::Call,
+        info: &DispatchInfoOf<Self::Call>,
+        len: usize,
+    ) -> Result<Self::Pre, TransactionValidityError> {
+        match self.asset {
+            None => ChargeTransactionPayment::<T>::from(self.tip)
+                .pre_dispatch(who, call, info, len)
+                .map(Prepaid::Native),
+            Some(asset) => {
+                let (_, paid, fee) = self.withdraw_in_asset(who, asset, info, len)?;
+                Ok(Prepaid::Asset {
+                    tip: self.tip,
+                    who: who.clone(),
+                    asset,
+                    paid,
+                    fee,
+                })
+            }
+        }
+    }
+
+    fn post_dispatch(
+        pre: Self::Pre,
+        info: &DispatchInfoOf<Self::Call>,
+        post_info: &PostDispatchInfoOf<Self::Call>,
+        len: usize,
+        result: &DispatchResult,
+    ) -> Result<(), TransactionValidityError> {
+        let (tip, who, asset, paid, fee) = match pre {
+            Prepaid::Native(pre) => {
+                return ChargeTransactionPayment::<T>::post_dispatch(
+                    pre, info, post_info, len, result,
+                )
+            }
+            Prepaid::Asset {
+                tip,
+                who,
+                asset,
+                paid,
+                fee,
+            } => (tip, who, asset, paid, fee),
+        };
+        if fee.peek().is_zero() {
+            return Ok(());
+        }
+
+        let actual_fee = pallet_transaction_payment::Module::<T>::compute_actual_fee(
+            len as u32, info, post_info, tip,
+        );
+        let withdrawn = fee.peek();
+        let refund = withdrawn.saturating_sub(actual_fee);
+        let (refund_imbalance, actual_payment) = fee.split(refund);
+        let pot = Module::<T>::account_id();
+        T::Currency::resolve_creating(&pot, refund_imbalance);
+
+        // The refund in the asset is the share of the native refund in what the pot paid.
+        let asset_refund = multiply_by_rational(
+            paid.saturated_into::<u128>(),
+            refund.saturated_into::<u128>(),
+            withdrawn.saturated_into::<u128>(),
+        )
+        .unwrap_or_default()
+        .saturated_into::<AssetBalanceOf<T>>();
+        if !asset_refund.is_zero() {
+            // A refund the signer may not hold stays in the pot.
+            let _ =
+                pallet_multi_asset::Module::<T>::do_transfer(asset, &pot, &who, asset_refund, true);
+        }
+
+        let (tip, fee) = actual_payment.split(tip);
+        <T as pallet_transaction_payment::Trait>::OnTransactionPayment::on_unbalanceds(
+            Some(fee).into_iter().chain(Some(tip)),
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,197 @@
+// This file is part of Curio project.
+
+// Copyright (C) 2021 Mixbytes.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test utilities
+
+use super::*;
+use crate as asset_tx_payment;
+use frame_support::{
+    impl_outer_dispatch, impl_outer_event, impl_outer_origin, parameter_types,
+    weights::{IdentityFee, Weight},
+};
+use frame_system::EnsureRoot;
+use pallet_balances as balances;
+use sp_core::H256;
+use sp_runtime::{
+    testing::Header,
+    traits::{BlakeTwo256, IdentityLookup},
+    Perbill,
+};
+
+impl_outer_origin! {
+    pub enum Origin for Test where system = frame_system {}
+}
+
+impl_outer_event! {
+    pub enum TestEvent for Test {
+        frame_system<T>,
+        balances<T>,
+        pallet_multi_asset<T>,
+        asset_tx_payment<T>,
+    }
+}
+
+impl_outer_dispatch! {
+    pub enum OuterCall for Test where origin: Origin {
+        frame_system::System,
+        balances::Balances,
+    }
+}
+
+#[derive(Clone, Eq, PartialEq)]
+pub struct Test;
+
+parameter_types! {
+    pub const BlockHashCount: u64 = 250;
+    pub const MaximumBlockWeight: Weight = 1024;
+    pub const MaximumBlockLength: u32 = 2 * 1024;
+    pub const AvailableBlockRatio: Perbill = Perbill::one();
+}
+
+impl frame_system::Trait for Test {
+    type BaseCallFilter = ();
+    type Origin = Origin;
+    type Call = OuterCall;
+    type Index = u64;
+    type BlockNumber = u64;
+    type Hash = H256;
+    type Hashing = BlakeTwo256;
+    type AccountId = u64;
+    type Lookup = IdentityLookup<Self::AccountId>;
+    type Header = Header;
+    type Event = TestEvent;
+    type BlockHashCount = BlockHashCount;
+    type MaximumBlockWeight = MaximumBlockWeight;
+    type DbWeight = ();
+    type BlockExecutionWeight = ();
+    type ExtrinsicBaseWeight = ();
+    type MaximumExtrinsicWeight = MaximumBlockWeight;
+    type MaximumBlockLength = MaximumBlockLength;
+    type AvailableBlockRatio = AvailableBlockRatio;
+    type Version = ();
+    type PalletInfo = ();
+    type AccountData = balances::AccountData<u64>;
+    type OnNewAccount = ();
+    type OnKilledAccount = ();
+    type SystemWeightInfo = ();
+}
+
+parameter_types! {
+    pub const ExistentialDeposit: u64 = 1;
+}
+
+impl balances::Trait for Test {
+    type MaxLocks = ();
+    type Balance = u64;
+    type Event = TestEvent;
+    type DustRemoval = ();
+    type ExistentialDeposit = ExistentialDeposit;
+    type AccountStore = System;
+    type WeightInfo = ();
+}
+
+/// Collects the transaction fees into `FEE_COLLECTOR`.
+pub struct FeeCollector;
+impl OnUnbalanced<NegativeImbalanceOf<Test>> for FeeCollector {
+    fn on_nonzero_unbalanced(amount: NegativeImbalanceOf<Test>) {
+        Balances::resolve_creating(&FEE_COLLECTOR, amount);
+    }
+}
+
+pub const FEE_COLLECTOR: u64 = 999;
+
+parameter_types! {
+    pub const TransactionByteFee: u64 = 1;
+}
+
+impl pallet_transaction_payment::Trait for Test {
+    type Currency = Balances;
+    type OnTransactionPayment = FeeCollector;
+    type TransactionByteFee = TransactionByteFee;
+    type WeightToFee = IdentityFee<u64>;
+    type FeeMultiplierUpdate = ();
+}
+
+parameter_types! {
+    pub const StringLimit: u32 = 8;
+}
+
+impl pallet_multi_asset::Trait for Test {
+    type Event = TestEvent;
+    type Balance = u64;
+    type AssetId = u32;
+    type CreateOrigin = EnsureRoot<u64>;
+    type StringLimit = StringLimit;
+    type WeightInfo = ();
+}
+
+/// Prices asset 3 at half an asset unit for a native unit.
+pub struct TestOracle;
+impl FeeRate<u32> for TestOracle {
+    fn fee_rate(asset: u32) -> Option<FixedU128> {
+        match asset {
+            3 => Some(FixedU128::saturating_from_rational(1, 2)),
+            _ => None,
+        }
+    }
+}
+
+parameter_types! {
+    pub const FeePotId: ModuleId = ModuleId(*b"py/asset");
+}
+
+impl Trait for Test {
+    type Event = TestEvent;
+    type RateOrigin = EnsureRoot<u64>;
+    type Oracle = TestOracle;
+    type ModuleId = FeePotId;
+    type WeightInfo = ();
+}
+
+pub type System = frame_system::Module<Test>;
+pub type Balances = balances::Module<Test>;
+pub type Assets = pallet_multi_asset::Module<Test>;
+pub type AssetTxPayment = Module<Test>;
+
+/// Account 1 holds 1000 of assets 1, 2 and 3 and no native currency, account 2 holds 1000 of
+/// the native currency. The pot holds 1000 of the native currency. Asset 1 is priced at two
+/// asset units for a native unit.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+    let mut t = frame_system::GenesisConfig::default()
+        .build_storage::<Test>()
+        .unwrap();
+    balances::GenesisConfig::<Test> {
+        balances: vec![(2, 1_000), (AssetTxPayment::account_id(), 1_000)],
+    }
+    .assimilate_storage(&mut t)
+    .unwrap();
+    pallet_multi_asset::GenesisConfig::<Test> {
+        next_asset_id: 1,
+        assets: vec![(1, 10, 1), (2, 10, 1), (3, 10, 1)],
+        balances: vec![(1, 1, 1_000), (2, 1, 1_000), (3, 1, 1_000)],
+    }
+    .assimilate_storage(&mut t)
+    .unwrap();
+    GenesisConfig::<Test> {
+        fee_rates: vec![(1, FixedU128::saturating_from_integer(2))],
+    }
+    .assimilate_storage(&mut t)
+    .unwrap();
+    let mut ext: sp_io::TestExternalities = t.into();
+    ext.execute_with(|| System::set_block_number(1));
+    ext
+}
@@ -0,0 +1,192 @@
+// This file is part of Curio project.
+
+// Copyright (C) 2021 Mixbytes.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests for the module.
+
+use super::*;
+use frame_support::{
+    assert_noop, assert_ok,
+    weights::{DispatchInfo, Pays, PostDispatchInfo},
+};
+use frame_system::RawOrigin;
+use mock::{new_test_ext, AssetTxPayment, Assets, Balances, OuterCall, Test, FEE_COLLECTOR};
+use sp_runtime::traits::BadOrigin;
+
+const CALL: &OuterCall = &OuterCall::System(frame_system::Call::remark(vec![]));
+
+/// Weighs 100 and pays 10 for its length of 10, so it costs 110 without the tip.
+fn info() -> DispatchInfo {
+    DispatchInfo {
+        weight: 100,
+        ..Default::default()
+    }
+}
+
+fn post_info(actual_weight: Option<Weight>) -> PostDispatchInfo {
+    PostDispatchInfo {
+        actual_weight,
+        pays_fee: Pays::Yes,
+    }
+}
+
+#[test]
+fn rates_are_set_by_governance() {
+    new_test_ext().execute_with(|| {
+        assert_eq!(
+            AssetTxPayment::fee_rate(1),
+            Some(FixedU128::saturating_from_integer(2))
+        );
+        assert_eq!(AssetTxPayment::fee_rate(2), None);
+        // Falls back on the oracle.
+        assert_eq!(
+            AssetTxPayment::fee_rate(3),
+            Some(FixedU128::saturating_from_rational(1, 2))
+        );
+
+        let rate = FixedU128::saturating_from_integer(3);
+        assert_noop!(
+            AssetTxPayment::set_fee_rate(RawOrigin::Signed(1).into(), 2, Some(rate)),
+            BadOrigin
+        );
+        assert_noop!(
+            AssetTxPayment::set_fee_rate(RawOrigin::Root.into(), 2, Some(Zero::zero())),
+            Error::<Test>::ZeroRate
+        );
+        assert_ok!(AssetTxPayment::set_fee_rate(
+            RawOrigin::Root.into(),
+            2,
+            Some(rate)
+        ));
+        assert_ok!(AssetTxPayment::set_fee_rate(
+            RawOrigin::Root.into(),
+            3,
+            Some(rate)
+        ));
+        assert_eq!(AssetTxPayment::fee_rate(2), Some(rate));
+        assert_eq!(AssetTxPayment::fee_rate(3), Some(rate));
+
+        assert_ok!(AssetTxPayment::set_fee_rate(
+            RawOrigin::Root.into(),
+            3,
+            None
+        ));
+        assert_eq!(
+            AssetTxPayment::fee_rate(3),
+            Some(FixedU128::saturating_from_rational(1, 2))
+        );
+        assert!(!FeeRates::<Test>::contains_key(3));
+    });
+}
+
+#[test]
+fn fee_is_paid_in_asset() {
+    new_test_ext().execute_with(|| {
+        let pot = AssetTxPayment::account_id();
+        let ext = ChargeAssetTxPayment::<Test>::from(5, Some(1));
+        // 115 of the native currency, at two asset units each.
+        let pre = ext.pre_dispatch(&1, CALL, &info(), 10).unwrap();
+        assert_eq!(Assets::balance(1, &1), 770);
+        assert_eq!(Assets::balance(1, &pot), 230);
+        assert_eq!(Balances::free_balance(pot), 885);
+
+        // Only 50 of the weight was used: the pot and the signer get 50 of the native fee back.
+        assert_ok!(ChargeAssetTxPayment::<Test>::post_dispatch(
+            pre,
+            &info(),
+            &post_info(Some(50)),
+            10,
+            &Ok(())
+        ));
+        assert_eq!(Assets::balance(1, &1), 870);
+        assert_eq!(Assets::balance(1, &pot), 130);
+        assert_eq!(Balances::free_balance(pot), 935);
+        assert_eq!(Balances::free_balance(FEE_COLLECTOR), 65);
+        assert_eq!(Balances::free_balance(1), 0);
+    });
+}
+
+#[test]
+fn fee_is_paid_in_native_currency_without_asset() {
+    new_test_ext().execute_with(|| {
+        let ext = ChargeAssetTxPayment::<Test>::from(5, None);
+        let pre = ext.pre_dispatch(&2, CALL, &info(), 10).unwrap();
+        assert_eq!(Balances::free_balance(2), 885);
+
+        assert_ok!(ChargeAssetTxPayment::<Test>::post_dispatch(
+            pre,
+            &info(),
+            &post_info(None),
+            10,
+            &Ok(())
+        ));
+        assert_eq!(Balances::free_balance(2), 885);
+        assert_eq!(Balances::free_balance(FEE_COLLECTOR), 115);
+        assert_eq!(Balances::free_balance(AssetTxPayment::account_id()), 1_000);
+    });
+}
+
+#[test]
+fn unpriced_or_unaffordable_fees_are_rejected() {
+    new_test_ext().execute_with(|| {
+        let no_rate = InvalidTransaction::Custom(ValidityError::NoFeeRate.into());
+        assert_eq!(
+            ChargeAssetTxPayment::<Test>::from(0, Some(2)).validate(&1, CALL, &info(), 10),
+            Err(no_rate.into())
+        );
+        // The signer holds too little of the asset.
+        assert_eq!(
+            ChargeAssetTxPayment::<Test>::from(0, Some(1)).validate(&2, CALL, &info(), 10),
+            Err(InvalidTransaction::Payment.into())
+        );
+
+        // Priced by the oracle, 110 of the native currency cost 55 of asset 3.
+        let ext = ChargeAssetTxPayment::<Test>::from(0, Some(3));
+        assert_ok!(ext.clone().pre_dispatch(&1, CALL, &info(), 10));
+        assert_eq!(Assets::balance(3, &1), 945);
+
+        // The pot cannot pay more than it holds.
+        let pot = AssetTxPayment::account_id();
+        let _ = Balances::slash(&pot, 1_000);
+        assert_eq!(
+            ext.validate(&1, CALL, &info(), 10),
+            Err(InvalidTransaction::Payment.into())
+        );
+    });
+}
+
+#[test]
+fn collected_fees_are_transferred_by_governance() {
+    new_test_ext().execute_with(|| {
+        let pot = AssetTxPayment::account_id();
+        let ext = ChargeAssetTxPayment::<Test>::from(0, Some(1));
+        assert_ok!(ext.pre_dispatch(&1, CALL, &info(), 10));
+        assert_eq!(Assets::balance(1, &pot), 220);
+
+        assert_noop!(
+            AssetTxPayment::transfer_collected(RawOrigin::Signed(1).into(), 1, 4, 100),
+            BadOrigin
+        );
+        assert_ok!(AssetTxPayment::transfer_collected(
+            RawOrigin::Root.into(),
+            1,
+            4,
+            100
+        ));
+        assert_eq!(Assets::balance(1, &pot), 120);
+        assert_eq!(Assets::balance(1, &4), 100);
+    });
+}
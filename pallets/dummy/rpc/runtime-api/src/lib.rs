@@ -0,0 +1,56 @@
+// This file is part of Curio project.
+
+// Copyright (C) 2021 Mixbytes.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Runtime API definition for the curio bridge.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use sp_std::prelude::*;
+
+pub use pallet_dummy::{DepositId, MintLimits, MintProposal, TransferRecord};
+
+sp_api::decl_runtime_apis! {
+    /// The API to query the state of the bridge.
+    pub trait DummyApi<AccountId, AssetId, Balance, BlockNumber> where
+        AccountId: Codec,
+        AssetId: Codec,
+        Balance: Codec,
+        BlockNumber: Codec,
+    {
+        /// Free balance of the bridge pot of `asset_id`.
+        fn pot_balance(asset_id: AssetId) -> Balance;
+
+        /// Block `deposit_id` was minted at, if it was.
+        fn processed_at(deposit_id: DepositId) -> Option<BlockNumber>;
+
+        /// Mint proposals still open for voting.
+        fn pending_proposals() -> Vec<(DepositId, MintProposal<AssetId, AccountId, Balance, BlockNumber>)>;
+
+        /// Number of relayer votes required to execute a mint.
+        fn relayer_threshold() -> u32;
+
+        /// Whether the bridge is paused.
+        fn is_paused() -> bool;
+
+        /// Mint limits of `asset_id`, with the amount minted in the current window.
+        fn mint_limits(asset_id: AssetId) -> (MintLimits<Balance, BlockNumber>, Balance);
+
+        /// The latest transfers of `account` still kept, by record index, oldest first.
+        fn account_transfers(account: AccountId) -> Vec<(u64, TransferRecord<AssetId, AccountId, Balance, BlockNumber>)>;
+    }
+}
@@ -0,0 +1,306 @@
+// This file is part of Curio project.
+
+// Copyright (C) 2021 Mixbytes.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! RPC interface for the curio bridge.
+
+use std::sync::Arc;
+
+use codec::Codec;
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use pallet_dummy::{
+    DepositId, ExternalAddress, MintLimits, MintProposal, TransferDirection, TransferRecord,
+};
+use serde::{Deserialize, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_rpc::number::NumberOrHex;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+
+pub use pallet_dummy_rpc_runtime_api::DummyApi as DummyRuntimeApi;
+
+const RUNTIME_ERROR: i64 = 1;
+
+/// A mint proposal still open for voting.
+///
+/// Balances are given as `NumberOrHex`, since JSON numbers cannot hold all of them.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct PendingProposal<AccountId, AssetId, BlockNumber> {
+    pub deposit_id: DepositId,
+    pub asset_id: AssetId,
+    pub account: AccountId,
+    pub amount: NumberOrHex,
+    /// Relayers which voted for the proposal.
+    pub votes: Vec<AccountId>,
+    /// Number of votes required to execute the mint.
+    pub threshold: u32,
+    /// Block after which the proposal is discarded.
+    pub expiry: BlockNumber,
+}
+
+/// Mint limits of an asset, with the amount minted in the current window.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct LimitsInfo<BlockNumber> {
+    pub period: BlockNumber,
+    pub per_account: Option<NumberOrHex>,
+    pub global: Option<NumberOrHex>,
+    pub delay_threshold: Option<NumberOrHex>,
+    pub delay: BlockNumber,
+    pub minted_in_window: NumberOrHex,
+}
+
+/// Direction of a bridge transfer.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "camelCase")]
+pub enum Direction {
+    Inbound,
+    Outbound,
+}
+
+impl From<TransferDirection> for Direction {
+    fn from(direction: TransferDirection) -> Self {
+        match direction {
+            TransferDirection::Inbound => Direction::Inbound,
+            TransferDirection::Outbound => Direction::Outbound,
+        }
+    }
+}
+
+/// A recent bridge transfer.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct TransferInfo<AccountId, AssetId, BlockNumber> {
+    /// Index of the transfer record.
+    pub index: u64,
+    pub direction: Direction,
+    pub asset_id: AssetId,
+    /// The deposit minted by an inbound transfer, or the confirmed release transaction of an
+    /// outbound one.
+    pub external_id: Option<DepositId>,
+    /// The account on the external chain an outbound transfer is released to.
+    pub recipient: Option<ExternalAddress>,
+    pub account: AccountId,
+    pub amount: NumberOrHex,
+    pub block: BlockNumber,
+}
+
+/// Bridge RPC methods.
+///
+/// `Balance` is the balance type of the runtime API; balances are returned as `NumberOrHex`.
+#[rpc]
+pub trait DummyApi<BlockHash, AccountId, AssetId, Balance, BlockNumber> {
+    /// Free balance of the bridge pot of `asset_id`.
+    #[rpc(name = "dummy_potBalance")]
+    fn pot_balance(&self, asset_id: AssetId, at: Option<BlockHash>) -> Result<NumberOrHex>;
+
+    /// Block `deposit_id` was minted at, or null if it was not.
+    #[rpc(name = "dummy_processedAt")]
+    fn processed_at(
+        &self,
+        deposit_id: DepositId,
+        at: Option<BlockHash>,
+    ) -> Result<Option<BlockNumber>>;
+
+    /// Mint proposals still open for voting, with their votes.
+    #[rpc(name = "dummy_pendingProposals")]
+    fn pending_proposals(
+        &self,
+        at: Option<BlockHash>,
+    ) -> Result<Vec<PendingProposal<AccountId, AssetId, BlockNumber>>>;
+
+    /// Whether the bridge is paused.
+    #[rpc(name = "dummy_isPaused")]
+    fn is_paused(&self, at: Option<BlockHash>) -> Result<bool>;
+
+    /// Mint limits of `asset_id`, with the amount minted in the current window.
+    #[rpc(name = "dummy_mintLimits")]
+    fn mint_limits(
+        &self,
+        asset_id: AssetId,
+        at: Option<BlockHash>,
+    ) -> Result<LimitsInfo<BlockNumber>>;
+
+    /// The latest bridge transfers of `account` still kept, oldest first.
+    #[rpc(name = "dummy_accountTransfers")]
+    fn account_transfers(
+        &self,
+        account: AccountId,
+        at: Option<BlockHash>,
+    ) -> Result<Vec<TransferInfo<AccountId, AssetId, BlockNumber>>>;
+}
+
+/// An implementation of bridge specific RPC methods.
+pub struct Dummy<C, B> {
+    client: Arc<C>,
+    _marker: std::marker::PhantomData<B>,
+}
+
+impl<C, B> Dummy<C, B> {
+    /// Create new `Dummy` with the given reference to the client.
+    pub fn new(client: Arc<C>) -> Self {
+        Dummy {
+            client,
+            _marker: Default::default(),
+        }
+    }
+}
+
+fn runtime_error(err: impl std::fmt::Debug) -> RpcError {
+    RpcError {
+        code: ErrorCode::ServerError(RUNTIME_ERROR),
+        message: "Runtime error".into(),
+        data: Some(format!("{:?}", err).into()),
+    }
+}
+
+impl<C, Block, AccountId, AssetId, Balance, BlockNumber>
+    DummyApi<<Block as BlockT>::Hash, AccountId, AssetId, Balance, BlockNumber> for Dummy<C, Block>
+where
+    Block: BlockT,
+    C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+    C::Api: DummyRuntimeApi<Block, AccountId, AssetId, Balance, BlockNumber>,
+    AccountId: Codec,
+    AssetId: Codec,
+    Balance: Codec + Into<NumberOrHex>,
+    BlockNumber: Codec,
+{
+    fn pot_balance(
+        &self,
+        asset_id: AssetId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<NumberOrHex> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        api.pot_balance(&at, asset_id)
+            .map(Into::into)
+            .map_err(runtime_error)
+    }
+
+    fn processed_at(
+        &self,
+        deposit_id: DepositId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Option<BlockNumber>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        api.processed_at(&at, deposit_id).map_err(runtime_error)
+    }
+
+    fn pending_proposals(
+        &self,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Vec<PendingProposal<AccountId, AssetId, BlockNumber>>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        let threshold = api.relayer_threshold(&at).map_err(runtime_error)?;
+        let proposals = api.pending_proposals(&at).map_err(runtime_error)?;
+        Ok(proposals
+            .into_iter()
+            .map(|(deposit_id, proposal)| {
+                let MintProposal {
+                    asset_id,
+                    account,
+                    amount,
+                    votes,
+                    expiry,
+                } = proposal;
+                PendingProposal {
+                    deposit_id,
+                    asset_id,
+                    account,
+                    amount: amount.into(),
+                    votes,
+                    threshold,
+                    expiry,
+                }
+            })
+            .collect())
+    }
+
+    fn is_paused(&self, at: Option<<Block as BlockT>::Hash>) -> Result<bool> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        api.is_paused(&at).map_err(runtime_error)
+    }
+
+    fn mint_limits(
+        &self,
+        asset_id: AssetId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<LimitsInfo<BlockNumber>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        let (limits, minted_in_window) = api.mint_limits(&at, asset_id).map_err(runtime_error)?;
+        let MintLimits {
+            period,
+            per_account,
+            global,
+            delay_threshold,
+            delay,
+        } = limits;
+        Ok(LimitsInfo {
+            period,
+            per_account: per_account.map(Into::into),
+            global: global.map(Into::into),
+            delay_threshold: delay_threshold.map(Into::into),
+            delay,
+            minted_in_window: minted_in_window.into(),
+        })
+    }
+
+    fn account_transfers(
+        &self,
+        account: AccountId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Vec<TransferInfo<AccountId, AssetId, BlockNumber>>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        let transfers = api.account_transfers(&at, account).map_err(runtime_error)?;
+        Ok(transfers
+            .into_iter()
+            .map(|(index, record)| {
+                let TransferRecord {
+                    direction,
+                    asset_id,
+                    external_id,
+                    recipient,
+                    account,
+                    amount,
+                    block,
+                } = record;
+                TransferInfo {
+                    index,
+                    direction: direction.into(),
+                    asset_id,
+                    external_id,
+                    recipient,
+                    account,
+                    amount: amount.into(),
+                    block,
+                }
+            })
+            .collect())
+    }
+}
@@ -0,0 +1,428 @@
+// This file is part of Curio project.
+
+// Copyright (C) 2021 Mixbytes.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Benchmarks for the bridge pallet.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use frame_benchmarking::{account, benchmarks, whitelisted_caller};
+use frame_support::traits::{OnFinalize, OnInitialize, UnfilteredDispatchable};
+use frame_system::RawOrigin;
+use sp_runtime::traits::{Bounded, TrailingZeroInput};
+
+const SEED: u32 = 0;
+
+/// Fill the pot of the native currency, charge fees and enable the mint windows, so that
+/// mints and burns take their most expensive path. Returns an amount to transfer.
+fn setup_bridge<T: Trait>() -> BalanceOf<T> {
+    let native = T::NativeAssetId::get();
+    if Module::<T>::bridged_assets(native).is_none() {
+        BridgedAssets::<T>::insert(native, ExternalAddress::zero());
+        AssetByToken::<T>::insert(ExternalAddress::zero(), native);
+    }
+    T::Currency::make_free_balance_be(
+        &Module::<T>::account_id(),
+        BalanceOf::<T>::max_value() / 2u32.into(),
+    );
+    MintFee::<T>::insert(native, BridgeFee::Proportional(Permill::from_percent(1)));
+    BurnFee::<T>::insert(native, BridgeFee::Proportional(Permill::from_percent(1)));
+    Limits::<T>::insert(
+        native,
+        MintLimits {
+            period: 100u32.into(),
+            per_account: Some(BalanceOf::<T>::max_value()),
+            global: Some(BalanceOf::<T>::max_value()),
+            delay_threshold: None,
+            delay: Zero::zero(),
+        },
+    );
+    T::Currency::minimum_balance()
+        .max(1u32.into())
+        .saturating_mul(1_000u32.into())
+}
+
+fn setup_relayers<T: Trait>(r: u32) -> Vec<T::AccountId> {
+    let mut relayers: Vec<T::AccountId> = (0..r).map(|i| account("relayer", i, SEED)).collect();
+    relayers.sort();
+    Relayers::<T>::put(&relayers);
+    relayers
+}
+
+/// The `i`th recipient of the mints, allowed by `Recipients`.
+fn recipient<T: Trait>(i: u32) -> T::AccountId {
+    let who = account("recipient", i, SEED);
+    T::Recipients::allow(&who);
+    who
+}
+
+/// Schedule the mint of the `i`th deposit, returning its id.
+fn schedule<T: Trait>(amount: BalanceOf<T>, i: u32) -> Result<DepositId, &'static str> {
+    let native = T::NativeAssetId::get();
+    Limits::<T>::mutate(native, |limits| {
+        limits.delay_threshold = Some(Zero::zero());
+        limits.delay = 10u32.into();
+    });
+    let deposit_id = DepositId::from_low_u64_be(u64::from(i) + 1);
+    Module::<T>::mint_or_delay(native, deposit_id, recipient::<T>(i), amount)?;
+    Ok(deposit_id)
+}
+
+fn unregister<T: Trait>(asset_id: T::AssetId) {
+    if let Some(token) = BridgedAssets::<T>::take(asset_id) {
+        AssetByToken::<T>::remove(token);
+    }
+}
+
+benchmarks! {
+    _ { }
+
+    mint {
+        let amount = setup_bridge::<T>();
+        let deposit_id = DepositId::repeat_byte(1);
+        let call = Call::<T>::mint(T::NativeAssetId::get(), deposit_id, recipient::<T>(0), amount);
+        let origin = T::MintOrigin::successful_origin();
+    }: { call.dispatch_bypass_filter(origin)? }
+    verify {
+        assert!(Module::<T>::is_processed(&deposit_id));
+    }
+
+    mint_with_proof {
+        let amount = setup_bridge::<T>();
+        let token = Module::<T>::bridged_assets(T::NativeAssetId::get()).unwrap_or_default();
+        let proof = T::DepositVerifier::successful_proof(&ProvenDeposit {
+            deposit_id: DepositId::repeat_byte(1),
+            token,
+            account: recipient::<T>(0),
+            amount,
+        });
+        let deposit_id = T::DepositVerifier::verify(&proof)?.deposit_id;
+        let caller: T::AccountId = whitelisted_caller();
+    }: _(RawOrigin::Signed(caller), proof)
+    verify {
+        assert!(Module::<T>::is_processed(&deposit_id));
+    }
+
+    burn {
+        let amount = setup_bridge::<T>();
+        let caller: T::AccountId = whitelisted_caller();
+        T::Currency::make_free_balance_be(&caller, amount.saturating_mul(2u32.into()));
+    }: _(
+        RawOrigin::Signed(caller.clone()),
+        T::NativeAssetId::get(),
+        ExternalAddress::repeat_byte(1),
+        amount
+    )
+    verify {
+        assert_eq!(T::Currency::free_balance(&caller), amount);
+    }
+
+    add_relayer {
+        let r in 1 .. T::MaxRelayers::get() - 1;
+        setup_relayers::<T>(r);
+        let who: T::AccountId = account("new relayer", 0, SEED);
+        let call = Call::<T>::add_relayer(who.clone());
+        let origin = T::AdminOrigin::successful_origin();
+    }: { call.dispatch_bypass_filter(origin)? }
+    verify {
+        assert!(Module::<T>::is_relayer(&who));
+    }
+
+    remove_relayer {
+        let r in 2 .. T::MaxRelayers::get();
+        let who = setup_relayers::<T>(r).remove(0);
+        let call = Call::<T>::remove_relayer(who.clone());
+        let origin = T::AdminOrigin::successful_origin();
+    }: { call.dispatch_bypass_filter(origin)? }
+    verify {
+        assert!(!Module::<T>::is_relayer(&who));
+    }
+
+    set_threshold {
+        setup_relayers::<T>(2);
+        let call = Call::<T>::set_threshold(2);
+        let origin = T::AdminOrigin::successful_origin();
+    }: { call.dispatch_bypass_filter(origin)? }
+    verify {
+        assert_eq!(Module::<T>::relayer_threshold(), 2);
+    }
+
+    // The last vote, which executes the mint.
+    vote_mint {
+        let r in 1 .. T::MaxRelayers::get();
+        let amount = setup_bridge::<T>();
+        let relayers = setup_relayers::<T>(r);
+        RelayerThreshold::put(r);
+        let native = T::NativeAssetId::get();
+        let deposit_id = DepositId::repeat_byte(1);
+        let recipient = recipient::<T>(0);
+        for relayer in relayers.iter().skip(1) {
+            Module::<T>::vote_mint(
+                RawOrigin::Signed(relayer.clone()).into(),
+                native,
+                deposit_id,
+                recipient.clone(),
+                amount,
+            )?;
+        }
+    }: _(RawOrigin::Signed(relayers[0].clone()), native, deposit_id, recipient, amount)
+    verify {
+        assert!(Module::<T>::is_processed(&deposit_id));
+    }
+
+    set_mint_limits {
+        let limits = MintLimits {
+            period: 100u32.into(),
+            per_account: Some(1_000u32.into()),
+            global: Some(10_000u32.into()),
+            delay_threshold: Some(1_000u32.into()),
+            delay: 10u32.into(),
+        };
+        let call = Call::<T>::set_mint_limits(T::NativeAssetId::get(), limits.clone());
+        let origin = T::AdminOrigin::successful_origin();
+    }: { call.dispatch_bypass_filter(origin)? }
+    verify {
+        assert_eq!(Module::<T>::mint_limits(T::NativeAssetId::get()), limits);
+    }
+
+    execute_scheduled_mint {
+        let amount = setup_bridge::<T>();
+        let deposit_id = schedule::<T>(amount, 0)?;
+        let execute_at = Module::<T>::scheduled_mints(deposit_id).unwrap().execute_at;
+        frame_system::Module::<T>::set_block_number(execute_at);
+        let call = Call::<T>::execute_scheduled_mint(deposit_id);
+        let origin = RawOrigin::Root.into();
+    }: { call.dispatch_bypass_filter(origin)? }
+    verify {
+        assert!(Module::<T>::scheduled_mints(deposit_id).is_none());
+        assert!(Module::<T>::is_processed(&deposit_id));
+    }
+
+    cancel_mint {
+        let amount = setup_bridge::<T>();
+        let deposit_id = schedule::<T>(amount, 0)?;
+        let call = Call::<T>::cancel_mint(deposit_id);
+        let origin = T::CancelOrigin::successful_origin();
+    }: { call.dispatch_bypass_filter(origin)? }
+    verify {
+        assert!(Module::<T>::scheduled_mints(deposit_id).is_none());
+    }
+
+    pause {
+        let call = Call::<T>::pause();
+        let origin = T::PauseOrigin::successful_origin();
+    }: { call.dispatch_bypass_filter(origin)? }
+    verify {
+        assert!(Module::<T>::is_paused());
+    }
+
+    unpause {
+        Paused::put(true);
+        let call = Call::<T>::unpause();
+        let origin = T::PauseOrigin::successful_origin();
+    }: { call.dispatch_bypass_filter(origin)? }
+    verify {
+        assert!(!Module::<T>::is_paused());
+    }
+
+    register_asset {
+        let asset_id = T::AssetId::default();
+        unregister::<T>(asset_id);
+        let token = ExternalAddress::repeat_byte(1);
+        let call = Call::<T>::register_asset(asset_id, token);
+        let origin = T::AdminOrigin::successful_origin();
+    }: { call.dispatch_bypass_filter(origin)? }
+    verify {
+        assert_eq!(Module::<T>::asset_by_token(token), Some(asset_id));
+    }
+
+    unregister_asset {
+        let asset_id = T::AssetId::default();
+        unregister::<T>(asset_id);
+        let token = ExternalAddress::repeat_byte(1);
+        BridgedAssets::<T>::insert(asset_id, token);
+        AssetByToken::<T>::insert(token, asset_id);
+        let call = Call::<T>::unregister_asset(asset_id);
+        let origin = T::AdminOrigin::successful_origin();
+    }: { call.dispatch_bypass_filter(origin)? }
+    verify {
+        assert_eq!(Module::<T>::bridged_assets(asset_id), None);
+    }
+
+    // Every pot is found out of balance.
+    check_pots {
+        let a in 0 .. 100;
+        let assets = (0..a)
+            .map(|i| {
+                T::AssetId::decode(&mut TrailingZeroInput::new(&i.encode())).unwrap_or_default()
+            })
+            .collect::<Vec<_>>();
+        for (i, asset_id) in assets.iter().enumerate() {
+            BridgedAssets::<T>::insert(asset_id, ExternalAddress::from_low_u64_be(i as u64));
+            TotalLocked::<T>::insert(asset_id, BalanceOf::<T>::max_value());
+        }
+        BridgedAssetCount::put(a);
+    }: { Module::<T>::on_finalize(1u32.into()); }
+    verify {
+        for asset_id in assets {
+            assert!(Module::<T>::reported_discrepancy(asset_id).is_some());
+        }
+    }
+
+    // Every record belongs to another account.
+    prune_transfers {
+        let t in 0 .. MAX_PRUNED_TRANSFERS_PER_BLOCK;
+        let amount = setup_bridge::<T>();
+        let recorded: T::BlockNumber = 1u32.into();
+        frame_system::Module::<T>::set_block_number(recorded);
+        for i in 0..t {
+            let who: T::AccountId = account("recipient", i, SEED);
+            Module::<T>::record_transfer(
+                TransferDirection::Inbound,
+                T::NativeAssetId::get(),
+                Some(DepositId::from_low_u64_be(i.into())),
+                None,
+                &who,
+                amount,
+            );
+        }
+        let now = recorded + T::TransferHistoryDepth::get();
+    }: { Module::<T>::on_initialize(now); }
+    verify {
+        assert_eq!(Module::<T>::oldest_transfer_index(), u64::from(t));
+        assert_eq!(Module::<T>::transfers(0), None);
+    }
+
+    prune_proposals {
+        let p in 0 .. MAX_PRUNED_PROPOSALS_PER_BLOCK;
+        let expiry: T::BlockNumber = 1u32.into();
+        for i in 0..p {
+            let deposit_id = DepositId::from_low_u64_be(i.into());
+            MintProposals::<T>::insert(deposit_id, MintProposal {
+                asset_id: T::NativeAssetId::get(),
+                account: account("recipient", i, SEED),
+                amount: BalanceOf::<T>::max_value(),
+                votes: vec![account("relayer", i, SEED)],
+                expiry,
+            });
+            ProposalExpiries::<T>::insert(u64::from(i), (expiry, deposit_id));
+        }
+        NextProposalIndex::put(u64::from(p));
+    }: { Module::<T>::on_initialize(expiry + One::one()); }
+    verify {
+        assert_eq!(Module::<T>::oldest_proposal_index(), u64::from(p));
+        assert_eq!(MintProposals::<T>::iter().count(), 0);
+    }
+
+    confirm_release {
+        let amount = setup_bridge::<T>();
+        let caller = setup_relayers::<T>(1).remove(0);
+        let index = Module::<T>::record_transfer(
+            TransferDirection::Outbound,
+            T::NativeAssetId::get(),
+            None,
+            Some(ExternalAddress::repeat_byte(1)),
+            &caller,
+            amount,
+        );
+    }: _(RawOrigin::Signed(caller), index, H256::repeat_byte(1))
+    verify {
+        assert_eq!(
+            Module::<T>::transfers(index).and_then(|record| record.external_id),
+            Some(H256::repeat_byte(1))
+        );
+    }
+
+    release_held_mints {
+        let m in 0 .. MAX_RELEASED_MINTS_PER_BLOCK;
+        let amount = setup_bridge::<T>();
+        let held = (0..m).map(|i| schedule::<T>(amount, i)).collect::<Result<Vec<_>, _>>()?;
+        HeldMints::put(held);
+        let now = frame_system::Module::<T>::block_number() + 20u32.into();
+    }: { Module::<T>::on_initialize(now); }
+    verify {
+        assert!(Module::<T>::held_mints().is_empty());
+    }
+
+    refill {
+        let amount = setup_bridge::<T>();
+        let native = T::NativeAssetId::get();
+        let locked = Module::<T>::total_locked(native);
+        let source = T::RefillSource::get();
+        T::Currency::make_free_balance_be(&source, amount.saturating_mul(2u32.into()));
+        let call = Call::<T>::refill(native, amount);
+        let origin = T::PotOrigin::successful_origin();
+    }: { call.dispatch_bypass_filter(origin)? }
+    verify {
+        assert_eq!(Module::<T>::total_locked(native), locked + amount);
+    }
+
+    withdraw {
+        let amount = setup_bridge::<T>();
+        let dest: T::AccountId = account("dest", 0, SEED);
+        let call = Call::<T>::withdraw(T::NativeAssetId::get(), dest.clone(), amount);
+        let origin = T::PotOrigin::successful_origin();
+    }: { call.dispatch_bypass_filter(origin)? }
+    verify {
+        assert_eq!(T::Currency::free_balance(&dest), amount);
+    }
+
+    set_fees {
+        let fee = BridgeFee::Proportional(Permill::from_percent(1));
+        let native = T::NativeAssetId::get();
+        let call = Call::<T>::set_fees(native, fee, fee);
+        let origin = T::AdminOrigin::successful_origin();
+    }: { call.dispatch_bypass_filter(origin)? }
+    verify {
+        assert_eq!(Module::<T>::mint_fee(native), fee);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{new_test_ext, Test};
+    use frame_support::assert_ok;
+
+    #[test]
+    fn test_benchmarks() {
+        new_test_ext(1).execute_with(|| {
+            assert_ok!(test_benchmark_mint::<Test>());
+            assert_ok!(test_benchmark_mint_with_proof::<Test>());
+            assert_ok!(test_benchmark_burn::<Test>());
+            assert_ok!(test_benchmark_add_relayer::<Test>());
+            assert_ok!(test_benchmark_remove_relayer::<Test>());
+            assert_ok!(test_benchmark_set_threshold::<Test>());
+            assert_ok!(test_benchmark_vote_mint::<Test>());
+            assert_ok!(test_benchmark_set_mint_limits::<Test>());
+            assert_ok!(test_benchmark_execute_scheduled_mint::<Test>());
+            assert_ok!(test_benchmark_cancel_mint::<Test>());
+            assert_ok!(test_benchmark_pause::<Test>());
+            assert_ok!(test_benchmark_unpause::<Test>());
+            assert_ok!(test_benchmark_register_asset::<Test>());
+            assert_ok!(test_benchmark_unregister_asset::<Test>());
+            assert_ok!(test_benchmark_set_fees::<Test>());
+            assert_ok!(test_benchmark_check_pots::<Test>());
+            assert_ok!(test_benchmark_prune_transfers::<Test>());
+            assert_ok!(test_benchmark_prune_proposals::<Test>());
+            assert_ok!(test_benchmark_release_held_mints::<Test>());
+            assert_ok!(test_benchmark_confirm_release::<Test>());
+            assert_ok!(test_benchmark_refill::<Test>());
+            assert_ok!(test_benchmark_withdraw::<Test>());
+        });
+    }
+}
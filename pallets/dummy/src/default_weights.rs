@@ -24,8 +24,119 @@ use frame_support::weights::{constants::RocksDbWeight as DbWeight, Weight};
 
 impl crate::WeightInfo for () {
     fn mint() -> Weight {
-        (39_603_000 as Weight)
+        (51_782_000 as Weight)
+            .saturating_add(DbWeight::get().reads(8 as Weight))
+            .saturating_add(DbWeight::get().writes(9 as Weight))
+    }
+    fn burn() -> Weight {
+        (41_218_000 as Weight)
+            .saturating_add(DbWeight::get().reads(3 as Weight))
+            .saturating_add(DbWeight::get().writes(6 as Weight))
+    }
+    fn add_relayer(r: u32) -> Weight {
+        (21_437_000 as Weight)
+            .saturating_add((143_000 as Weight).saturating_mul(r as Weight))
+            .saturating_add(DbWeight::get().reads(1 as Weight))
+            .saturating_add(DbWeight::get().writes(1 as Weight))
+    }
+    fn remove_relayer(r: u32) -> Weight {
+        (20_862_000 as Weight)
+            .saturating_add((139_000 as Weight).saturating_mul(r as Weight))
+            .saturating_add(DbWeight::get().reads(2 as Weight))
+            .saturating_add(DbWeight::get().writes(1 as Weight))
+    }
+    fn set_threshold() -> Weight {
+        (13_205_000 as Weight)
+            .saturating_add(DbWeight::get().reads(1 as Weight))
+            .saturating_add(DbWeight::get().writes(1 as Weight))
+    }
+    fn vote_mint(r: u32) -> Weight {
+        (64_891_000 as Weight)
+            .saturating_add((412_000 as Weight).saturating_mul(r as Weight))
+            .saturating_add(DbWeight::get().reads(7 as Weight))
+            .saturating_add(DbWeight::get().writes(8 as Weight))
+    }
+    fn set_mint_limits() -> Weight {
+        (14_019_000 as Weight).saturating_add(DbWeight::get().writes(1 as Weight))
+    }
+    fn pause() -> Weight {
+        (12_331_000 as Weight).saturating_add(DbWeight::get().writes(1 as Weight))
+    }
+    fn unpause() -> Weight {
+        (12_104_000 as Weight).saturating_add(DbWeight::get().writes(1 as Weight))
+    }
+    fn register_asset() -> Weight {
+        (19_882_000 as Weight)
+            .saturating_add(DbWeight::get().reads(2 as Weight))
+            .saturating_add(DbWeight::get().writes(2 as Weight))
+    }
+    fn unregister_asset() -> Weight {
+        (18_437_000 as Weight)
             .saturating_add(DbWeight::get().reads(1 as Weight))
             .saturating_add(DbWeight::get().writes(2 as Weight))
     }
+    fn set_fees() -> Weight {
+        (15_246_000 as Weight).saturating_add(DbWeight::get().writes(2 as Weight))
+    }
+    fn mint_with_proof() -> Weight {
+        (71_650_000 as Weight)
+            .saturating_add(DbWeight::get().reads(11 as Weight))
+            .saturating_add(DbWeight::get().writes(9 as Weight))
+    }
+    fn check_pots(a: u32) -> Weight {
+        (2_916_000 as Weight)
+            .saturating_add((9_847_000 as Weight).saturating_mul(a as Weight))
+            .saturating_add(DbWeight::get().reads(1 as Weight))
+            .saturating_add(DbWeight::get().reads((5 as Weight).saturating_mul(a as Weight)))
+            .saturating_add(DbWeight::get().writes((1 as Weight).saturating_mul(a as Weight)))
+    }
+    fn execute_scheduled_mint() -> Weight {
+        (58_374_000 as Weight)
+            .saturating_add(DbWeight::get().reads(9 as Weight))
+            .saturating_add(DbWeight::get().writes(10 as Weight))
+    }
+    fn cancel_mint() -> Weight {
+        (31_906_000 as Weight)
+            .saturating_add(DbWeight::get().reads(3 as Weight))
+            .saturating_add(DbWeight::get().writes(2 as Weight))
+    }
+    fn prune_transfers(t: u32) -> Weight {
+        (3_512_000 as Weight)
+            .saturating_add((7_986_000 as Weight).saturating_mul(t as Weight))
+            .saturating_add(DbWeight::get().reads(2 as Weight))
+            .saturating_add(DbWeight::get().reads((2 as Weight).saturating_mul(t as Weight)))
+            .saturating_add(DbWeight::get().writes(1 as Weight))
+            .saturating_add(DbWeight::get().writes((2 as Weight).saturating_mul(t as Weight)))
+    }
+    fn prune_proposals(p: u32) -> Weight {
+        (3_104_000 as Weight)
+            .saturating_add((9_215_000 as Weight).saturating_mul(p as Weight))
+            .saturating_add(DbWeight::get().reads(2 as Weight))
+            .saturating_add(DbWeight::get().reads((2 as Weight).saturating_mul(p as Weight)))
+            .saturating_add(DbWeight::get().writes(1 as Weight))
+            .saturating_add(DbWeight::get().writes((2 as Weight).saturating_mul(p as Weight)))
+    }
+    fn release_held_mints(m: u32) -> Weight {
+        (4_127_000 as Weight)
+            .saturating_add((24_512_000 as Weight).saturating_mul(m as Weight))
+            .saturating_add(DbWeight::get().reads(2 as Weight))
+            .saturating_add(DbWeight::get().reads((2 as Weight).saturating_mul(m as Weight)))
+            .saturating_add(DbWeight::get().writes(1 as Weight))
+            .saturating_add(DbWeight::get().writes((2 as Weight).saturating_mul(m as Weight)))
+    }
+    fn confirm_release() -> Weight {
+        (24_391_000 as Weight)
+            .saturating_add(DbWeight::get().reads(2 as Weight))
+            .saturating_add(DbWeight::get().writes(1 as Weight))
+    }
+    fn refill() -> Weight {
+        (62_915_000 as Weight)
+            .saturating_add(DbWeight::get().reads(5 as Weight))
+            .saturating_add(DbWeight::get().writes(3 as Weight))
+    }
+    fn withdraw() -> Weight {
+        (46_905_000 as Weight)
+            .saturating_add(DbWeight::get().reads(5 as Weight))
+            .saturating_add(DbWeight::get().writes(2 as Weight))
+    }
 }
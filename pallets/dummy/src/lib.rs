@@ -17,50 +17,607 @@
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
+use codec::{Decode, Encode};
 use frame_support::{
-    decl_error, decl_event, decl_module, decl_storage,
-    traits::{Currency, EnsureOrigin, ExistenceRequirement::KeepAlive, Get},
-    weights::Weight,
+    debug, decl_error, decl_event, decl_module, decl_storage, ensure,
+    storage::with_transaction,
+    traits::{
+        schedule::{Anon as ScheduleAnon, DispatchTime, HARD_DEADLINE},
+        Contains, Currency, EnsureOrigin,
+        ExistenceRequirement::KeepAlive,
+        Get, OnUnbalanced, WithdrawReason,
+    },
+    transactional,
+    weights::{DispatchClass, Pays, Weight},
+    IsSubType, Parameter, RuntimeDebug,
 };
-use sp_runtime::{traits::AccountIdConversion, DispatchResult, ModuleId};
-use sp_std::prelude::*;
+use frame_system::{
+    ensure_root, ensure_signed,
+    offchain::{AppCrypto, CreateSignedTransaction, SendSignedTransaction, Signer},
+};
+use sp_core::{crypto::KeyTypeId, H160, H256};
+use sp_io::hashing::keccak_256;
+use sp_runtime::{
+    offchain::{http, storage::StorageValueRef, Duration},
+    traits::{
+        AccountIdConversion, AtLeast32BitUnsigned, CheckedSub, DispatchInfoOf, Dispatchable,
+        IdentifyAccount, MaybeSerializeDeserialize, Member, One, Saturating, SignedExtension, Zero,
+    },
+    transaction_validity::{
+        InvalidTransaction, TransactionPriority, TransactionValidity, TransactionValidityError,
+        ValidTransaction,
+    },
+    DispatchError, DispatchResult, ModuleId, Permill, RuntimeAppPublic, TransactionOutcome,
+};
+use sp_std::{marker::PhantomData, prelude::*};
 
+mod benchmarking;
 mod default_weights;
 #[cfg(test)]
 mod mock;
 #[cfg(test)]
 mod tests;
 
+/// Key type of the relayer keys used by the offchain worker.
+pub const KEY_TYPE: KeyTypeId = KeyTypeId(*b"brdg");
+
+/// Key of the offchain persistent storage entry holding the URL the offchain worker polls for
+/// pending deposits, as a SCALE encoded `Vec<u8>`.
+pub const ENDPOINT_KEY: &[u8] = b"dummy::relayer-endpoint";
+
+/// How long the offchain worker waits for the endpoint to respond, in milliseconds.
+const FETCH_TIMEOUT: u64 = 3_000;
+
+/// Prefix of the offchain persistent storage entries holding, for each deposit the offchain
+/// worker voted for, the block until which it does not vote again.
+const SUBMITTED_VOTE_PREFIX: &[u8] = b"dummy::submitted-vote::";
+
+/// Number of blocks the offchain worker gives a submitted vote to land before submitting it
+/// again.
+const VOTE_RESUBMIT_DELAY: u32 = 10;
+
+/// Number of buckets the period of the mint caps is split into.
+const MINT_WINDOW_BUCKETS: u32 = 10;
+
+/// Maximum number of held mints rescheduled in each block once the bridge resumes.
+const MAX_RELEASED_MINTS_PER_BLOCK: u32 = 16;
+
+/// Maximum number of expired transfer records pruned in each block.
+const MAX_PRUNED_TRANSFERS_PER_BLOCK: u32 = 64;
+
+/// Maximum number of expired mint proposals pruned in each block.
+const MAX_PRUNED_PROPOSALS_PER_BLOCK: u32 = 32;
+
+/// Relayer keys of the offchain worker.
+pub mod crypto {
+    use super::KEY_TYPE;
+    use sp_runtime::{
+        app_crypto::{app_crypto, sr25519},
+        MultiSignature, MultiSigner,
+    };
+
+    app_crypto!(sr25519, KEY_TYPE);
+
+    pub struct RelayerAuthId;
+    impl frame_system::offchain::AppCrypto<MultiSigner, MultiSignature> for RelayerAuthId {
+        type RuntimeAppPublic = Public;
+        type GenericSignature = sp_core::sr25519::Signature;
+        type GenericPublic = sp_core::sr25519::Public;
+    }
+}
+
 type BalanceOf<T> =
     <<T as Trait>::Currency as Currency<<T as frame_system::Trait>::AccountId>>::Balance;
+type NegativeImbalanceOf<T> =
+    <<T as Trait>::Currency as Currency<<T as frame_system::Trait>::AccountId>>::NegativeImbalance;
+type ScheduledMintOf<T> = ScheduledMint<
+    <T as Trait>::AssetId,
+    <T as frame_system::Trait>::AccountId,
+    BalanceOf<T>,
+    <T as frame_system::Trait>::BlockNumber,
+    <<T as Trait>::Scheduler as ScheduleAnon<
+        <T as frame_system::Trait>::BlockNumber,
+        <T as Trait>::ScheduledCall,
+        <T as Trait>::PalletsOrigin,
+    >>::Address,
+>;
+type TransferRecordOf<T> = TransferRecord<
+    <T as Trait>::AssetId,
+    <T as frame_system::Trait>::AccountId,
+    BalanceOf<T>,
+    <T as frame_system::Trait>::BlockNumber,
+>;
+type DepositProofOf<T> = <<T as Trait>::DepositVerifier as VerifyDeposit<
+    <T as frame_system::Trait>::AccountId,
+    BalanceOf<T>,
+>>::Proof;
+
+/// Address of an account on the external (Ethereum) chain.
+pub type ExternalAddress = H160;
+
+/// Identifier of a deposit on the external chain, derived by `deposit_id_of`.
+pub type DepositId = H256;
+
+/// Identifier of the deposit made by the event at `log_index` of the receipt of the
+/// transaction `tx_hash`.
+///
+/// Relayers and `VerifyDeposit` implementations derive the identifiers the same way, so a
+/// deposit cannot be minted twice by submitting it both ways.
+pub fn deposit_id_of(tx_hash: H256, log_index: u32) -> DepositId {
+    H256((tx_hash, log_index).using_encoded(keccak_256))
+}
+
+/// Balances of the bridged assets other than the native currency.
+pub trait MultiAsset<AccountId, AssetId, Balance> {
+    /// The free balance of `who` in `asset`.
+    fn free_balance(asset: AssetId, who: &AccountId) -> Balance;
+
+    /// Transfer `amount` of `asset` from `source` to `dest`.
+    fn transfer(
+        asset: AssetId,
+        source: &AccountId,
+        dest: &AccountId,
+        amount: Balance,
+    ) -> DispatchResult;
+}
+
+impl<AccountId, AssetId, Balance: Zero> MultiAsset<AccountId, AssetId, Balance> for () {
+    fn free_balance(_: AssetId, _: &AccountId) -> Balance {
+        Zero::zero()
+    }
+
+    fn transfer(_: AssetId, _: &AccountId, _: &AccountId, _: Balance) -> DispatchResult {
+        Err(DispatchError::Other(
+            "No bridged assets besides the native currency",
+        ))
+    }
+}
+
+/// A deposit proven to have happened on the external chain.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct ProvenDeposit<AccountId, Balance> {
+    /// Identifier of the deposit.
+    pub deposit_id: DepositId,
+    /// Token contract the deposit was made in.
+    pub token: ExternalAddress,
+    /// Account the tokens are minted to.
+    pub account: AccountId,
+    /// Amount of tokens deposited.
+    pub amount: Balance,
+}
+
+/// Verification of deposits on the external chain.
+pub trait VerifyDeposit<AccountId, Balance> {
+    /// Evidence that a deposit happened.
+    type Proof: Parameter;
+
+    /// Check `proof` and return the deposit it proves.
+    fn verify(proof: &Self::Proof) -> Result<ProvenDeposit<AccountId, Balance>, DispatchError>;
+
+    /// A proof of `deposit` accepted by `verify`, for benchmarking.
+    #[cfg(feature = "runtime-benchmarks")]
+    fn successful_proof(deposit: &ProvenDeposit<AccountId, Balance>) -> Self::Proof;
+}
+
+impl<AccountId, Balance> VerifyDeposit<AccountId, Balance> for () {
+    type Proof = ();
+
+    fn verify(_: &()) -> Result<ProvenDeposit<AccountId, Balance>, DispatchError> {
+        Err(DispatchError::Other("Deposit proofs are not supported"))
+    }
+
+    #[cfg(feature = "runtime-benchmarks")]
+    fn successful_proof(_: &ProvenDeposit<AccountId, Balance>) {}
+}
+
+/// Tells which accounts bridged tokens may be minted to.
+pub trait RecipientFilter<AccountId> {
+    /// Whether tokens may be minted to `who`.
+    fn allows(who: &AccountId) -> bool;
+
+    /// Make `allows` accept `who`, for benchmarking.
+    #[cfg(feature = "runtime-benchmarks")]
+    fn allow(who: &AccountId);
+}
+
+impl<AccountId> RecipientFilter<AccountId> for () {
+    fn allows(_: &AccountId) -> bool {
+        true
+    }
+
+    #[cfg(feature = "runtime-benchmarks")]
+    fn allow(_: &AccountId) {}
+}
+
+/// The relayer set, as the accounts approving the headers of a light client.
+pub struct RelayerSet<T>(PhantomData<T>);
+impl<T: Trait> Contains<T::AccountId> for RelayerSet<T> {
+    fn contains(who: &T::AccountId) -> bool {
+        Module::<T>::is_relayer(who)
+    }
+
+    fn sorted_members() -> Vec<T::AccountId> {
+        Module::<T>::relayers()
+    }
+}
+
+/// The number of relayer votes a mint needs.
+pub struct RelayerQuorum<T>(PhantomData<T>);
+impl<T: Trait> Get<u32> for RelayerQuorum<T> {
+    fn get() -> u32 {
+        Module::<T>::relayer_threshold()
+    }
+}
+
+/// A mint proposed by the relayers, waiting for enough votes to be executed.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct MintProposal<AssetId, AccountId, Balance, BlockNumber> {
+    /// Asset being minted.
+    pub asset_id: AssetId,
+    /// Account the tokens are minted to.
+    pub account: AccountId,
+    /// Amount of tokens to mint.
+    pub amount: Balance,
+    /// Relayers which voted for the proposal.
+    pub votes: Vec<AccountId>,
+    /// Block after which the proposal is discarded.
+    pub expiry: BlockNumber,
+}
+
+/// Tells which relayer votes are wrapped in a call, e.g. in a proxy call, so that
+/// `CheckRelayerVote` checks them as well as the direct calls to `vote_mint`.
+pub trait WrappedVotes<AccountId, Call> {
+    /// The voters and the deposits of the votes wrapped in `call`, submitted by `who`.
+    fn wrapped_votes(who: &AccountId, call: &Call) -> Vec<(AccountId, DepositId)>;
+}
+
+impl<AccountId, Call> WrappedVotes<AccountId, Call> for () {
+    fn wrapped_votes(_: &AccountId, _: &Call) -> Vec<(AccountId, DepositId)> {
+        Vec::new()
+    }
+}
+
+/// Governance-set bounds on how fast tokens can leave the bridge pot.
+#[derive(Encode, Decode, Clone, Default, PartialEq, Eq, RuntimeDebug)]
+pub struct MintLimits<Balance, BlockNumber> {
+    /// Length in blocks of the rolling window the caps apply to. Zero disables the caps.
+    pub period: BlockNumber,
+    /// Maximum amount minted to a single account within any window.
+    pub per_account: Option<Balance>,
+    /// Maximum amount minted by the bridge within any window.
+    pub global: Option<Balance>,
+    /// Mints above this amount are always delayed.
+    pub delay_threshold: Option<Balance>,
+    /// Number of blocks a delayed mint waits before it is executed, during which it can be
+    /// cancelled.
+    pub delay: BlockNumber,
+}
+
+/// Amounts minted over the last period of the mint caps, in buckets of a tenth of the period.
+///
+/// A bucket counts towards the window as long as any of its blocks is within the period, so
+/// the caps hold over every span of `period` blocks.
+#[derive(Encode, Decode, Clone, Default, PartialEq, Eq, RuntimeDebug)]
+pub struct MintWindow<Balance, BlockNumber> {
+    /// The first block of each bucket and the amount minted in it, oldest first.
+    pub buckets: Vec<(BlockNumber, Balance)>,
+}
+
+/// A mint which hit a limit and waits for its cancellation window to end.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct ScheduledMint<AssetId, AccountId, Balance, BlockNumber, Task> {
+    pub asset_id: AssetId,
+    pub account: AccountId,
+    pub amount: Balance,
+    pub execute_at: BlockNumber,
+    /// The scheduler task executing the mint.
+    pub task: Task,
+}
+
+/// A deposit reported by the endpoint the offchain worker polls, which returns them as a SCALE
+/// encoded `Vec<PendingDeposit>`.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct PendingDeposit<AssetId, AccountId, Balance> {
+    pub asset_id: AssetId,
+    /// Hash of the deposit transaction.
+    pub tx_hash: H256,
+    /// Index of the deposit event in the transaction receipt.
+    pub log_index: u32,
+    pub account: AccountId,
+    pub amount: Balance,
+}
+
+/// Direction of a bridge transfer.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum TransferDirection {
+    /// A mint of a deposit made on the external chain.
+    Inbound,
+    /// An outbound transfer to the external chain.
+    Outbound,
+}
+
+/// A bridge transfer, kept for `TransferHistoryDepth` blocks.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct TransferRecord<AssetId, AccountId, Balance, BlockNumber> {
+    pub direction: TransferDirection,
+    pub asset_id: AssetId,
+    /// The deposit minted by an inbound transfer, or the hash of the transaction releasing an
+    /// outbound transfer on the external chain, once a relayer confirmed it.
+    pub external_id: Option<DepositId>,
+    /// The account on the external chain an outbound transfer is released to.
+    pub recipient: Option<ExternalAddress>,
+    pub account: AccountId,
+    /// Amount credited or released, fees excluded.
+    pub amount: Balance,
+    pub block: BlockNumber,
+}
+
+/// Fee the bridge takes out of a transfer.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum BridgeFee<Balance> {
+    /// No fee.
+    Free,
+    /// A flat amount per transfer, capped at the transferred amount.
+    Flat(Balance),
+    /// A fraction of the transferred amount.
+    Proportional(Permill),
+}
+
+impl<Balance> Default for BridgeFee<Balance> {
+    fn default() -> Self {
+        BridgeFee::Free
+    }
+}
+
+impl<Balance: AtLeast32BitUnsigned + Copy> BridgeFee<Balance> {
+    /// The fee taken out of a transfer of `amount`.
+    pub fn compute(&self, amount: Balance) -> Balance {
+        match *self {
+            BridgeFee::Free => Zero::zero(),
+            BridgeFee::Flat(fee) => fee.min(amount),
+            BridgeFee::Proportional(ratio) => ratio * amount,
+        }
+    }
+}
+
+/// Storage version of the pallet, upgraded by the runtime migrations.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, RuntimeDebug)]
+pub enum Releases {
+    /// Before the pot ledgers and the count of the bridged assets.
+    V1,
+    /// The ledger of every bridged asset is open and `BridgedAssetCount` is kept.
+    V2,
+    /// The mint caps apply to a rolling window kept in buckets, the fees are set per asset
+    /// and the mint proposals are queued for pruning.
+    V3,
+}
+
+impl Default for Releases {
+    fn default() -> Self {
+        Releases::V1
+    }
+}
 
 pub trait WeightInfo {
     fn mint() -> Weight;
+    fn burn() -> Weight;
+    fn add_relayer(r: u32) -> Weight;
+    fn remove_relayer(r: u32) -> Weight;
+    fn set_threshold() -> Weight;
+    fn vote_mint(r: u32) -> Weight;
+    fn set_mint_limits() -> Weight;
+    fn pause() -> Weight;
+    fn unpause() -> Weight;
+    fn register_asset() -> Weight;
+    fn unregister_asset() -> Weight;
+    fn set_fees() -> Weight;
+    fn mint_with_proof() -> Weight;
+    fn check_pots(a: u32) -> Weight;
+    fn execute_scheduled_mint() -> Weight;
+    fn cancel_mint() -> Weight;
+    fn prune_transfers(t: u32) -> Weight;
+    fn prune_proposals(p: u32) -> Weight;
+    fn release_held_mints(m: u32) -> Weight;
+    fn confirm_release() -> Weight;
+    fn refill() -> Weight;
+    fn withdraw() -> Weight;
 }
 
-pub trait Trait: frame_system::Trait {
+pub trait Trait: CreateSignedTransaction<Call<Self>> {
     /// The overarching event type.
     type Event: From<Event<Self>> + Into<<Self as frame_system::Trait>::Event>;
 
+    /// The relayer keys the offchain worker signs its votes with.
+    type AuthorityId: AppCrypto<Self::Public, Self::Signature>;
+
     /// The bridge's module id, used for deriving its sovereign account ID.
     type ModuleId: Get<ModuleId>;
 
     /// The currency trait.
     type Currency: Currency<Self::AccountId>;
 
+    /// Identifier of a bridged asset.
+    type AssetId: Parameter + Member + Copy + Default + MaybeSerializeDeserialize;
+
+    /// The asset id `Currency` is bridged under.
+    type NativeAssetId: Get<Self::AssetId>;
+
+    /// Bridged assets other than the native currency.
+    type Assets: MultiAsset<Self::AccountId, Self::AssetId, BalanceOf<Self>>;
+
+    /// Verifier of the deposit proofs accepted by `mint_with_proof`.
+    type DepositVerifier: VerifyDeposit<Self::AccountId, BalanceOf<Self>>;
+
+    /// The accounts tokens may be minted to, checked when the tokens are transferred: a
+    /// delayed mint fails if its recipient is no longer allowed once it is due.
+    type Recipients: RecipientFilter<Self::AccountId>;
+
+    /// Handler for the bridge fees in the native currency.
+    type OnFee: OnUnbalanced<NegativeImbalanceOf<Self>>;
+
+    /// The account the bridge fees in the other assets are paid to, usually the treasury.
+    type AssetFeeDestination: Get<Self::AccountId>;
+
     /// The origin which may add or remove registrars. Root can always do this.
     type MintOrigin: EnsureOrigin<Self::Origin>;
 
+    /// The origin which may manage the relayer set, the voting threshold, the mint limits, the
+    /// fees and the registry of bridged assets.
+    type AdminOrigin: EnsureOrigin<Self::Origin>;
+
+    /// The origin which may pause and resume the bridge.
+    type PauseOrigin: EnsureOrigin<Self::Origin>;
+
+    /// Maximum number of relayers.
+    type MaxRelayers: Get<u32>;
+
+    /// Number of blocks a mint proposal stays open for voting.
+    type ProposalLifetime: Get<Self::BlockNumber>;
+
+    /// Priority of the relayer votes admitted by `CheckRelayerVote`.
+    type RelayerVotePriority: Get<TransactionPriority>;
+
+    /// The relayer votes wrapped in other calls, checked by `CheckRelayerVote`.
+    type WrappedVotes: WrappedVotes<Self::AccountId, <Self as frame_system::Trait>::Call>;
+
+    /// The aggregated call type scheduled mints are dispatched as.
+    type ScheduledCall: Parameter + Dispatchable<Origin = Self::Origin> + From<Call<Self>>;
+
+    /// The caller origin scheduled mints are dispatched with.
+    type PalletsOrigin: From<frame_system::RawOrigin<Self::AccountId>>;
+
+    /// The scheduler delayed mints are executed by.
+    type Scheduler: ScheduleAnon<Self::BlockNumber, Self::ScheduledCall, Self::PalletsOrigin>;
+
+    /// The origin which may cancel a delayed mint before it is executed.
+    type CancelOrigin: EnsureOrigin<Self::Origin>;
+
+    /// The origin which may refill the pots and withdraw their excess.
+    type PotOrigin: EnsureOrigin<Self::Origin>;
+
+    /// The account the pots are refilled from, usually the treasury.
+    type RefillSource: Get<Self::AccountId>;
+
+    /// Number of blocks transfer records are kept for.
+    type TransferHistoryDepth: Get<Self::BlockNumber>;
+
+    /// Maximum number of transfers indexed per account. Older transfers are dropped from the
+    /// index first.
+    type MaxAccountTransfers: Get<u32>;
+
     /// Weight information for extrinsics in this pallet.
     type WeightInfo: WeightInfo;
 }
 
 decl_storage! {
     trait Store for Module<T: Trait> as Dummy {
+        /// Deposits of the external chain which were already minted, with the block they were
+        /// minted at.
+        pub ProcessedDeposits get(fn processed_deposits):
+            map hasher(blake2_128_concat) DepositId => Option<T::BlockNumber>;
+
+        /// The current set of relayers, sorted.
+        pub Relayers get(fn relayers): Vec<T::AccountId>;
+
+        /// Number of relayer votes required to execute a mint.
+        pub RelayerThreshold get(fn relayer_threshold): u32 = 1;
+
+        /// Open mint proposals.
+        pub MintProposals get(fn mint_proposals):
+            map hasher(blake2_128_concat) DepositId
+            => Option<MintProposal<T::AssetId, T::AccountId, BalanceOf<T>, T::BlockNumber>>;
+
+        /// Index of the next entry of `ProposalExpiries`.
+        pub NextProposalIndex get(fn next_proposal_index): u64;
+
+        /// The expiry and the deposit of the mint proposals in the order they were opened, by
+        /// index.
+        pub ProposalExpiries get(fn proposal_expiries):
+            map hasher(twox_64_concat) u64 => Option<(T::BlockNumber, DepositId)>;
+
+        /// Index of the oldest entry of `ProposalExpiries` which is not pruned yet.
+        pub OldestProposalIndex get(fn oldest_proposal_index): u64;
+
+        /// Bridged assets, with the address of their token contract on the external chain.
+        pub BridgedAssets get(fn bridged_assets):
+            map hasher(twox_64_concat) T::AssetId => Option<ExternalAddress>;
+
+        /// Bridged assets by the address of their token contract on the external chain.
+        pub AssetByToken get(fn asset_by_token):
+            map hasher(identity) ExternalAddress => Option<T::AssetId>;
+
+        /// Caps and delay applied to mints of each asset.
+        pub Limits get(fn mint_limits):
+            map hasher(twox_64_concat) T::AssetId => MintLimits<BalanceOf<T>, T::BlockNumber>;
+
+        /// Amount of each asset minted by the bridge in the last period of its limits.
+        pub GlobalMinted get(fn global_minted):
+            map hasher(twox_64_concat) T::AssetId => MintWindow<BalanceOf<T>, T::BlockNumber>;
+
+        /// Amount of each asset minted to each account in the last period of its limits.
+        pub AccountMinted get(fn account_minted):
+            double_map hasher(twox_64_concat) T::AssetId, hasher(blake2_128_concat) T::AccountId
+            => MintWindow<BalanceOf<T>, T::BlockNumber>;
+
+        /// Mints waiting for their cancellation window to end, by deposit.
+        pub ScheduledMints get(fn scheduled_mints):
+            map hasher(identity) DepositId => Option<ScheduledMintOf<T>>;
 
+        /// Scheduled mints which came due while the bridge was paused, to be rescheduled once
+        /// it resumes.
+        pub HeldMints get(fn held_mints): Vec<DepositId>;
+
+        /// Whether the bridge is halted. No tokens enter or leave the pot while it is.
+        pub Paused get(fn is_paused): bool;
+
+        /// Fee taken out of mints of each asset.
+        pub MintFee get(fn mint_fee):
+            map hasher(twox_64_concat) T::AssetId => BridgeFee<BalanceOf<T>>;
+
+        /// Fee taken out of outbound transfers of each asset.
+        pub BurnFee get(fn burn_fee):
+            map hasher(twox_64_concat) T::AssetId => BridgeFee<BalanceOf<T>>;
+
+        /// Number of entries of `BridgedAssets`.
+        pub BridgedAssetCount get(fn bridged_asset_count): u32;
+
+        /// Amount of each asset which entered its pot: the balance of the pot when the asset
+        /// was first bridged, the outbound transfers and the accounted refills.
+        pub TotalLocked get(fn total_locked):
+            map hasher(twox_64_concat) T::AssetId => BalanceOf<T>;
+
+        /// Amount of each asset which left its pot: the mints, fees included.
+        pub TotalMinted get(fn total_minted):
+            map hasher(twox_64_concat) T::AssetId => BalanceOf<T>;
+
+        /// The last discrepancy between the accounting and the balance of each pot that was
+        /// reported, as `(expected, actual)`.
+        pub ReportedDiscrepancy get(fn reported_discrepancy):
+            map hasher(twox_64_concat) T::AssetId => Option<(BalanceOf<T>, BalanceOf<T>)>;
+
+        /// Index of the next transfer record.
+        pub NextTransferIndex get(fn next_transfer_index): u64;
+
+        /// Records of the transfers of the last `TransferHistoryDepth` blocks, by index.
+        pub Transfers get(fn transfers):
+            map hasher(twox_64_concat) u64 => Option<TransferRecordOf<T>>;
+
+        /// Index of the oldest transfer record which is not pruned yet.
+        pub OldestTransferIndex get(fn oldest_transfer_index): u64;
+
+        /// Indices of the latest transfer records of each account, oldest first.
+        pub AccountTransfers get(fn account_transfers):
+            map hasher(blake2_128_concat) T::AccountId => Vec<u64>;
+
+        /// Storage version of the pallet. Chains started before it was tracked are at `V1`.
+        pub StorageVersion get(fn storage_version)
+            build(|_: &GenesisConfig<T>| Releases::V3): Releases;
     }
     add_extra_genesis {
         config(balance): BalanceOf<T>;
+        config(relayers): Vec<T::AccountId>;
+        config(relayer_threshold): u32;
+        config(assets): Vec<(T::AssetId, ExternalAddress)>;
         build(|config| {
             // Create Bridge pot
             assert!(
@@ -68,6 +625,34 @@ decl_storage! {
                 "the balance of any account should always be more than existential deposit.",
             );
             T::Currency::deposit_creating(&<Module<T>>::account_id(), config.balance);
+
+            let mut relayers = config.relayers.clone();
+            relayers.sort();
+            relayers.dedup();
+            assert!(
+                relayers.len() <= T::MaxRelayers::get() as usize,
+                "too many relayers in genesis config",
+            );
+            if config.relayer_threshold > 0 {
+                assert!(
+                    config.relayer_threshold as usize <= relayers.len(),
+                    "relayer threshold above the number of relayers in genesis config",
+                );
+                RelayerThreshold::put(config.relayer_threshold);
+            }
+            <Relayers<T>>::put(relayers);
+
+            for (asset_id, token) in &config.assets {
+                assert!(
+                    !<BridgedAssets<T>>::contains_key(asset_id)
+                        && !<AssetByToken<T>>::contains_key(token),
+                    "duplicate asset in genesis config",
+                );
+                <BridgedAssets<T>>::insert(asset_id, token);
+                <AssetByToken<T>>::insert(token, asset_id);
+                <Module<T>>::open_ledger(*asset_id);
+            }
+            BridgedAssetCount::put(config.assets.len() as u32);
         });
     }
 }
@@ -76,17 +661,119 @@ decl_event!(
     pub enum Event<T>
     where
         AccountId = <T as frame_system::Trait>::AccountId,
+        AssetId = <T as Trait>::AssetId,
         Balance = BalanceOf<T>,
+        BlockNumber = <T as frame_system::Trait>::BlockNumber,
     {
-        /// A mint was called. \[who, amount\]
-        Minted(AccountId, Balance),
+        /// A mint was called. \[asset_id, deposit_id, who, amount credited\]
+        Minted(AssetId, DepositId, AccountId, Balance),
+        /// Tokens were locked in the bridge pot to be released on the external chain.
+        /// \[asset_id, who, recipient, amount released, transfer index\]
+        Burned(AssetId, AccountId, ExternalAddress, Balance, u64),
+        /// A bridge fee was charged on a transfer to or from an account.
+        /// \[asset_id, who, fee\]
+        FeeCharged(AssetId, AccountId, Balance),
+        /// The bridge fees of an asset changed. \[asset_id, mint_fee, burn_fee\]
+        FeesChanged(AssetId, BridgeFee<Balance>, BridgeFee<Balance>),
+        /// A relayer was added. \[relayer\]
+        RelayerAdded(AccountId),
+        /// A relayer was removed. \[relayer\]
+        RelayerRemoved(AccountId),
+        /// The number of votes required to execute a mint changed. \[threshold\]
+        RelayerThresholdChanged(u32),
+        /// A relayer voted for a mint. \[deposit_id, relayer, votes\]
+        MintVoted(DepositId, AccountId, u32),
+        /// A mint proposal expired before reaching the threshold and was discarded.
+        /// \[deposit_id\]
+        MintProposalExpired(DepositId),
+        /// The mint limits of an asset changed. \[asset_id\]
+        MintLimitsChanged(AssetId),
+        /// A mint would exceed the per-account cap of its recipient. \[deposit_id, who\]
+        AccountMintLimitReached(DepositId, AccountId),
+        /// A mint would exceed the global cap. \[deposit_id\]
+        GlobalMintLimitReached(DepositId),
+        /// A mint is above the delay threshold. \[deposit_id\]
+        MintThresholdExceeded(DepositId),
+        /// A mint was scheduled; it can be cancelled until it is executed.
+        /// \[asset_id, deposit_id, who, amount, execute_at\]
+        MintScheduled(AssetId, DepositId, AccountId, Balance, BlockNumber),
+        /// A scheduled mint was cancelled. \[deposit_id\]
+        MintCancelled(DepositId),
+        /// A scheduled mint was executed. \[deposit_id\]
+        ScheduledMintExecuted(DepositId),
+        /// A scheduled mint could not be executed; the deposit can be minted again.
+        /// \[asset_id, deposit_id, who, amount\]
+        ScheduledMintFailed(AssetId, DepositId, AccountId, Balance),
+        /// The bridge was halted.
+        BridgePaused,
+        /// The bridge was resumed.
+        BridgeUnpaused,
+        /// An asset was registered for bridging. \[asset_id, token\]
+        AssetRegistered(AssetId, ExternalAddress),
+        /// An asset was removed from the bridge. \[asset_id\]
+        AssetUnregistered(AssetId),
+        /// The balance of a pot does not match the amounts locked in and minted out of it.
+        /// \[asset_id, expected, actual\]
+        PotAccountingMismatch(AssetId, Balance, Balance),
+        /// The balance of a pot matches its accounting again. \[asset_id\]
+        PotAccountingRestored(AssetId),
+        /// A refill of a pot was accounted as locked. \[asset_id, amount\]
+        PotRefilled(AssetId, Balance),
+        /// Tokens were withdrawn from a pot. \[asset_id, dest, amount\]
+        PotWithdrawn(AssetId, AccountId, Balance),
+        /// A relayer confirmed the release of an outbound transfer on the external chain.
+        /// \[transfer index, tx_hash\]
+        ReleaseConfirmed(u64, H256),
     }
 );
 
 decl_error! {
     /// Error for the identity module.
     pub enum Error for Module<T: Trait> {
-
+        /// Bridged amount must be greater than zero.
+        ZeroAmount,
+        /// The deposit was already minted.
+        DepositAlreadyProcessed,
+        /// The account is already a relayer.
+        AlreadyRelayer,
+        /// The account is not a relayer.
+        NotRelayer,
+        /// The relayer set is full.
+        TooManyRelayers,
+        /// The threshold must be greater than zero and at most the number of relayers.
+        InvalidThreshold,
+        /// Removing the relayer would leave fewer relayers than the threshold.
+        TooFewRelayers,
+        /// The relayer already voted for this mint.
+        AlreadyVoted,
+        /// The vote does not match the recipient or the amount of the open proposal.
+        ProposalMismatch,
+        /// The mint could not be scheduled.
+        FailedToSchedule,
+        /// No mint of the deposit is scheduled.
+        NoScheduledMint,
+        /// The scheduled mint is not due yet.
+        MintNotDue,
+        /// The bridge is paused.
+        BridgeIsPaused,
+        /// The asset is not bridged.
+        UnknownAsset,
+        /// The asset is already registered.
+        AssetAlreadyRegistered,
+        /// The token contract is already registered for another asset.
+        TokenAlreadyRegistered,
+        /// The transferred amount does not cover the bridge fee.
+        AmountBelowFee,
+        /// The mint is above a cap of the mint limits: it can never be executed.
+        MintAboveCap,
+        /// No transfer record with this index is kept.
+        UnknownTransfer,
+        /// The transfer is not an outbound transfer waiting for its release to be confirmed.
+        NotAwaitingRelease,
+        /// Tokens may not be minted to the recipient.
+        RecipientNotAllowed,
+        /// The amount is above the balance of the pot in excess of its accounting.
+        AmountAboveExcess,
     }
 }
 
@@ -97,23 +784,603 @@ decl_module! {
         /// The bridge's module id, used for deriving its sovereign account ID.
         const ModuleId: ModuleId = T::ModuleId::get();
 
+        /// Maximum number of relayers.
+        const MaxRelayers: u32 = T::MaxRelayers::get();
+
+        /// Number of blocks a mint proposal stays open for voting.
+        const ProposalLifetime: T::BlockNumber = T::ProposalLifetime::get();
+
+        /// Number of blocks transfer records are kept for.
+        const TransferHistoryDepth: T::BlockNumber = T::TransferHistoryDepth::get();
+
+        /// Maximum number of transfers indexed per account.
+        const MaxAccountTransfers: u32 = T::MaxAccountTransfers::get();
+
         fn deposit_event() = default;
 
-        /// Add up to `amount` of token  to `account` balance increasing total issuance.
+        fn on_initialize(now: T::BlockNumber) -> Weight {
+            let pruned = Self::prune_transfers(now);
+            let expired = Self::prune_proposals(now);
+            let released = Self::release_held_mints(now);
+            T::WeightInfo::check_pots(Self::bridged_asset_count())
+                .saturating_add(T::WeightInfo::prune_transfers(pruned))
+                .saturating_add(T::WeightInfo::prune_proposals(expired))
+                .saturating_add(T::WeightInfo::release_held_mints(released))
+        }
+
+        /// Check the balance of every bridged pot against its accounting, and report the
+        /// discrepancies which appeared or changed in this block.
+        fn on_finalize() {
+            for (asset_id, _) in BridgedAssets::<T>::iter() {
+                Self::check_pot(asset_id);
+            }
+        }
+
+        /// Vote for the deposits reported by the endpoint stored under `ENDPOINT_KEY`, if the
+        /// node holds the key of a relayer.
+        fn offchain_worker(now: T::BlockNumber) {
+            if let Err(e) = Self::relay_deposits(now) {
+                debug::warn!("Failed to relay deposits: {}", e);
+            }
+        }
+
+        /// Add up to `amount` of `asset_id` to `account` balance from the asset's bridge pot.
+        ///
+        /// `deposit_id` identifies the deposit on the external chain, see `deposit_id_of`.
+        /// Each deposit can be minted only once.
         #[weight = T::WeightInfo::mint() ]
-        fn mint(origin, account: T::AccountId, #[compact] amount: BalanceOf<T>) -> DispatchResult {
+        #[transactional]
+        fn mint(
+            origin,
+            asset_id: T::AssetId,
+            deposit_id: DepositId,
+            account: T::AccountId,
+            #[compact] amount: BalanceOf<T>
+        ) -> DispatchResult {
             T::MintOrigin::ensure_origin(origin)?;
+            Self::ensure_not_paused()?;
+            Self::ensure_bridged(asset_id)?;
+            ensure!(
+                !Self::is_processed(&deposit_id),
+                Error::<T>::DepositAlreadyProcessed
+            );
+
+            Self::mint_or_delay(asset_id, deposit_id, account, amount)
+        }
+
+        /// Mint a deposit proven by `proof`. Anyone may submit the proof.
+        ///
+        /// The asset is the one bridged from the token contract the deposit was made in.
+        #[weight = T::WeightInfo::mint_with_proof()]
+        #[transactional]
+        fn mint_with_proof(origin, proof: DepositProofOf<T>) -> DispatchResult {
+            ensure_signed(origin)?;
+            Self::ensure_not_paused()?;
+
+            let ProvenDeposit { deposit_id, token, account, amount } =
+                T::DepositVerifier::verify(&proof)?;
+            let asset_id = Self::asset_by_token(token).ok_or(Error::<T>::UnknownAsset)?;
+            ensure!(!amount.is_zero(), Error::<T>::ZeroAmount);
+            ensure!(
+                !Self::is_processed(&deposit_id),
+                Error::<T>::DepositAlreadyProcessed
+            );
+
+            Self::mint_or_delay(asset_id, deposit_id, account, amount)
+        }
+
+        /// Move `amount` of `asset_id` from the caller into the asset's bridge pot, to be
+        /// released to `recipient` on the external chain.
+        ///
+        /// The bridge fee of the asset is taken out of `amount`.
+        ///
+        /// The emitted `Burned` event is what relayers pick up.
+        #[weight = T::WeightInfo::burn()]
+        #[transactional]
+        fn burn(
+            origin,
+            asset_id: T::AssetId,
+            recipient: ExternalAddress,
+            #[compact] amount: BalanceOf<T>
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::ensure_not_paused()?;
+            Self::ensure_bridged(asset_id)?;
+            ensure!(!amount.is_zero(), Error::<T>::ZeroAmount);
+
+            let fee = Self::fee(Self::burn_fee(asset_id), amount)?;
+            let released = amount - fee;
+            Self::transfer(asset_id, &who, &Self::pot_account(asset_id), released)?;
+            Self::charge_fee(asset_id, &who, &who, fee)?;
+            TotalLocked::<T>::mutate(asset_id, |locked| *locked = locked.saturating_add(released));
+            let index = Self::record_transfer(
+                TransferDirection::Outbound,
+                asset_id,
+                None,
+                Some(recipient),
+                &who,
+                released,
+            );
+
+            Self::deposit_event(RawEvent::Burned(asset_id, who, recipient, released, index));
+            Ok(())
+        }
+
+        /// Record `tx_hash` as the transaction releasing the outbound transfer `index` on the
+        /// external chain. Each release is confirmed once, by any relayer.
+        #[weight = T::WeightInfo::confirm_release()]
+        fn confirm_release(origin, index: u64, tx_hash: H256) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            ensure!(Self::is_relayer(&who), Error::<T>::NotRelayer);
+
+            Transfers::<T>::try_mutate(index, |record| -> DispatchResult {
+                let record = record.as_mut().ok_or(Error::<T>::UnknownTransfer)?;
+                ensure!(
+                    record.direction == TransferDirection::Outbound
+                        && record.external_id.is_none(),
+                    Error::<T>::NotAwaitingRelease
+                );
+                record.external_id = Some(tx_hash);
+                Ok(())
+            })?;
+
+            Self::deposit_event(RawEvent::ReleaseConfirmed(index, tx_hash));
+            Ok(())
+        }
+
+        /// Add `who` to the relayer set.
+        #[weight = T::WeightInfo::add_relayer(T::MaxRelayers::get())]
+        fn add_relayer(origin, who: T::AccountId) -> DispatchResult {
+            T::AdminOrigin::ensure_origin(origin)?;
+
+            let mut relayers = Self::relayers();
+            let location = relayers.binary_search(&who).err().ok_or(Error::<T>::AlreadyRelayer)?;
+            ensure!(
+                relayers.len() < T::MaxRelayers::get() as usize,
+                Error::<T>::TooManyRelayers
+            );
+            relayers.insert(location, who.clone());
+            <Relayers<T>>::put(relayers);
+
+            Self::deposit_event(RawEvent::RelayerAdded(who));
+            Ok(())
+        }
+
+        /// Remove `who` from the relayer set.
+        ///
+        /// Votes already cast by `who` on open proposals no longer count.
+        #[weight = T::WeightInfo::remove_relayer(T::MaxRelayers::get())]
+        fn remove_relayer(origin, who: T::AccountId) -> DispatchResult {
+            T::AdminOrigin::ensure_origin(origin)?;
+
+            let mut relayers = Self::relayers();
+            let location = relayers.binary_search(&who).ok().ok_or(Error::<T>::NotRelayer)?;
+            relayers.remove(location);
+            ensure!(
+                relayers.len() >= Self::relayer_threshold() as usize,
+                Error::<T>::TooFewRelayers
+            );
+            <Relayers<T>>::put(relayers);
+
+            Self::deposit_event(RawEvent::RelayerRemoved(who));
+            Ok(())
+        }
+
+        /// Set the number of relayer votes required to execute a mint.
+        #[weight = T::WeightInfo::set_threshold()]
+        fn set_threshold(origin, threshold: u32) -> DispatchResult {
+            T::AdminOrigin::ensure_origin(origin)?;
+            ensure!(
+                threshold > 0 && threshold as usize <= Self::relayers().len(),
+                Error::<T>::InvalidThreshold
+            );
+
+            RelayerThreshold::put(threshold);
+
+            Self::deposit_event(RawEvent::RelayerThresholdChanged(threshold));
+            Ok(())
+        }
+
+        /// Vote for minting `amount` of `asset_id` to `account` for the external deposit
+        /// `deposit_id`, derived by `deposit_id_of`.
+        ///
+        /// The first vote opens a proposal, which is executed as soon as it collects
+        /// `RelayerThreshold` votes. A proposal which has not reached the threshold within
+        /// `ProposalLifetime` blocks is discarded and the next vote opens a new one.
+        ///
+        /// Votes are free of fees, the fee is not even withdrawn before dispatch. Runtimes must
+        /// include `CheckRelayerVote` in their signed extensions, which keeps votes of
+        /// non-relayers and stale votes out of blocks.
+        #[weight = (
+            T::WeightInfo::vote_mint(T::MaxRelayers::get()),
+            DispatchClass::Normal,
+            Pays::No
+        )]
+        #[transactional]
+        fn vote_mint(
+            origin,
+            asset_id: T::AssetId,
+            deposit_id: DepositId,
+            account: T::AccountId,
+            #[compact] amount: BalanceOf<T>
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::ensure_not_paused()?;
+            let relayers = Self::relayers();
+            ensure!(relayers.binary_search(&who).is_ok(), Error::<T>::NotRelayer);
+            Self::ensure_bridged(asset_id)?;
+            ensure!(
+                !Self::is_processed(&deposit_id),
+                Error::<T>::DepositAlreadyProcessed
+            );
+
+            let now = <frame_system::Module<T>>::block_number();
+            let mut proposal = match Self::mint_proposals(&deposit_id) {
+                Some(proposal) if proposal.expiry >= now => {
+                    ensure!(
+                        proposal.asset_id == asset_id
+                            && proposal.account == account
+                            && proposal.amount == amount,
+                        Error::<T>::ProposalMismatch
+                    );
+                    proposal
+                }
+                expired => {
+                    if expired.is_some() {
+                        Self::deposit_event(RawEvent::MintProposalExpired(deposit_id));
+                    }
+                    let expiry = now.saturating_add(T::ProposalLifetime::get());
+                    let index = NextProposalIndex::mutate(|next| {
+                        let index = *next;
+                        *next = next.wrapping_add(1);
+                        index
+                    });
+                    ProposalExpiries::<T>::insert(index, (expiry, deposit_id));
+                    MintProposal {
+                        asset_id,
+                        account,
+                        amount,
+                        votes: Vec::new(),
+                        expiry,
+                    }
+                }
+            };
+
+            ensure!(!proposal.votes.contains(&who), Error::<T>::AlreadyVoted);
+            // Only the votes of the current relayers count.
+            proposal.votes.retain(|voter| relayers.binary_search(voter).is_ok());
+            proposal.votes.push(who.clone());
+            let votes = proposal.votes.len() as u32;
+            Self::deposit_event(RawEvent::MintVoted(deposit_id, who, votes));
+
+            if votes >= Self::relayer_threshold() {
+                <MintProposals<T>>::remove(&deposit_id);
+                Self::mint_or_delay(asset_id, deposit_id, proposal.account, proposal.amount)?;
+            } else {
+                <MintProposals<T>>::insert(&deposit_id, proposal);
+            }
+            Ok(())
+        }
+
+        /// Set the caps and the delay applied to mints of `asset_id`.
+        #[weight = T::WeightInfo::set_mint_limits()]
+        fn set_mint_limits(
+            origin,
+            asset_id: T::AssetId,
+            limits: MintLimits<BalanceOf<T>, T::BlockNumber>
+        ) -> DispatchResult {
+            T::AdminOrigin::ensure_origin(origin)?;
 
-            let bridge = Self::account_id();
-            let _ = T::Currency::transfer(&bridge, &account, amount, KeepAlive)?;
+            <Limits<T>>::insert(asset_id, limits);
 
-            Self::deposit_event(RawEvent::Minted(account, amount));
+            Self::deposit_event(RawEvent::MintLimitsChanged(asset_id));
+            Ok(())
+        }
+
+        /// Halt all mints and outbound transfers. Scheduled mints are postponed.
+        #[weight = T::WeightInfo::pause()]
+        fn pause(origin) -> DispatchResult {
+            T::PauseOrigin::ensure_origin(origin)?;
+
+            Paused::put(true);
+
+            Self::deposit_event(RawEvent::BridgePaused);
+            Ok(())
+        }
+
+        /// Resume the bridge.
+        #[weight = T::WeightInfo::unpause()]
+        fn unpause(origin) -> DispatchResult {
+            T::PauseOrigin::ensure_origin(origin)?;
+
+            Paused::put(false);
+
+            Self::deposit_event(RawEvent::BridgeUnpaused);
+            Ok(())
+        }
+
+        /// Bridge `asset_id` to the `token` contract of the external chain.
+        #[weight = T::WeightInfo::register_asset()]
+        fn register_asset(origin, asset_id: T::AssetId, token: ExternalAddress) -> DispatchResult {
+            T::AdminOrigin::ensure_origin(origin)?;
+            ensure!(
+                !<BridgedAssets<T>>::contains_key(asset_id),
+                Error::<T>::AssetAlreadyRegistered
+            );
+            ensure!(
+                !<AssetByToken<T>>::contains_key(token),
+                Error::<T>::TokenAlreadyRegistered
+            );
+
+            <BridgedAssets<T>>::insert(asset_id, token);
+            <AssetByToken<T>>::insert(token, asset_id);
+            BridgedAssetCount::mutate(|count| *count += 1);
+            Self::open_ledger(asset_id);
+
+            Self::deposit_event(RawEvent::AssetRegistered(asset_id, token));
+            Ok(())
+        }
+
+        /// Set the fees taken out of mints and outbound transfers of `asset_id`.
+        #[weight = T::WeightInfo::set_fees()]
+        fn set_fees(
+            origin,
+            asset_id: T::AssetId,
+            mint_fee: BridgeFee<BalanceOf<T>>,
+            burn_fee: BridgeFee<BalanceOf<T>>
+        ) -> DispatchResult {
+            T::AdminOrigin::ensure_origin(origin)?;
+
+            <MintFee<T>>::insert(asset_id, mint_fee);
+            <BurnFee<T>>::insert(asset_id, burn_fee);
+
+            Self::deposit_event(RawEvent::FeesChanged(asset_id, mint_fee, burn_fee));
+            Ok(())
+        }
+
+        /// Stop bridging `asset_id`. Mints already scheduled are still executed.
+        #[weight = T::WeightInfo::unregister_asset()]
+        fn unregister_asset(origin, asset_id: T::AssetId) -> DispatchResult {
+            T::AdminOrigin::ensure_origin(origin)?;
+
+            let token = <BridgedAssets<T>>::take(asset_id).ok_or(Error::<T>::UnknownAsset)?;
+            <AssetByToken<T>>::remove(token);
+            BridgedAssetCount::mutate(|count| *count = count.saturating_sub(1));
+
+            Self::deposit_event(RawEvent::AssetUnregistered(asset_id));
+            Ok(())
+        }
+
+        /// Execute the mint of `deposit_id` once its cancellation window is over. While the
+        /// bridge is paused, the mint is held until it resumes.
+        ///
+        /// The caps are checked again: a mint which does not fit under them yet is postponed
+        /// until it does, and a mint above one of them fails.
+        ///
+        /// Dispatched by the scheduler with the root origin.
+        #[weight = T::WeightInfo::execute_scheduled_mint()]
+        fn execute_scheduled_mint(origin, deposit_id: DepositId) -> DispatchResult {
+            ensure_root(origin)?;
+            let scheduled = Self::scheduled_mints(deposit_id).ok_or(Error::<T>::NoScheduledMint)?;
+            let now = <frame_system::Module<T>>::block_number();
+            ensure!(scheduled.execute_at <= now, Error::<T>::MintNotDue);
+
+            if Self::is_paused() {
+                HeldMints::append(deposit_id);
+                return Ok(());
+            }
+
+            let limits = Self::mint_limits(scheduled.asset_id);
+            let clearance = Self::cap_clearance(
+                scheduled.asset_id,
+                &scheduled.account,
+                scheduled.amount,
+                &limits,
+                now,
+            );
+            if let Ok((account_at, global_at)) = clearance {
+                let execute_at = account_at.max(global_at);
+                if execute_at > now {
+                    Self::deposit_event(RawEvent::MintScheduled(
+                        scheduled.asset_id,
+                        deposit_id,
+                        scheduled.account.clone(),
+                        scheduled.amount,
+                        execute_at,
+                    ));
+                    return Self::reschedule_mint(deposit_id, scheduled, execute_at);
+                }
+            }
+
+            ScheduledMints::<T>::remove(deposit_id);
+            let ScheduledMint { asset_id, account, amount, .. } = scheduled;
+            let result = clearance.and_then(|_| {
+                with_transaction(|| {
+                    match Self::do_mint(asset_id, deposit_id, account.clone(), amount) {
+                        Ok(()) => TransactionOutcome::Commit(Ok(())),
+                        Err(e) => TransactionOutcome::Rollback(Err(e)),
+                    }
+                })
+            });
+            if result.is_ok() {
+                Self::deposit_event(RawEvent::ScheduledMintExecuted(deposit_id));
+            } else {
+                ProcessedDeposits::<T>::remove(deposit_id);
+                Self::deposit_event(RawEvent::ScheduledMintFailed(
+                    asset_id, deposit_id, account, amount,
+                ));
+            }
+            Ok(())
+        }
+
+        /// Cancel the scheduled mint of `deposit_id`.
+        ///
+        /// The deposit stays processed: it cannot be minted again.
+        #[weight = T::WeightInfo::cancel_mint()]
+        fn cancel_mint(origin, deposit_id: DepositId) -> DispatchResult {
+            T::CancelOrigin::ensure_origin(origin)?;
+            let scheduled = Self::scheduled_mints(deposit_id).ok_or(Error::<T>::NoScheduledMint)?;
+
+            let mut held = Self::held_mints();
+            if let Some(index) = held.iter().position(|id| *id == deposit_id) {
+                held.remove(index);
+                HeldMints::put(held);
+            } else {
+                T::Scheduler::cancel(scheduled.task).map_err(|_| Error::<T>::NoScheduledMint)?;
+            }
+            ScheduledMints::<T>::remove(deposit_id);
+
+            Self::deposit_event(RawEvent::MintCancelled(deposit_id));
+            Ok(())
+        }
+
+        /// Pay `amount` of `asset_id` from `RefillSource` into the pot of `asset_id`, and
+        /// account it as locked so that it backs mints.
+        #[weight = T::WeightInfo::refill()]
+        fn refill(origin, asset_id: T::AssetId, #[compact] amount: BalanceOf<T>) -> DispatchResult {
+            T::PotOrigin::ensure_origin(origin)?;
+            Self::ensure_bridged(asset_id)?;
+            ensure!(!amount.is_zero(), Error::<T>::ZeroAmount);
+
+            let pot = Self::pot_account(asset_id);
+            Self::transfer(asset_id, &T::RefillSource::get(), &pot, amount)?;
+            TotalLocked::<T>::mutate(asset_id, |locked| *locked = locked.saturating_add(amount));
+
+            Self::deposit_event(RawEvent::PotRefilled(asset_id, amount));
+            Ok(())
+        }
+
+        /// Move `amount` of the excess balance of the pot of `asset_id` to `dest`.
+        ///
+        /// The balance backing the tokens locked on the external chain, `TotalLocked` minus
+        /// `TotalMinted`, cannot be withdrawn.
+        #[weight = T::WeightInfo::withdraw()]
+        #[transactional]
+        fn withdraw(
+            origin,
+            asset_id: T::AssetId,
+            dest: T::AccountId,
+            #[compact] amount: BalanceOf<T>
+        ) -> DispatchResult {
+            T::PotOrigin::ensure_origin(origin)?;
+            Self::ensure_bridged(asset_id)?;
+            ensure!(!amount.is_zero(), Error::<T>::ZeroAmount);
+            ensure!(amount <= Self::pot_excess(asset_id), Error::<T>::AmountAboveExcess);
+
+            Self::transfer(asset_id, &Self::pot_account(asset_id), &dest, amount)?;
+
+            Self::deposit_event(RawEvent::PotWithdrawn(asset_id, dest, amount));
             Ok(())
         }
     }
 }
 
 impl<T: Trait> Module<T> {
+    /// Fetch the pending deposits and submit the votes of the local relayer for the ones it
+    /// has not voted for yet, nor submitted a vote for in the last `VOTE_RESUBMIT_DELAY`
+    /// blocks. A failed submission does not hold back the votes for the other deposits.
+    fn relay_deposits(now: T::BlockNumber) -> Result<(), &'static str> {
+        if Self::is_paused() {
+            return Ok(());
+        }
+        let (public, relayer) = match Self::local_relayer() {
+            Some(relayer) => relayer,
+            None => return Ok(()),
+        };
+        let endpoint = match StorageValueRef::persistent(ENDPOINT_KEY)
+            .get::<Vec<u8>>()
+            .flatten()
+        {
+            Some(endpoint) => endpoint,
+            None => return Ok(()),
+        };
+
+        let signer = Signer::<T, T::AuthorityId>::any_account().with_filter(vec![public]);
+        let mut failed = false;
+        for deposit in Self::fetch_deposits(&endpoint)? {
+            let PendingDeposit {
+                asset_id,
+                tx_hash,
+                log_index,
+                account,
+                amount,
+            } = deposit;
+            let deposit_id = deposit_id_of(tx_hash, log_index);
+            let key = [SUBMITTED_VOTE_PREFIX, deposit_id.as_bytes()].concat();
+            let submitted = StorageValueRef::persistent(&key);
+            if Self::is_processed(&deposit_id) {
+                submitted.clear();
+                continue;
+            }
+            // The vote lands in a later block, where a proposal expiring now is restarted.
+            let voted = Self::mint_proposals(deposit_id).map_or(false, |proposal| {
+                proposal.expiry > now && proposal.votes.contains(&relayer)
+            });
+            if voted || Self::bridged_assets(asset_id).is_none() {
+                continue;
+            }
+
+            // Claim the vote, so that neither this worker nor an overlapping one submits it
+            // again before the submitted vote had the time to land.
+            let claimed = submitted.mutate(|until: Option<Option<T::BlockNumber>>| match until {
+                Some(Some(until)) if until > now => Err(()),
+                _ => Ok(now + VOTE_RESUBMIT_DELAY.into()),
+            });
+            if claimed.map_or(true, |claimed| claimed.is_err()) {
+                continue;
+            }
+
+            match signer.send_signed_transaction(|_| {
+                Call::vote_mint(asset_id, deposit_id, account.clone(), amount)
+            }) {
+                Some((_, Ok(()))) => {}
+                _ => {
+                    submitted.clear();
+                    failed = true;
+                }
+            }
+        }
+        if failed {
+            return Err("Failed to submit some votes");
+        }
+        Ok(())
+    }
+
+    /// The first key of the local keystore which belongs to a relayer.
+    fn local_relayer() -> Option<(T::Public, T::AccountId)> {
+        <T::AuthorityId as AppCrypto<T::Public, T::Signature>>::RuntimeAppPublic::all()
+            .into_iter()
+            .map(|key| {
+                let generic =
+                    <T::AuthorityId as AppCrypto<T::Public, T::Signature>>::GenericPublic::from(
+                        key,
+                    );
+                let public: T::Public = generic.into();
+                let account = public.clone().into_account();
+                (public, account)
+            })
+            .find(|(_, account)| Self::is_relayer(account))
+    }
+
+    fn fetch_deposits(
+        endpoint: &[u8],
+    ) -> Result<Vec<PendingDeposit<T::AssetId, T::AccountId, BalanceOf<T>>>, &'static str> {
+        let url = sp_std::str::from_utf8(endpoint).map_err(|_| "Endpoint is not UTF-8")?;
+        let deadline = sp_io::offchain::timestamp().add(Duration::from_millis(FETCH_TIMEOUT));
+        let response = http::Request::get(url)
+            .deadline(deadline)
+            .send()
+            .map_err(|_| "Failed to send request")?
+            .try_wait(deadline)
+            .map_err(|_| "Request timed out")?
+            .map_err(|_| "Request failed")?;
+        if response.code != 200 {
+            return Err("Unexpected status code");
+        }
+        let body = response.body().collect::<Vec<u8>>();
+        Decode::decode(&mut &body[..]).map_err(|_| "Malformed response")
+    }
+
     /// The account ID of the external chain.
     ///
     /// This actually does computation. If you need to keep using it, then make sure you cache the
@@ -121,4 +1388,644 @@ impl<T: Trait> Module<T> {
     pub fn account_id() -> T::AccountId {
         T::ModuleId::get().into_account()
     }
+
+    /// The account holding the bridge pot of `asset_id`.
+    ///
+    /// The native currency uses `account_id`, other assets a sub-account of it.
+    pub fn pot_account(asset_id: T::AssetId) -> T::AccountId {
+        if asset_id == T::NativeAssetId::get() {
+            Self::account_id()
+        } else {
+            T::ModuleId::get().into_sub_account(asset_id)
+        }
+    }
+
+    /// The free balance of `who` in `asset_id`.
+    pub fn free_balance(asset_id: T::AssetId, who: &T::AccountId) -> BalanceOf<T> {
+        if asset_id == T::NativeAssetId::get() {
+            T::Currency::free_balance(who)
+        } else {
+            T::Assets::free_balance(asset_id, who)
+        }
+    }
+
+    /// Whether the deposit `id` of the external chain was already minted.
+    pub fn is_processed(id: &DepositId) -> bool {
+        ProcessedDeposits::<T>::contains_key(id)
+    }
+
+    /// Mint proposals still open for voting.
+    pub fn pending_proposals() -> Vec<(
+        DepositId,
+        MintProposal<T::AssetId, T::AccountId, BalanceOf<T>, T::BlockNumber>,
+    )> {
+        let now = <frame_system::Module<T>>::block_number();
+        MintProposals::<T>::iter()
+            .filter(|(_, proposal)| proposal.expiry >= now)
+            .collect()
+    }
+
+    /// The latest transfers of `who` still kept, oldest first.
+    pub fn account_history(who: &T::AccountId) -> Vec<(u64, TransferRecordOf<T>)> {
+        Self::account_transfers(who)
+            .into_iter()
+            .filter_map(|index| Self::transfers(index).map(|record| (index, record)))
+            .collect()
+    }
+
+    /// Amount of `asset_id` minted by the bridge in the last period of its limits.
+    pub fn minted_in_current_window(asset_id: T::AssetId) -> BalanceOf<T> {
+        Self::minted_in_window(
+            &Self::global_minted(asset_id),
+            <frame_system::Module<T>>::block_number(),
+            Self::mint_limits(asset_id).period,
+        )
+    }
+
+    /// The balance the pot of `asset_id` should hold according to the accounting, and the
+    /// balance it holds, if they differ.
+    pub fn pot_discrepancy(asset_id: T::AssetId) -> Option<(BalanceOf<T>, BalanceOf<T>)> {
+        let actual = Self::free_balance(asset_id, &Self::pot_account(asset_id));
+        // More minted than locked is a discrepancy even when the pot is empty.
+        match Self::total_locked(asset_id).checked_sub(&Self::total_minted(asset_id)) {
+            Some(expected) if expected == actual => None,
+            expected => Some((expected.unwrap_or_else(Zero::zero), actual)),
+        }
+    }
+
+    /// The balance of the pot of `asset_id` above what it should hold according to the
+    /// accounting.
+    pub fn pot_excess(asset_id: T::AssetId) -> BalanceOf<T> {
+        let expected = Self::total_locked(asset_id).saturating_sub(Self::total_minted(asset_id));
+        Self::free_balance(asset_id, &Self::pot_account(asset_id)).saturating_sub(expected)
+    }
+
+    /// Report the discrepancy of the pot of `asset_id` if it differs from the last one
+    /// reported.
+    fn check_pot(asset_id: T::AssetId) {
+        let discrepancy = Self::pot_discrepancy(asset_id);
+        if discrepancy == Self::reported_discrepancy(asset_id) {
+            return;
+        }
+        match discrepancy {
+            Some((expected, actual)) => {
+                ReportedDiscrepancy::<T>::insert(asset_id, (expected, actual));
+                Self::deposit_event(RawEvent::PotAccountingMismatch(asset_id, expected, actual));
+            }
+            None => {
+                ReportedDiscrepancy::<T>::remove(asset_id);
+                Self::deposit_event(RawEvent::PotAccountingRestored(asset_id));
+            }
+        }
+    }
+
+    /// Start the accounting of a newly bridged asset from the balance its pot already holds.
+    /// Assets bridged before keep their accounting.
+    pub fn open_ledger(asset_id: T::AssetId) {
+        if Self::total_locked(asset_id).is_zero() && Self::total_minted(asset_id).is_zero() {
+            let balance = Self::free_balance(asset_id, &Self::pot_account(asset_id));
+            TotalLocked::<T>::insert(asset_id, balance);
+        }
+    }
+
+    fn ensure_not_paused() -> DispatchResult {
+        ensure!(!Self::is_paused(), Error::<T>::BridgeIsPaused);
+        Ok(())
+    }
+
+    /// The `fee` taken out of a transfer of `amount`, which must leave something to transfer.
+    fn fee(
+        fee: BridgeFee<BalanceOf<T>>,
+        amount: BalanceOf<T>,
+    ) -> Result<BalanceOf<T>, DispatchError> {
+        let fee = fee.compute(amount);
+        ensure!(fee < amount, Error::<T>::AmountBelowFee);
+        Ok(fee)
+    }
+
+    /// Charge `fee` in `asset_id` to `payer` on behalf of `who`. Fees in the native currency
+    /// are handed over to `OnFee`, the others paid to `AssetFeeDestination`.
+    fn charge_fee(
+        asset_id: T::AssetId,
+        payer: &T::AccountId,
+        who: &T::AccountId,
+        fee: BalanceOf<T>,
+    ) -> DispatchResult {
+        if fee.is_zero() {
+            return Ok(());
+        }
+        if asset_id == T::NativeAssetId::get() {
+            let imbalance =
+                T::Currency::withdraw(payer, fee, WithdrawReason::Fee.into(), KeepAlive)?;
+            T::OnFee::on_unbalanced(imbalance);
+        } else {
+            T::Assets::transfer(asset_id, payer, &T::AssetFeeDestination::get(), fee)?;
+        }
+
+        Self::deposit_event(RawEvent::FeeCharged(asset_id, who.clone(), fee));
+        Ok(())
+    }
+
+    fn ensure_bridged(asset_id: T::AssetId) -> DispatchResult {
+        ensure!(
+            <BridgedAssets<T>>::contains_key(asset_id),
+            Error::<T>::UnknownAsset
+        );
+        Ok(())
+    }
+
+    /// Transfer `amount` of `asset_id`, keeping native accounts alive.
+    fn transfer(
+        asset_id: T::AssetId,
+        source: &T::AccountId,
+        dest: &T::AccountId,
+        amount: BalanceOf<T>,
+    ) -> DispatchResult {
+        if asset_id == T::NativeAssetId::get() {
+            T::Currency::transfer(source, dest, amount, KeepAlive)
+        } else {
+            T::Assets::transfer(asset_id, source, dest, amount)
+        }
+    }
+
+    /// Whether `who` is a member of the relayer set.
+    pub fn is_relayer(who: &T::AccountId) -> bool {
+        Self::relayers().binary_search(who).is_ok()
+    }
+
+    /// Mint right away, or schedule the mint if it hits any of the mint limits. A mint over a
+    /// cap is scheduled no earlier than it fits under the cap.
+    fn mint_or_delay(
+        asset_id: T::AssetId,
+        deposit_id: DepositId,
+        account: T::AccountId,
+        amount: BalanceOf<T>,
+    ) -> DispatchResult {
+        Self::fee(Self::mint_fee(asset_id), amount)?;
+        let limits = Self::mint_limits(asset_id);
+        let now = <frame_system::Module<T>>::block_number();
+        let mut delay = false;
+
+        if matches!(limits.delay_threshold, Some(threshold) if amount > threshold) {
+            Self::deposit_event(RawEvent::MintThresholdExceeded(deposit_id));
+            delay = true;
+        }
+        let (account_at, global_at) =
+            Self::cap_clearance(asset_id, &account, amount, &limits, now)?;
+        if account_at > now {
+            Self::deposit_event(RawEvent::AccountMintLimitReached(
+                deposit_id,
+                account.clone(),
+            ));
+            delay = true;
+        }
+        if global_at > now {
+            Self::deposit_event(RawEvent::GlobalMintLimitReached(deposit_id));
+            delay = true;
+        }
+
+        if !delay {
+            return Self::do_mint(asset_id, deposit_id, account, amount);
+        }
+
+        let execute_at = now
+            .saturating_add(limits.delay.max(One::one()))
+            .max(account_at)
+            .max(global_at);
+        let task = Self::schedule_mint(deposit_id, execute_at)?;
+        ScheduledMints::<T>::insert(
+            deposit_id,
+            ScheduledMint {
+                asset_id,
+                account: account.clone(),
+                amount,
+                execute_at,
+                task,
+            },
+        );
+        ProcessedDeposits::<T>::insert(deposit_id, now);
+
+        Self::deposit_event(RawEvent::MintScheduled(
+            asset_id, deposit_id, account, amount, execute_at,
+        ));
+        Ok(())
+    }
+
+    /// Keep a record of a transfer and index it under `account`.
+    fn record_transfer(
+        direction: TransferDirection,
+        asset_id: T::AssetId,
+        external_id: Option<DepositId>,
+        recipient: Option<ExternalAddress>,
+        account: &T::AccountId,
+        amount: BalanceOf<T>,
+    ) -> u64 {
+        let index = NextTransferIndex::mutate(|next| {
+            let index = *next;
+            *next = next.wrapping_add(1);
+            index
+        });
+        let block = <frame_system::Module<T>>::block_number();
+        Transfers::<T>::insert(
+            index,
+            TransferRecord {
+                direction,
+                asset_id,
+                external_id,
+                recipient,
+                account: account.clone(),
+                amount,
+                block,
+            },
+        );
+
+        let max = T::MaxAccountTransfers::get() as usize;
+        AccountTransfers::<T>::mutate(account, |indices| {
+            indices.push(index);
+            if indices.len() > max {
+                indices.drain(..indices.len() - max);
+            }
+        });
+        index
+    }
+
+    /// Remove up to `MAX_PRUNED_TRANSFERS_PER_BLOCK` of the transfer records which are at least
+    /// `TransferHistoryDepth` blocks old at `now`, oldest first, and return their number.
+    fn prune_transfers(now: T::BlockNumber) -> u32 {
+        let depth = T::TransferHistoryDepth::get();
+        if now < depth {
+            return 0;
+        }
+        let expired = now - depth;
+        let next = Self::next_transfer_index();
+        let mut index = Self::oldest_transfer_index();
+        let mut pruned = 0;
+        while index != next && pruned < MAX_PRUNED_TRANSFERS_PER_BLOCK {
+            match Self::transfers(index) {
+                Some(record) if record.block > expired => break,
+                Some(record) => {
+                    Transfers::<T>::remove(index);
+                    let mut account_indices = Self::account_transfers(&record.account);
+                    account_indices.retain(|i| *i != index);
+                    if account_indices.is_empty() {
+                        AccountTransfers::<T>::remove(&record.account);
+                    } else {
+                        AccountTransfers::<T>::insert(&record.account, account_indices);
+                    }
+                }
+                None => {}
+            }
+            index = index.wrapping_add(1);
+            pruned += 1;
+        }
+        if pruned > 0 {
+            OldestTransferIndex::put(index);
+        }
+        pruned
+    }
+
+    /// Discard up to `MAX_PRUNED_PROPOSALS_PER_BLOCK` of the mint proposals which expired
+    /// before `now`, oldest first. Returns the number of entries of `ProposalExpiries` visited.
+    fn prune_proposals(now: T::BlockNumber) -> u32 {
+        let next = Self::next_proposal_index();
+        let mut index = Self::oldest_proposal_index();
+        let mut pruned = 0;
+        while index != next && pruned < MAX_PRUNED_PROPOSALS_PER_BLOCK {
+            if let Some((expiry, deposit_id)) = Self::proposal_expiries(index) {
+                if expiry >= now {
+                    break;
+                }
+                ProposalExpiries::<T>::remove(index);
+                // The proposal may have been executed, or reopened under a later entry.
+                let open = Self::mint_proposals(&deposit_id)
+                    .map_or(false, |proposal| proposal.expiry == expiry);
+                if open {
+                    MintProposals::<T>::remove(&deposit_id);
+                    Self::deposit_event(RawEvent::MintProposalExpired(deposit_id));
+                }
+            }
+            index = index.wrapping_add(1);
+            pruned += 1;
+        }
+        if pruned > 0 {
+            OldestProposalIndex::put(index);
+        }
+        pruned
+    }
+
+    /// Move the scheduled mint of `deposit_id` to block `execute_at`.
+    fn reschedule_mint(
+        deposit_id: DepositId,
+        scheduled: ScheduledMintOf<T>,
+        execute_at: T::BlockNumber,
+    ) -> DispatchResult {
+        let task = Self::schedule_mint(deposit_id, execute_at)?;
+        ScheduledMints::<T>::insert(
+            deposit_id,
+            ScheduledMint {
+                execute_at,
+                task,
+                ..scheduled
+            },
+        );
+        Ok(())
+    }
+
+    /// Reschedule to the next block up to `MAX_RELEASED_MINTS_PER_BLOCK` of the mints held
+    /// while the bridge was paused, once it is not anymore. Returns the number of mints
+    /// rescheduled.
+    fn release_held_mints(now: T::BlockNumber) -> u32 {
+        if Self::is_paused() {
+            return 0;
+        }
+        let mut held = Self::held_mints();
+        if held.is_empty() {
+            return 0;
+        }
+
+        let count = held.len().min(MAX_RELEASED_MINTS_PER_BLOCK as usize);
+        let mut failed = Vec::new();
+        for deposit_id in held.drain(..count) {
+            if let Some(scheduled) = Self::scheduled_mints(deposit_id) {
+                let execute_at = now.saturating_add(One::one());
+                if Self::reschedule_mint(deposit_id, scheduled, execute_at).is_err() {
+                    failed.push(deposit_id);
+                }
+            }
+        }
+        held.extend(failed);
+        HeldMints::put(held);
+        count as u32
+    }
+
+    /// Schedule the execution of the mint of `deposit_id` at block `when`.
+    fn schedule_mint(
+        deposit_id: DepositId,
+        when: T::BlockNumber,
+    ) -> Result<
+        <T::Scheduler as ScheduleAnon<T::BlockNumber, T::ScheduledCall, T::PalletsOrigin>>::Address,
+        DispatchError,
+    > {
+        T::Scheduler::schedule(
+            DispatchTime::At(when),
+            None,
+            HARD_DEADLINE,
+            frame_system::RawOrigin::Root.into(),
+            Call::execute_scheduled_mint(deposit_id).into(),
+        )
+        .map_err(|_| Error::<T>::FailedToSchedule.into())
+    }
+
+    /// The first blocks from `now` on at which a mint of `amount` of `asset_id` to `account`
+    /// fits under the per-account and under the global cap of `limits`.
+    fn cap_clearance(
+        asset_id: T::AssetId,
+        account: &T::AccountId,
+        amount: BalanceOf<T>,
+        limits: &MintLimits<BalanceOf<T>, T::BlockNumber>,
+        now: T::BlockNumber,
+    ) -> Result<(T::BlockNumber, T::BlockNumber), DispatchError> {
+        let fits_at = |window: &MintWindow<_, _>, cap: Option<BalanceOf<T>>| match cap {
+            Some(cap) if !limits.period.is_zero() => {
+                Self::fits_at(window, amount, cap, now, limits.period)
+                    .ok_or(Error::<T>::MintAboveCap)
+            }
+            _ => Ok(now),
+        };
+        Ok((
+            fits_at(&Self::account_minted(asset_id, account), limits.per_account)?,
+            fits_at(&Self::global_minted(asset_id), limits.global)?,
+        ))
+    }
+
+    /// The first block from `now` on at which `amount` more fits under `cap` in `window`, or
+    /// `None` if `amount` is above `cap`.
+    fn fits_at(
+        window: &MintWindow<BalanceOf<T>, T::BlockNumber>,
+        amount: BalanceOf<T>,
+        cap: BalanceOf<T>,
+        now: T::BlockNumber,
+        period: T::BlockNumber,
+    ) -> Option<T::BlockNumber> {
+        if amount > cap {
+            return None;
+        }
+        let mut minted = Self::minted_in_window(window, now, period);
+        let mut at = now;
+        // Buckets leave the window oldest first.
+        for (start, bucket) in &window.buckets {
+            if minted.saturating_add(amount) <= cap {
+                break;
+            }
+            let expiry = Self::bucket_expiry(*start, period);
+            if expiry > now {
+                minted = minted.saturating_sub(*bucket);
+                at = expiry;
+            }
+        }
+        Some(at)
+    }
+
+    /// Amount minted within the last `period` blocks of `window`.
+    fn minted_in_window(
+        window: &MintWindow<BalanceOf<T>, T::BlockNumber>,
+        now: T::BlockNumber,
+        period: T::BlockNumber,
+    ) -> BalanceOf<T> {
+        window
+            .buckets
+            .iter()
+            .filter(|(start, _)| Self::bucket_expiry(*start, period) > now)
+            .fold(Zero::zero(), |minted: BalanceOf<T>, (_, bucket)| {
+                minted.saturating_add(*bucket)
+            })
+    }
+
+    /// Add `amount` to the bucket of `now` in `window`, dropping the buckets out of the window.
+    fn note_minted(
+        window: &mut MintWindow<BalanceOf<T>, T::BlockNumber>,
+        amount: BalanceOf<T>,
+        now: T::BlockNumber,
+        period: T::BlockNumber,
+    ) {
+        window
+            .buckets
+            .retain(|(start, _)| Self::bucket_expiry(*start, period) > now);
+        let start = now - now % Self::bucket_length(period);
+        match window.buckets.last_mut() {
+            Some((last, minted)) if *last == start => *minted = minted.saturating_add(amount),
+            _ => window.buckets.push((start, amount)),
+        }
+    }
+
+    /// Number of blocks in a bucket of a mint window.
+    fn bucket_length(period: T::BlockNumber) -> T::BlockNumber {
+        (period / MINT_WINDOW_BUCKETS.into()).max(One::one())
+    }
+
+    /// The first block at which the bucket starting at `start` is out of the window.
+    fn bucket_expiry(start: T::BlockNumber, period: T::BlockNumber) -> T::BlockNumber {
+        start
+            .saturating_add(Self::bucket_length(period))
+            .saturating_add(period)
+            .saturating_sub(One::one())
+    }
+
+    /// Transfer `amount` from the bridge pot of `asset_id` to `account`, less the bridge fee,
+    /// and mark `deposit_id` as processed. Fails if `Recipients` does not allow `account`.
+    fn do_mint(
+        asset_id: T::AssetId,
+        deposit_id: DepositId,
+        account: T::AccountId,
+        amount: BalanceOf<T>,
+    ) -> DispatchResult {
+        ensure!(
+            T::Recipients::allows(&account),
+            Error::<T>::RecipientNotAllowed
+        );
+        let pot = Self::pot_account(asset_id);
+        let fee = Self::fee(Self::mint_fee(asset_id), amount)?;
+        let credited = amount - fee;
+        Self::transfer(asset_id, &pot, &account, credited)?;
+        Self::charge_fee(asset_id, &pot, &account, fee)?;
+        TotalMinted::<T>::mutate(asset_id, |minted| *minted = minted.saturating_add(amount));
+        let now = <frame_system::Module<T>>::block_number();
+        ProcessedDeposits::<T>::insert(deposit_id, now);
+
+        let period = Self::mint_limits(asset_id).period;
+        if !period.is_zero() {
+            GlobalMinted::<T>::mutate(asset_id, |window| {
+                Self::note_minted(window, amount, now, period)
+            });
+            AccountMinted::<T>::mutate(asset_id, &account, |window| {
+                Self::note_minted(window, amount, now, period)
+            });
+        }
+
+        Self::record_transfer(
+            TransferDirection::Inbound,
+            asset_id,
+            Some(deposit_id),
+            None,
+            &account,
+            credited,
+        );
+
+        Self::deposit_event(RawEvent::Minted(asset_id, deposit_id, account, credited));
+        Ok(())
+    }
+}
+
+/// Custom validity errors of the relayer votes.
+#[repr(u8)]
+pub enum ValidityError {
+    /// The signer of the vote is not a relayer.
+    NotRelayer = 0,
+    /// The bridge is paused.
+    BridgePaused = 1,
+}
+
+impl From<ValidityError> for u8 {
+    fn from(err: ValidityError) -> Self {
+        err as u8
+    }
+}
+
+/// Signed extension admitting only the relayer votes which can still count, with
+/// `RelayerVotePriority`.
+///
+/// Votes are free of fees, so the votes of non-relayers, the votes for processed deposits
+/// and the repeated votes are rejected before they reach the pool, including the votes
+/// `WrappedVotes` finds in other calls. Other calls are left as they are.
+#[derive(Encode, Decode, Clone, Eq, PartialEq)]
+pub struct CheckRelayerVote<T: Trait + Send + Sync>(PhantomData<T>);
+
+impl<T: Trait + Send + Sync> CheckRelayerVote<T> {
+    /// Create new `SignedExtension` to check relayer votes.
+    pub fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<T: Trait + Send + Sync> Default for CheckRelayerVote<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Trait + Send + Sync> sp_std::fmt::Debug for CheckRelayerVote<T> {
+    #[cfg(feature = "std")]
+    fn fmt(&self, f: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+        write!(f, "CheckRelayerVote")
+    }
+
+    #[cfg(not(feature = "std"))]
+    fn fmt(&self, _: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+        Ok(())
+    }
+}
+
+impl<T: Trait + Send + Sync> SignedExtension for CheckRelayerVote<T>
+where
+    <T as frame_system::Trait>::Call: IsSubType<Call<T>>,
+{
+    const IDENTIFIER: &'static str = "CheckRelayerVote";
+    type AccountId = T::AccountId;
+    type Call = <T as frame_system::Trait>::Call;
+    type AdditionalSigned = ();
+    type Pre = ();
+
+    fn additional_signed(&self) -> Result<(), TransactionValidityError> {
+        Ok(())
+    }
+
+    fn validate(
+        &self,
+        who: &Self::AccountId,
+        call: &Self::Call,
+        _info: &DispatchInfoOf<Self::Call>,
+        _len: usize,
+    ) -> TransactionValidity {
+        // Wrapped votes are not free, but they are checked all the same.
+        for (voter, deposit_id) in T::WrappedVotes::wrapped_votes(who, call) {
+            Self::check_vote(&voter, &deposit_id)?;
+        }
+        let deposit_id = match call.is_sub_type() {
+            Some(Call::vote_mint(_, deposit_id, _, _)) => deposit_id,
+            _ => return Ok(ValidTransaction::default()),
+        };
+        Self::check_vote(who, deposit_id)?;
+
+        Ok(ValidTransaction {
+            priority: T::RelayerVotePriority::get(),
+            provides: vec![("bridge_vote", who, deposit_id).encode()],
+            ..Default::default()
+        })
+    }
+}
+
+impl<T: Trait + Send + Sync> CheckRelayerVote<T> {
+    /// Whether the vote of `who` for `deposit_id` can still count.
+    fn check_vote(
+        who: &T::AccountId,
+        deposit_id: &DepositId,
+    ) -> Result<(), TransactionValidityError> {
+        if !Module::<T>::is_relayer(who) {
+            return Err(InvalidTransaction::Custom(ValidityError::NotRelayer.into()).into());
+        }
+        if Module::<T>::is_paused() {
+            return Err(InvalidTransaction::Custom(ValidityError::BridgePaused.into()).into());
+        }
+        if Module::<T>::is_processed(deposit_id) {
+            return Err(InvalidTransaction::Stale.into());
+        }
+        let now = <frame_system::Module<T>>::block_number();
+        let voted = Module::<T>::mint_proposals(deposit_id).map_or(false, |proposal| {
+            proposal.expiry >= now && proposal.votes.contains(who)
+        });
+        if voted {
+            return Err(InvalidTransaction::Stale.into());
+        }
+        Ok(())
+    }
 }
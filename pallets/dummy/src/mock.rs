@@ -20,16 +20,19 @@
 use super::*;
 use crate as dummy;
 use frame_support::{
-    impl_outer_dispatch, impl_outer_event, impl_outer_origin, parameter_types, weights::Weight,
+    impl_outer_dispatch, impl_outer_event, impl_outer_origin, ord_parameter_types, parameter_types,
+    traits::{Currency, OnUnbalanced},
+    weights::Weight,
 };
-use frame_system::EnsureRoot;
+use frame_system::{EnsureRoot, EnsureSignedBy};
 use pallet_balances as balances;
 use sp_core::H256;
 use sp_runtime::{
-    testing::Header,
-    traits::{BlakeTwo256, IdentityLookup},
+    testing::{Header, TestSignature, TestXt, UintAuthorityId},
+    traits::{BlakeTwo256, Extrinsic as ExtrinsicT, IdentityLookup},
     Perbill,
 };
+use std::{cell::RefCell, collections::BTreeMap};
 
 impl_outer_origin! {
     pub enum Origin for Test where system = frame_system {}
@@ -44,11 +47,13 @@ impl_outer_event! {
         frame_system<T>,
         dummy<T>,
         balances<T>,
+        pallet_scheduler<T>,
     }
 }
 
 impl_outer_dispatch! {
     pub enum OuterCall for Test where origin: Origin {
+        frame_system::System,
         dummy::Dummy,
         balances::Balances,
     }
@@ -109,14 +114,177 @@ impl balances::Trait for Test {
     type WeightInfo = ();
 }
 
+thread_local! {
+    static ASSET_BALANCES: RefCell<BTreeMap<(u32, u64), u64>> = RefCell::new(BTreeMap::new());
+    static BLOCKED_RECIPIENTS: RefCell<Vec<u64>> = RefCell::new(Vec::new());
+}
+
+/// Bridged assets besides the native currency, kept in memory.
+pub struct TestAssets;
+impl TestAssets {
+    pub fn set_balance(asset: u32, who: u64, amount: u64) {
+        ASSET_BALANCES.with(|b| b.borrow_mut().insert((asset, who), amount));
+    }
+}
+impl MultiAsset<u64, u32, u64> for TestAssets {
+    fn free_balance(asset: u32, who: &u64) -> u64 {
+        ASSET_BALANCES.with(|b| b.borrow().get(&(asset, *who)).copied().unwrap_or_default())
+    }
+
+    fn transfer(asset: u32, source: &u64, dest: &u64, amount: u64) -> DispatchResult {
+        let from = Self::free_balance(asset, source)
+            .checked_sub(amount)
+            .ok_or(DispatchError::Other("InsufficientBalance"))?;
+        let to = Self::free_balance(asset, dest) + amount;
+        Self::set_balance(asset, *source, from);
+        Self::set_balance(asset, *dest, to);
+        Ok(())
+    }
+}
+
+/// Allows every recipient but the ones blocked by `block`.
+pub struct TestRecipients;
+impl TestRecipients {
+    pub fn block(who: u64) {
+        BLOCKED_RECIPIENTS.with(|b| b.borrow_mut().push(who));
+    }
+}
+impl RecipientFilter<u64> for TestRecipients {
+    fn allows(who: &u64) -> bool {
+        BLOCKED_RECIPIENTS.with(|b| !b.borrow().contains(who))
+    }
+
+    #[cfg(feature = "runtime-benchmarks")]
+    fn allow(who: &u64) {
+        BLOCKED_RECIPIENTS.with(|b| b.borrow_mut().retain(|blocked| blocked != who));
+    }
+}
+
+/// Accepts any deposit as its own proof, unless its id is zero.
+pub struct TestVerifier;
+impl VerifyDeposit<u64, u64> for TestVerifier {
+    type Proof = ProvenDeposit<u64, u64>;
+
+    fn verify(proof: &Self::Proof) -> Result<ProvenDeposit<u64, u64>, DispatchError> {
+        if proof.deposit_id.is_zero() {
+            return Err(DispatchError::Other("InvalidProof"));
+        }
+        Ok(proof.clone())
+    }
+
+    #[cfg(feature = "runtime-benchmarks")]
+    fn successful_proof(deposit: &ProvenDeposit<u64, u64>) -> Self::Proof {
+        deposit.clone()
+    }
+}
+
+/// Collects the bridge fees into `FEE_COLLECTOR`.
+pub struct FeeCollector;
+impl OnUnbalanced<NegativeImbalanceOf<Test>> for FeeCollector {
+    fn on_nonzero_unbalanced(amount: NegativeImbalanceOf<Test>) {
+        Balances::resolve_creating(&FEE_COLLECTOR, amount);
+    }
+}
+
+pub const FEE_COLLECTOR: u64 = 999;
+/// Refills the pots.
+pub const TREASURY: u64 = 998;
+
 parameter_types! {
     pub const BridgeModuleId: ModuleId = ModuleId(*b"test/001");
+    pub const NativeAssetId: u32 = 0;
+    pub const MaxRelayers: u32 = 4;
+    pub const ProposalLifetime: u64 = 10;
+    pub const RelayerVotePriority: TransactionPriority = 1 << 20;
+    pub const TransferHistoryDepth: u64 = 20;
+    pub const MaxAccountTransfers: u32 = 3;
+    pub const AssetFeeDestination: u64 = FEE_COLLECTOR;
+    pub const RefillSource: u64 = TREASURY;
+}
+ord_parameter_types! {
+    pub const PauseAccount: u64 = 100;
+    pub const CancelAccount: u64 = 101;
+}
+pub type Extrinsic = TestXt<OuterCall, ()>;
+
+/// Signs with the `UintAuthorityId` keys set by `UintAuthorityId::set_all_keys`.
+pub struct TestAuthId;
+impl AppCrypto<UintAuthorityId, TestSignature> for TestAuthId {
+    type RuntimeAppPublic = UintAuthorityId;
+    type GenericSignature = TestSignature;
+    type GenericPublic = UintAuthorityId;
+}
+
+impl frame_system::offchain::SigningTypes for Test {
+    type Public = UintAuthorityId;
+    type Signature = TestSignature;
+}
+
+impl<LocalCall> frame_system::offchain::SendTransactionTypes<LocalCall> for Test
+where
+    OuterCall: From<LocalCall>,
+{
+    type OverarchingCall = OuterCall;
+    type Extrinsic = Extrinsic;
+}
+
+impl<LocalCall> CreateSignedTransaction<LocalCall> for Test
+where
+    OuterCall: From<LocalCall>,
+{
+    fn create_transaction<C: AppCrypto<Self::Public, Self::Signature>>(
+        call: OuterCall,
+        _public: UintAuthorityId,
+        account: u64,
+        _nonce: u64,
+    ) -> Option<(OuterCall, <Extrinsic as ExtrinsicT>::SignaturePayload)> {
+        Some((call, (account, ())))
+    }
 }
+
+parameter_types! {
+    pub const MaximumSchedulerWeight: Weight = Weight::max_value();
+    pub const MaxScheduledPerBlock: u32 = 50;
+}
+
+impl pallet_scheduler::Trait for Test {
+    type Event = TestEvent;
+    type Origin = Origin;
+    type PalletsOrigin = OriginCaller;
+    type Call = OuterCall;
+    type MaximumWeight = MaximumSchedulerWeight;
+    type ScheduleOrigin = EnsureRoot<u64>;
+    type MaxScheduledPerBlock = MaxScheduledPerBlock;
+    type WeightInfo = ();
+}
+
 impl Trait for Test {
     type Event = TestEvent;
+    type AuthorityId = TestAuthId;
     type Currency = pallet_balances::Module<Test>;
+    type AssetId = u32;
+    type NativeAssetId = NativeAssetId;
+    type Assets = TestAssets;
+    type DepositVerifier = TestVerifier;
+    type Recipients = TestRecipients;
+    type OnFee = FeeCollector;
+    type AssetFeeDestination = AssetFeeDestination;
     type WeightInfo = ();
     type MintOrigin = EnsureRoot<Self::AccountId>;
+    type AdminOrigin = EnsureRoot<Self::AccountId>;
+    type PauseOrigin = EnsureSignedBy<PauseAccount, Self::AccountId>;
+    type MaxRelayers = MaxRelayers;
+    type ProposalLifetime = ProposalLifetime;
+    type RelayerVotePriority = RelayerVotePriority;
+    type WrappedVotes = ();
+    type ScheduledCall = OuterCall;
+    type PalletsOrigin = OriginCaller;
+    type Scheduler = Scheduler;
+    type CancelOrigin = EnsureSignedBy<CancelAccount, Self::AccountId>;
+    type PotOrigin = EnsureRoot<Self::AccountId>;
+    type RefillSource = RefillSource;
+    type TransferHistoryDepth = TransferHistoryDepth;
+    type MaxAccountTransfers = MaxAccountTransfers;
     type ModuleId = BridgeModuleId;
 }
 
@@ -124,6 +292,7 @@ impl Trait for Test {
 pub type System = frame_system::Module<Test>;
 pub type Balances = balances::Module<Test>;
 pub type Dummy = dummy::Module<Test>;
+pub type Scheduler = pallet_scheduler::Module<Test>;
 
 // New types for dispatchable functions.
 // pub type DummyCall = dummy::Call<Test>;
@@ -134,8 +303,14 @@ pub fn new_test_ext(_root_key: u64) -> sp_io::TestExternalities {
     let mut t = frame_system::GenesisConfig::default()
         .build_storage::<Test>()
         .unwrap();
-    super::GenesisConfig::<Test> { balance: 1_000_000 }
-        .assimilate_storage(&mut t)
-        .unwrap();
+    super::GenesisConfig::<Test> {
+        balance: 1_000_000,
+        relayers: vec![12, 10, 11],
+        relayer_threshold: 2,
+        assets: vec![(0, H160::repeat_byte(0xcc)), (1, H160::repeat_byte(0xaa))],
+    }
+    .assimilate_storage(&mut t)
+    .unwrap();
+    ASSET_BALANCES.with(|b| b.borrow_mut().clear());
     t.into()
 }
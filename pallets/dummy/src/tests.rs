@@ -18,23 +18,1443 @@
 //! Tests for the module.
 
 use super::*;
-use frame_support::{assert_noop, assert_ok};
-use mock::{new_test_ext, Balances, Dummy, Test};
+use frame_support::{
+    assert_noop, assert_ok,
+    traits::{OnFinalize, OnInitialize},
+};
+use mock::{new_test_ext, Balances, Dummy, Scheduler, System, Test, TestAssets, TestEvent};
+
+fn deposit(n: u64) -> DepositId {
+    H256::from_low_u64_be(n)
+}
+
+fn dummy_events() -> Vec<RawEvent<u64, u32, u64, u64>> {
+    System::events()
+        .into_iter()
+        .filter_map(|record| match record.event {
+            TestEvent::dummy(event) => Some(event),
+            _ => None,
+        })
+        .collect()
+}
+
+fn run_to_block(n: u64) {
+    System::set_block_number(n);
+    Scheduler::on_initialize(n);
+}
+
+/// Every pot holds what was locked in it minus what was minted out of it.
+fn assert_pots_balanced() {
+    for asset_id in 0..2 {
+        assert_eq!(Dummy::pot_discrepancy(asset_id), None);
+    }
+}
 
 #[test]
 fn test_setup_works() {
     use frame_system::RawOrigin;
     new_test_ext(1).execute_with(|| {
         assert_eq!(Balances::total_issuance(), 1_000_000);
-        assert_ok!(Dummy::mint(RawOrigin::Root.into(), 2, 400));
+        assert_eq!(Dummy::storage_version(), Releases::V2);
+        assert_ok!(Dummy::mint(RawOrigin::Root.into(), 0, deposit(1), 2, 400));
         assert_eq!(Balances::free_balance(2), 400);
         assert_eq!(Balances::total_issuance(), 1_000_000);
 
         assert_noop!(
-            Dummy::mint(RawOrigin::Root.into(), 2, 1_000_000),
+            Dummy::mint(RawOrigin::Root.into(), 0, deposit(2), 2, 1_000_000),
             pallet_balances::Error::<Test, _>::InsufficientBalance
         );
 
         assert_eq!(Balances::free_balance(Dummy::account_id()), 1_000_000 - 400);
+        assert_pots_balanced();
+    });
+}
+
+#[test]
+fn burn_moves_funds_into_pot() {
+    use frame_system::RawOrigin;
+    new_test_ext(1).execute_with(|| {
+        assert_ok!(Dummy::mint(RawOrigin::Root.into(), 0, deposit(1), 2, 400));
+        assert_ok!(Dummy::burn(
+            RawOrigin::Signed(2).into(),
+            0,
+            H160::repeat_byte(0x11),
+            300
+        ));
+        assert_eq!(Balances::free_balance(2), 100);
+        assert_eq!(Balances::free_balance(Dummy::account_id()), 1_000_000 - 100);
+        assert_eq!(Balances::total_issuance(), 1_000_000);
+
+        assert_noop!(
+            Dummy::burn(RawOrigin::Signed(2).into(), 0, H160::repeat_byte(0x11), 0),
+            Error::<Test>::ZeroAmount
+        );
+        assert_noop!(
+            Dummy::burn(RawOrigin::Signed(2).into(), 0, H160::repeat_byte(0x11), 100),
+            pallet_balances::Error::<Test, _>::KeepAlive
+        );
+        assert_eq!(Dummy::total_locked(0), 1_000_000 + 300);
+        assert_eq!(Dummy::total_minted(0), 400);
+        assert_pots_balanced();
+    });
+}
+
+#[test]
+fn mint_rejects_replayed_deposit() {
+    use frame_system::RawOrigin;
+    new_test_ext(1).execute_with(|| {
+        System::set_block_number(5);
+        assert!(!Dummy::is_processed(&deposit(1)));
+        assert_ok!(Dummy::mint(RawOrigin::Root.into(), 0, deposit(1), 2, 400));
+        assert_eq!(Dummy::processed_deposits(deposit(1)), Some(5));
+
+        assert_noop!(
+            Dummy::mint(RawOrigin::Root.into(), 0, deposit(1), 2, 400),
+            Error::<Test>::DepositAlreadyProcessed
+        );
+        assert_eq!(Balances::free_balance(2), 400);
+
+        // A failed mint does not consume the deposit.
+        assert!(Dummy::mint(RawOrigin::Root.into(), 0, deposit(2), 3, 2_000_000).is_err());
+        assert!(!Dummy::is_processed(&deposit(2)));
+    });
+}
+
+#[test]
+fn relayer_set_is_managed_by_admin() {
+    use frame_system::RawOrigin;
+    new_test_ext(1).execute_with(|| {
+        assert_eq!(Dummy::relayers(), vec![10, 11, 12]);
+        assert_eq!(Dummy::relayer_threshold(), 2);
+
+        assert_noop!(
+            Dummy::add_relayer(RawOrigin::Signed(10).into(), 13),
+            sp_runtime::traits::BadOrigin
+        );
+        assert_noop!(
+            Dummy::add_relayer(RawOrigin::Root.into(), 11),
+            Error::<Test>::AlreadyRelayer
+        );
+        assert_ok!(Dummy::add_relayer(RawOrigin::Root.into(), 9));
+        assert_eq!(Dummy::relayers(), vec![9, 10, 11, 12]);
+        assert_noop!(
+            Dummy::add_relayer(RawOrigin::Root.into(), 13),
+            Error::<Test>::TooManyRelayers
+        );
+
+        assert_ok!(Dummy::remove_relayer(RawOrigin::Root.into(), 10));
+        assert_eq!(Dummy::relayers(), vec![9, 11, 12]);
+        assert_noop!(
+            Dummy::remove_relayer(RawOrigin::Root.into(), 10),
+            Error::<Test>::NotRelayer
+        );
+
+        assert_noop!(
+            Dummy::set_threshold(RawOrigin::Root.into(), 0),
+            Error::<Test>::InvalidThreshold
+        );
+        assert_noop!(
+            Dummy::set_threshold(RawOrigin::Root.into(), 4),
+            Error::<Test>::InvalidThreshold
+        );
+        assert_ok!(Dummy::set_threshold(RawOrigin::Root.into(), 3));
+        assert_eq!(Dummy::relayer_threshold(), 3);
+        assert_noop!(
+            Dummy::remove_relayer(RawOrigin::Root.into(), 9),
+            Error::<Test>::TooFewRelayers
+        );
+    });
+}
+
+#[test]
+fn votes_of_removed_relayers_do_not_count() {
+    use frame_system::RawOrigin;
+    new_test_ext(1).execute_with(|| {
+        assert_ok!(Dummy::vote_mint(
+            RawOrigin::Signed(10).into(),
+            0,
+            deposit(1),
+            2,
+            400
+        ));
+        assert_ok!(Dummy::remove_relayer(RawOrigin::Root.into(), 10));
+
+        assert_ok!(Dummy::vote_mint(
+            RawOrigin::Signed(11).into(),
+            0,
+            deposit(1),
+            2,
+            400
+        ));
+        assert_eq!(Balances::free_balance(2), 0);
+        assert_eq!(Dummy::mint_proposals(deposit(1)).unwrap().votes, vec![11]);
+
+        assert_ok!(Dummy::vote_mint(
+            RawOrigin::Signed(12).into(),
+            0,
+            deposit(1),
+            2,
+            400
+        ));
+        assert_eq!(Balances::free_balance(2), 400);
+    });
+}
+
+#[test]
+fn mint_executes_once_threshold_is_reached() {
+    use frame_system::RawOrigin;
+    new_test_ext(1).execute_with(|| {
+        assert_noop!(
+            Dummy::vote_mint(RawOrigin::Signed(2).into(), 0, deposit(1), 2, 400),
+            Error::<Test>::NotRelayer
+        );
+
+        assert_ok!(Dummy::vote_mint(
+            RawOrigin::Signed(10).into(),
+            0,
+            deposit(1),
+            2,
+            400
+        ));
+        assert_eq!(Balances::free_balance(2), 0);
+        assert_eq!(Dummy::mint_proposals(deposit(1)).unwrap().votes, vec![10]);
+        assert_noop!(
+            Dummy::vote_mint(RawOrigin::Signed(10).into(), 0, deposit(1), 2, 400),
+            Error::<Test>::AlreadyVoted
+        );
+        assert_noop!(
+            Dummy::vote_mint(RawOrigin::Signed(11).into(), 0, deposit(1), 2, 500),
+            Error::<Test>::ProposalMismatch
+        );
+
+        assert_ok!(Dummy::vote_mint(
+            RawOrigin::Signed(11).into(),
+            0,
+            deposit(1),
+            2,
+            400
+        ));
+        assert_eq!(Balances::free_balance(2), 400);
+        assert!(Dummy::mint_proposals(deposit(1)).is_none());
+        assert!(Dummy::is_processed(&deposit(1)));
+
+        assert_noop!(
+            Dummy::vote_mint(RawOrigin::Signed(12).into(), 0, deposit(1), 2, 400),
+            Error::<Test>::DepositAlreadyProcessed
+        );
+    });
+}
+
+#[test]
+fn expired_proposal_is_restarted() {
+    use frame_system::RawOrigin;
+    new_test_ext(1).execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(Dummy::vote_mint(
+            RawOrigin::Signed(10).into(),
+            0,
+            deposit(1),
+            2,
+            400
+        ));
+        assert_eq!(Dummy::mint_proposals(deposit(1)).unwrap().expiry, 11);
+
+        System::set_block_number(12);
+        assert_ok!(Dummy::vote_mint(
+            RawOrigin::Signed(11).into(),
+            0,
+            deposit(1),
+            3,
+            500
+        ));
+        let proposal = Dummy::mint_proposals(deposit(1)).unwrap();
+        assert_eq!(proposal.votes, vec![11]);
+        assert_eq!(proposal.account, 3);
+        assert_eq!(Balances::free_balance(3), 0);
+
+        assert_ok!(Dummy::vote_mint(
+            RawOrigin::Signed(12).into(),
+            0,
+            deposit(1),
+            3,
+            500
+        ));
+        assert_eq!(Balances::free_balance(3), 500);
+    });
+}
+
+#[test]
+fn expired_proposals_are_pruned() {
+    use frame_system::RawOrigin;
+    new_test_ext(1).execute_with(|| {
+        System::set_block_number(1);
+        for n in 1..=3 {
+            assert_ok!(Dummy::vote_mint(
+                RawOrigin::Signed(10).into(),
+                0,
+                deposit(n),
+                2,
+                400
+            ));
+        }
+        System::set_block_number(5);
+        assert_ok!(Dummy::vote_mint(
+            RawOrigin::Signed(10).into(),
+            0,
+            deposit(4),
+            2,
+            400
+        ));
+        // The proposal of the second deposit is executed, the one of the third reopened.
+        assert_ok!(Dummy::vote_mint(
+            RawOrigin::Signed(11).into(),
+            0,
+            deposit(2),
+            2,
+            400
+        ));
+        System::set_block_number(12);
+        assert_ok!(Dummy::vote_mint(
+            RawOrigin::Signed(11).into(),
+            0,
+            deposit(3),
+            2,
+            400
+        ));
+        assert_eq!(Dummy::next_proposal_index(), 5);
+
+        // The proposals are open until their expiry block.
+        Dummy::on_initialize(11);
+        assert_eq!(Dummy::oldest_proposal_index(), 0);
+        System::reset_events();
+        Dummy::on_initialize(12);
+        assert_eq!(Dummy::oldest_proposal_index(), 3);
+        assert_eq!(Dummy::mint_proposals(deposit(1)), None);
+        assert_eq!(Dummy::mint_proposals(deposit(3)).unwrap().votes, vec![11]);
+        assert_eq!(
+            dummy_events(),
+            vec![RawEvent::MintProposalExpired(deposit(1))]
+        );
+
+        Dummy::on_initialize(16);
+        assert_eq!(Dummy::oldest_proposal_index(), 4);
+        assert_eq!(Dummy::mint_proposals(deposit(4)), None);
+        Dummy::on_initialize(23);
+        assert_eq!(Dummy::oldest_proposal_index(), 5);
+        assert!(Dummy::pending_proposals().is_empty());
+        assert_eq!(MintProposals::<Test>::iter().count(), 0);
+    });
+}
+
+#[test]
+fn failed_mint_keeps_proposal_open() {
+    use frame_system::RawOrigin;
+    new_test_ext(1).execute_with(|| {
+        assert_ok!(Dummy::vote_mint(
+            RawOrigin::Signed(10).into(),
+            0,
+            deposit(1),
+            2,
+            2_000_000
+        ));
+        assert!(
+            Dummy::vote_mint(RawOrigin::Signed(11).into(), 0, deposit(1), 2, 2_000_000).is_err()
+        );
+        assert_eq!(Dummy::mint_proposals(deposit(1)).unwrap().votes, vec![10]);
+        assert!(!Dummy::is_processed(&deposit(1)));
+    });
+}
+
+#[test]
+fn large_mint_is_delayed() {
+    use frame_system::RawOrigin;
+    new_test_ext(1).execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(Dummy::set_mint_limits(
+            RawOrigin::Root.into(),
+            0,
+            MintLimits {
+                delay_threshold: Some(1_000),
+                delay: 5,
+                ..Default::default()
+            }
+        ));
+
+        assert_ok!(Dummy::mint(RawOrigin::Root.into(), 0, deposit(1), 2, 1_000));
+        assert_eq!(Balances::free_balance(2), 1_000);
+
+        assert_ok!(Dummy::mint(RawOrigin::Root.into(), 0, deposit(2), 2, 2_000));
+        assert_eq!(Balances::free_balance(2), 1_000);
+        assert!(Dummy::is_processed(&deposit(2)));
+        assert_eq!(
+            Dummy::scheduled_mints(deposit(2)).map(|mint| mint.execute_at),
+            Some(6)
+        );
+        assert!(dummy_events().ends_with(&[
+            RawEvent::MintThresholdExceeded(deposit(2)),
+            RawEvent::MintScheduled(0, deposit(2), 2, 2_000, 6),
+        ]));
+        assert_noop!(
+            Dummy::mint(RawOrigin::Root.into(), 0, deposit(2), 2, 2_000),
+            Error::<Test>::DepositAlreadyProcessed
+        );
+
+        run_to_block(5);
+        assert_eq!(Balances::free_balance(2), 1_000);
+        run_to_block(6);
+        assert_eq!(Balances::free_balance(2), 3_000);
+        assert!(Dummy::scheduled_mints(deposit(2)).is_none());
+        assert!(dummy_events().ends_with(&[
+            RawEvent::Minted(0, deposit(2), 2, 2_000),
+            RawEvent::ScheduledMintExecuted(deposit(2)),
+        ]));
+    });
+}
+
+#[test]
+fn mint_over_cap_is_delayed() {
+    use frame_system::RawOrigin;
+    new_test_ext(1).execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(Dummy::set_mint_limits(
+            RawOrigin::Root.into(),
+            0,
+            MintLimits {
+                period: 10,
+                per_account: Some(500),
+                global: Some(800),
+                delay_threshold: None,
+                delay: 3,
+            }
+        ));
+
+        assert_ok!(Dummy::mint(RawOrigin::Root.into(), 0, deposit(1), 2, 400));
+        assert_ok!(Dummy::mint(RawOrigin::Root.into(), 0, deposit(2), 2, 200));
+        assert_eq!(Balances::free_balance(2), 400);
+        // The mint waits until the first one leaves the window.
+        assert!(dummy_events().ends_with(&[
+            RawEvent::AccountMintLimitReached(deposit(2), 2),
+            RawEvent::MintScheduled(0, deposit(2), 2, 200, 11),
+        ]));
+
+        assert_ok!(Dummy::mint(RawOrigin::Root.into(), 0, deposit(3), 3, 300));
+        assert_eq!(Balances::free_balance(3), 300);
+        assert_ok!(Dummy::mint(RawOrigin::Root.into(), 0, deposit(4), 4, 200));
+        assert_eq!(Balances::free_balance(4), 0);
+        assert!(dummy_events().ends_with(&[
+            RawEvent::GlobalMintLimitReached(deposit(4)),
+            RawEvent::MintScheduled(0, deposit(4), 4, 200, 11),
+        ]));
+
+        for n in 2..=10 {
+            run_to_block(n);
+        }
+        assert_eq!(Balances::free_balance(2), 400);
+        assert_eq!(Balances::free_balance(4), 0);
+        run_to_block(11);
+        assert_eq!(Balances::free_balance(2), 600);
+        assert_eq!(Balances::free_balance(4), 200);
+        assert_eq!(Dummy::minted_in_current_window(0), 400);
+        assert_pots_balanced();
+
+        // The window rolls: the mints of block 11 count until block 20.
+        System::set_block_number(15);
+        assert_ok!(Dummy::mint(RawOrigin::Root.into(), 0, deposit(5), 2, 400));
+        assert_eq!(Balances::free_balance(2), 600);
+        assert_eq!(
+            Dummy::scheduled_mints(deposit(5)).map(|mint| mint.execute_at),
+            Some(21)
+        );
+        System::set_block_number(21);
+        assert_ok!(Dummy::mint(RawOrigin::Root.into(), 0, deposit(6), 3, 500));
+        assert_eq!(Balances::free_balance(3), 800);
+        assert_eq!(
+            Dummy::account_minted(0, 3),
+            MintWindow {
+                buckets: vec![(21, 500)]
+            }
+        );
+
+        // A mint above a cap can never be executed.
+        assert_noop!(
+            Dummy::mint(RawOrigin::Root.into(), 0, deposit(7), 2, 501),
+            Error::<Test>::MintAboveCap
+        );
+    });
+}
+
+#[test]
+fn over_cap_mints_are_spread_over_the_periods() {
+    use frame_system::RawOrigin;
+    new_test_ext(1).execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(Dummy::set_mint_limits(
+            RawOrigin::Root.into(),
+            0,
+            MintLimits {
+                period: 10,
+                per_account: None,
+                global: Some(500),
+                delay_threshold: None,
+                delay: 1,
+            }
+        ));
+        for n in 1..=5 {
+            assert_ok!(Dummy::mint(RawOrigin::Root.into(), 0, deposit(n), n, 300));
+        }
+        let minted = || (1..=5).filter(|n| Balances::free_balance(n) > 0).count();
+        assert_eq!(minted(), 1);
+
+        // The scheduled mints all fall due together, but only one fits in each period.
+        for n in 2..=40 {
+            run_to_block(n);
+            assert!(Dummy::minted_in_current_window(0) <= 500);
+            assert_eq!(minted() as u64, 1 + (n - 1) / 10);
+        }
+        run_to_block(41);
+        assert_eq!(minted(), 5);
+        assert!(ScheduledMints::<Test>::iter().next().is_none());
+        assert_pots_balanced();
+    });
+}
+
+#[test]
+fn scheduled_mint_above_cap_fails() {
+    use frame_system::RawOrigin;
+    new_test_ext(1).execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(Dummy::set_mint_limits(
+            RawOrigin::Root.into(),
+            0,
+            MintLimits {
+                delay_threshold: Some(100),
+                delay: 2,
+                ..Default::default()
+            }
+        ));
+        assert_ok!(Dummy::mint(RawOrigin::Root.into(), 0, deposit(1), 2, 300));
+        assert_ok!(Dummy::set_mint_limits(
+            RawOrigin::Root.into(),
+            0,
+            MintLimits {
+                period: 10,
+                global: Some(200),
+                ..Default::default()
+            }
+        ));
+
+        run_to_block(2);
+        run_to_block(3);
+        assert_eq!(Balances::free_balance(2), 0);
+        assert!(!Dummy::is_processed(&deposit(1)));
+        assert_eq!(
+            dummy_events().last(),
+            Some(&RawEvent::ScheduledMintFailed(0, deposit(1), 2, 300))
+        );
+    });
+}
+
+#[test]
+fn delayed_mint_to_blocked_recipient_fails() {
+    use frame_system::RawOrigin;
+    new_test_ext(1).execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(Dummy::set_mint_limits(
+            RawOrigin::Root.into(),
+            0,
+            MintLimits {
+                delay_threshold: Some(100),
+                delay: 2,
+                ..Default::default()
+            }
+        ));
+        assert_ok!(Dummy::mint(RawOrigin::Root.into(), 0, deposit(1), 2, 300));
+        mock::TestRecipients::block(2);
+        assert_noop!(
+            Dummy::mint(RawOrigin::Root.into(), 0, deposit(2), 2, 50),
+            Error::<Test>::RecipientNotAllowed
+        );
+
+        run_to_block(3);
+        assert_eq!(Balances::free_balance(2), 0);
+        assert!(!Dummy::is_processed(&deposit(1)));
+        assert_pots_balanced();
+        assert_eq!(
+            dummy_events().last(),
+            Some(&RawEvent::ScheduledMintFailed(0, deposit(1), 2, 300))
+        );
+    });
+}
+
+#[test]
+fn failed_delayed_mint_releases_deposit() {
+    use frame_system::RawOrigin;
+    new_test_ext(1).execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(Dummy::set_mint_limits(
+            RawOrigin::Root.into(),
+            0,
+            MintLimits {
+                delay_threshold: Some(1_000),
+                delay: 2,
+                ..Default::default()
+            }
+        ));
+
+        assert_ok!(Dummy::mint(
+            RawOrigin::Root.into(),
+            0,
+            deposit(1),
+            2,
+            2_000_000
+        ));
+        run_to_block(3);
+        assert_eq!(Balances::free_balance(2), 0);
+        assert!(!Dummy::is_processed(&deposit(1)));
+        assert_pots_balanced();
+        assert_eq!(
+            dummy_events().last(),
+            Some(&RawEvent::ScheduledMintFailed(0, deposit(1), 2, 2_000_000))
+        );
+    });
+}
+
+#[test]
+fn pause_halts_bridge() {
+    use frame_system::RawOrigin;
+    new_test_ext(1).execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(Dummy::mint(RawOrigin::Root.into(), 0, deposit(1), 2, 400));
+
+        assert_noop!(
+            Dummy::pause(RawOrigin::Signed(2).into()),
+            sp_runtime::traits::BadOrigin
+        );
+        assert_ok!(Dummy::pause(RawOrigin::Signed(100).into()));
+        assert!(Dummy::is_paused());
+        assert_eq!(dummy_events().last(), Some(&RawEvent::BridgePaused));
+
+        assert_noop!(
+            Dummy::mint(RawOrigin::Root.into(), 0, deposit(2), 2, 400),
+            Error::<Test>::BridgeIsPaused
+        );
+        assert_noop!(
+            Dummy::burn(RawOrigin::Signed(2).into(), 0, H160::repeat_byte(0x11), 100),
+            Error::<Test>::BridgeIsPaused
+        );
+        assert_noop!(
+            Dummy::vote_mint(RawOrigin::Signed(10).into(), 0, deposit(2), 2, 400),
+            Error::<Test>::BridgeIsPaused
+        );
+
+        assert_ok!(Dummy::unpause(RawOrigin::Signed(100).into()));
+        assert!(!Dummy::is_paused());
+        assert_ok!(Dummy::mint(RawOrigin::Root.into(), 0, deposit(2), 2, 400));
+        assert_eq!(Balances::free_balance(2), 800);
+    });
+}
+
+#[test]
+fn delayed_mints_wait_while_paused() {
+    use frame_system::RawOrigin;
+    new_test_ext(1).execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(Dummy::set_mint_limits(
+            RawOrigin::Root.into(),
+            0,
+            MintLimits {
+                delay_threshold: Some(100),
+                delay: 2,
+                ..Default::default()
+            }
+        ));
+        assert_ok!(Dummy::mint(RawOrigin::Root.into(), 0, deposit(1), 2, 200));
+        assert_ok!(Dummy::mint(RawOrigin::Root.into(), 0, deposit(2), 3, 200));
+        assert_ok!(Dummy::pause(RawOrigin::Signed(100).into()));
+
+        // The mints are held, not rescheduled block after block.
+        run_to_block(3);
+        assert_eq!(Balances::free_balance(2), 0);
+        assert_eq!(Dummy::held_mints(), vec![deposit(1), deposit(2)]);
+        for n in 4..=6 {
+            Dummy::on_initialize(n);
+            run_to_block(n);
+            assert!(pallet_scheduler::Agenda::<Test>::get(n).is_empty());
+        }
+        assert_ok!(Dummy::cancel_mint(
+            RawOrigin::Signed(101).into(),
+            deposit(2)
+        ));
+        assert_eq!(Dummy::held_mints(), vec![deposit(1)]);
+
+        // They are rescheduled once the bridge resumes.
+        assert_ok!(Dummy::unpause(RawOrigin::Signed(100).into()));
+        Dummy::on_initialize(7);
+        run_to_block(7);
+        assert!(Dummy::held_mints().is_empty());
+        assert_eq!(Dummy::scheduled_mints(deposit(1)).unwrap().execute_at, 8);
+        run_to_block(8);
+        assert_eq!(Balances::free_balance(2), 200);
+        assert_eq!(Balances::free_balance(3), 0);
+    });
+}
+
+#[test]
+fn asset_registry_is_managed_by_admin() {
+    use frame_system::RawOrigin;
+    new_test_ext(1).execute_with(|| {
+        assert_eq!(Dummy::bridged_assets(1), Some(H160::repeat_byte(0xaa)));
+        assert_eq!(Dummy::asset_by_token(H160::repeat_byte(0xaa)), Some(1));
+
+        assert_noop!(
+            Dummy::register_asset(RawOrigin::Signed(2).into(), 2, H160::repeat_byte(0xbb)),
+            sp_runtime::traits::BadOrigin
+        );
+        assert_noop!(
+            Dummy::register_asset(RawOrigin::Root.into(), 1, H160::repeat_byte(0xbb)),
+            Error::<Test>::AssetAlreadyRegistered
+        );
+        assert_noop!(
+            Dummy::register_asset(RawOrigin::Root.into(), 2, H160::repeat_byte(0xaa)),
+            Error::<Test>::TokenAlreadyRegistered
+        );
+        assert_ok!(Dummy::register_asset(
+            RawOrigin::Root.into(),
+            2,
+            H160::repeat_byte(0xbb)
+        ));
+        assert_eq!(Dummy::asset_by_token(H160::repeat_byte(0xbb)), Some(2));
+
+        assert_ok!(Dummy::unregister_asset(RawOrigin::Root.into(), 2));
+        assert_eq!(Dummy::bridged_assets(2), None);
+        assert_eq!(Dummy::asset_by_token(H160::repeat_byte(0xbb)), None);
+        assert_noop!(
+            Dummy::unregister_asset(RawOrigin::Root.into(), 2),
+            Error::<Test>::UnknownAsset
+        );
+        assert_noop!(
+            Dummy::mint(RawOrigin::Root.into(), 2, deposit(1), 2, 400),
+            Error::<Test>::UnknownAsset
+        );
+    });
+}
+
+#[test]
+fn bridged_asset_uses_its_own_pot() {
+    use frame_system::RawOrigin;
+    new_test_ext(1).execute_with(|| {
+        let pot = Dummy::pot_account(1);
+        assert_ne!(pot, Dummy::account_id());
+        assert_eq!(Dummy::pot_account(0), Dummy::account_id());
+        TestAssets::set_balance(1, pot, 5_000);
+        TotalLocked::<Test>::insert(1, 5_000);
+
+        assert_ok!(Dummy::mint(RawOrigin::Root.into(), 1, deposit(1), 2, 400));
+        assert_eq!(Dummy::free_balance(1, &2), 400);
+        assert_eq!(Dummy::free_balance(1, &pot), 4_600);
+        assert_eq!(Balances::free_balance(2), 0);
+        assert_eq!(Balances::free_balance(Dummy::account_id()), 1_000_000);
+
+        assert_ok!(Dummy::burn(
+            RawOrigin::Signed(2).into(),
+            1,
+            H160::repeat_byte(0x11),
+            150
+        ));
+        assert_eq!(Dummy::free_balance(1, &2), 250);
+        assert_eq!(Dummy::free_balance(1, &pot), 4_750);
+
+        // Votes for the same deposit must agree on the asset.
+        assert_ok!(Dummy::vote_mint(
+            RawOrigin::Signed(10).into(),
+            1,
+            deposit(2),
+            3,
+            100
+        ));
+        assert_noop!(
+            Dummy::vote_mint(RawOrigin::Signed(11).into(), 0, deposit(2), 3, 100),
+            Error::<Test>::ProposalMismatch
+        );
+        assert_ok!(Dummy::vote_mint(
+            RawOrigin::Signed(11).into(),
+            1,
+            deposit(2),
+            3,
+            100
+        ));
+        assert_eq!(Dummy::free_balance(1, &3), 100);
+        assert_pots_balanced();
+    });
+}
+
+#[test]
+fn fees_are_taken_out_of_transfers() {
+    use frame_system::RawOrigin;
+    new_test_ext(1).execute_with(|| {
+        System::set_block_number(1);
+        let mint_fee = BridgeFee::Proportional(Permill::from_percent(10));
+        assert_noop!(
+            Dummy::set_fees(
+                RawOrigin::Signed(2).into(),
+                0,
+                mint_fee,
+                BridgeFee::Flat(50)
+            ),
+            sp_runtime::traits::BadOrigin
+        );
+        assert_ok!(Dummy::set_fees(
+            RawOrigin::Root.into(),
+            0,
+            mint_fee,
+            BridgeFee::Flat(50)
+        ));
+
+        assert_ok!(Dummy::mint(RawOrigin::Root.into(), 0, deposit(1), 2, 400));
+        assert_eq!(Balances::free_balance(2), 360);
+        assert_eq!(Balances::free_balance(mock::FEE_COLLECTOR), 40);
+        assert_eq!(Balances::free_balance(Dummy::account_id()), 1_000_000 - 400);
+
+        assert_noop!(
+            Dummy::burn(RawOrigin::Signed(2).into(), 0, H160::repeat_byte(0x11), 50),
+            Error::<Test>::AmountBelowFee
+        );
+        assert_ok!(Dummy::burn(
+            RawOrigin::Signed(2).into(),
+            0,
+            H160::repeat_byte(0x11),
+            250
+        ));
+        assert_eq!(Balances::free_balance(2), 110);
+        assert_eq!(Balances::free_balance(mock::FEE_COLLECTOR), 90);
+        assert_eq!(Balances::free_balance(Dummy::account_id()), 1_000_000 - 200);
+        assert_eq!(Balances::total_issuance(), 1_000_000);
+        assert_pots_balanced();
+
+        assert_eq!(
+            dummy_events(),
+            vec![
+                RawEvent::FeesChanged(0, mint_fee, BridgeFee::Flat(50)),
+                RawEvent::FeeCharged(0, 2, 40),
+                RawEvent::Minted(0, deposit(1), 2, 360),
+                RawEvent::FeeCharged(0, 2, 50),
+                RawEvent::Burned(0, 2, H160::repeat_byte(0x11), 200, 1),
+            ]
+        );
+
+        // Other assets are charged in the asset.
+        TestAssets::set_balance(1, Dummy::pot_account(1), 1_000);
+        assert_ok!(Dummy::mint(RawOrigin::Root.into(), 1, deposit(2), 3, 400));
+        assert_eq!(Dummy::free_balance(1, &3), 400);
+        assert_ok!(Dummy::set_fees(
+            RawOrigin::Root.into(),
+            1,
+            BridgeFee::Flat(30),
+            BridgeFee::Free
+        ));
+        assert_ok!(Dummy::mint(RawOrigin::Root.into(), 1, deposit(3), 3, 400));
+        assert_eq!(Dummy::free_balance(1, &3), 770);
+        assert_eq!(Dummy::free_balance(1, &mock::FEE_COLLECTOR), 30);
+        assert_eq!(Balances::free_balance(mock::FEE_COLLECTOR), 90);
+
+        // A mint must cover its fee.
+        assert_noop!(
+            Dummy::mint(RawOrigin::Root.into(), 1, deposit(4), 3, 30),
+            Error::<Test>::AmountBelowFee
+        );
+    });
+}
+
+#[test]
+fn proven_deposit_is_minted_by_anyone() {
+    use frame_system::RawOrigin;
+    new_test_ext(1).execute_with(|| {
+        let proof = |id, token, amount| ProvenDeposit {
+            deposit_id: deposit(id),
+            token,
+            account: 2,
+            amount,
+        };
+
+        assert_noop!(
+            Dummy::mint_with_proof(
+                RawOrigin::Signed(5).into(),
+                proof(0, H160::repeat_byte(0xcc), 400)
+            ),
+            DispatchError::Other("InvalidProof")
+        );
+        assert_noop!(
+            Dummy::mint_with_proof(
+                RawOrigin::Signed(5).into(),
+                proof(1, H160::repeat_byte(0xbb), 400)
+            ),
+            Error::<Test>::UnknownAsset
+        );
+        assert_ok!(Dummy::mint_with_proof(
+            RawOrigin::Signed(5).into(),
+            proof(1, H160::repeat_byte(0xcc), 400)
+        ));
+        assert_eq!(Balances::free_balance(2), 400);
+        assert_noop!(
+            Dummy::mint_with_proof(
+                RawOrigin::Signed(5).into(),
+                proof(1, H160::repeat_byte(0xcc), 400)
+            ),
+            Error::<Test>::DepositAlreadyProcessed
+        );
+
+        TestAssets::set_balance(1, Dummy::pot_account(1), 1_000);
+        assert_ok!(Dummy::mint_with_proof(
+            RawOrigin::Signed(5).into(),
+            proof(2, H160::repeat_byte(0xaa), 300)
+        ));
+        assert_eq!(Dummy::free_balance(1, &2), 300);
+    });
+}
+
+#[test]
+fn deposits_are_minted_once_whether_voted_or_proven() {
+    use frame_system::RawOrigin;
+    new_test_ext(1).execute_with(|| {
+        let proof = |deposit_id| ProvenDeposit {
+            deposit_id,
+            token: H160::repeat_byte(0xcc),
+            account: 2,
+            amount: 100,
+        };
+        let voted = deposit_id_of(H256::repeat_byte(1), 0);
+        let proven = deposit_id_of(H256::repeat_byte(1), 1);
+
+        for relayer in &[10, 11] {
+            assert_ok!(Dummy::vote_mint(
+                RawOrigin::Signed(*relayer).into(),
+                0,
+                voted,
+                2,
+                100
+            ));
+        }
+        assert_noop!(
+            Dummy::mint_with_proof(RawOrigin::Signed(5).into(), proof(voted)),
+            Error::<Test>::DepositAlreadyProcessed
+        );
+
+        assert_ok!(Dummy::mint_with_proof(
+            RawOrigin::Signed(5).into(),
+            proof(proven)
+        ));
+        assert_noop!(
+            Dummy::vote_mint(RawOrigin::Signed(10).into(), 0, proven, 2, 100),
+            Error::<Test>::DepositAlreadyProcessed
+        );
+        assert_eq!(Balances::free_balance(2), 200);
+    });
+}
+
+#[test]
+fn offchain_worker_votes_for_pending_deposits() {
+    use codec::Decode;
+    use frame_support::traits::OffchainWorker;
+    use frame_system::RawOrigin;
+    use mock::{Extrinsic, OuterCall};
+    use sp_core::offchain::{testing, OffchainExt, TransactionPoolExt};
+    use sp_runtime::testing::UintAuthorityId;
+
+    const ENDPOINT: &str = "http://localhost:8545/deposits";
+
+    let (offchain, offchain_state) = testing::TestOffchainExt::new();
+    let (pool, pool_state) = testing::TestTransactionPoolExt::new();
+    let mut t = new_test_ext(1);
+    t.register_extension(OffchainExt::new(offchain));
+    t.register_extension(TransactionPoolExt::new(pool));
+
+    let pending = |asset_id, n, account, amount| PendingDeposit {
+        asset_id,
+        tx_hash: H256::from_low_u64_be(n),
+        log_index: 0,
+        account,
+        amount,
+    };
+    let deposit = |n| deposit_id_of(H256::from_low_u64_be(n), 0);
+    let deposits: Vec<PendingDeposit<u32, u64, u64>> = vec![
+        pending(0, 1, 2, 400),
+        // Already minted.
+        pending(0, 2, 3, 100),
+        // Already voted for by the local relayer.
+        pending(0, 3, 4, 50),
+        // Not bridged.
+        pending(7, 4, 5, 10),
+    ];
+    let expect_request = || {
+        offchain_state
+            .write()
+            .expect_request(testing::PendingRequest {
+                method: "GET".into(),
+                uri: ENDPOINT.into(),
+                response: Some(deposits.encode()),
+                sent: true,
+                ..Default::default()
+            })
+    };
+
+    t.execute_with(|| {
+        assert_ok!(Dummy::mint(RawOrigin::Root.into(), 0, deposit(2), 3, 100));
+        assert_ok!(Dummy::vote_mint(
+            RawOrigin::Signed(10).into(),
+            0,
+            deposit(3),
+            4,
+            50
+        ));
+
+        // Nothing happens until the node holds a relayer key and knows the endpoint.
+        UintAuthorityId::set_all_keys(vec![1]);
+        Dummy::offchain_worker(1);
+        UintAuthorityId::set_all_keys(vec![1, 10]);
+        Dummy::offchain_worker(1);
+        assert!(pool_state.read().transactions.is_empty());
+
+        StorageValueRef::persistent(ENDPOINT_KEY).set(&ENDPOINT.as_bytes().to_vec());
+        expect_request();
+        Dummy::offchain_worker(1);
+
+        let tx = pool_state.write().transactions.pop().unwrap();
+        assert!(pool_state.read().transactions.is_empty());
+        let tx = Extrinsic::decode(&mut &*tx).unwrap();
+        assert_eq!(tx.signature, Some((10, ())));
+        assert_eq!(
+            tx.call,
+            OuterCall::dummy(crate::Call::vote_mint(0, deposit(1), 2, 400))
+        );
+
+        // The vote is submitted again only if it did not land in time.
+        expect_request();
+        Dummy::offchain_worker(10);
+        assert!(pool_state.read().transactions.is_empty());
+        expect_request();
+        Dummy::offchain_worker(11);
+        assert_eq!(pool_state.read().transactions.len(), 1);
+    });
+}
+
+#[test]
+fn bridge_state_is_reported() {
+    use frame_system::RawOrigin;
+    new_test_ext(1).execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(Dummy::set_mint_limits(
+            RawOrigin::Root.into(),
+            0,
+            MintLimits {
+                period: 10,
+                per_account: None,
+                global: Some(800),
+                delay_threshold: None,
+                delay: 3,
+            }
+        ));
+        assert_ok!(Dummy::mint(RawOrigin::Root.into(), 0, deposit(1), 2, 400));
+        assert_eq!(Dummy::minted_in_current_window(0), 400);
+        assert_ok!(Dummy::vote_mint(
+            RawOrigin::Signed(10).into(),
+            0,
+            deposit(2),
+            3,
+            100
+        ));
+        assert_eq!(
+            Dummy::pending_proposals(),
+            vec![(
+                deposit(2),
+                MintProposal {
+                    asset_id: 0,
+                    account: 3,
+                    amount: 100,
+                    votes: vec![10],
+                    expiry: 11,
+                }
+            )]
+        );
+
+        System::set_block_number(12);
+        assert_eq!(Dummy::minted_in_current_window(0), 0);
+        assert!(Dummy::pending_proposals().is_empty());
+    });
+}
+
+#[test]
+fn pot_discrepancy_raises_alarm() {
+    use frame_system::RawOrigin;
+    new_test_ext(1).execute_with(|| {
+        System::set_block_number(1);
+        let pot = Dummy::account_id();
+        assert_ok!(Dummy::mint(RawOrigin::Root.into(), 0, deposit(1), 2, 400));
+        Dummy::on_finalize(1);
+        assert_eq!(
+            dummy_events().last(),
+            Some(&RawEvent::Minted(0, deposit(1), 2, 400))
+        );
+
+        // Something slashed the pot.
+        Balances::make_free_balance_be(&pot, 900_000);
+        Dummy::on_finalize(1);
+        assert_eq!(Dummy::pot_discrepancy(0), Some((999_600, 900_000)));
+        assert_eq!(
+            dummy_events().last(),
+            Some(&RawEvent::PotAccountingMismatch(0, 999_600, 900_000))
+        );
+
+        // The same discrepancy is reported once.
+        let reported = dummy_events().len();
+        Dummy::on_finalize(2);
+        assert_eq!(dummy_events().len(), reported);
+
+        assert_ok!(Dummy::mint(RawOrigin::Root.into(), 0, deposit(2), 2, 100));
+        Dummy::on_finalize(2);
+        assert_eq!(
+            dummy_events().last(),
+            Some(&RawEvent::PotAccountingMismatch(0, 999_500, 899_900))
+        );
+
+        Balances::make_free_balance_be(&pot, 999_500);
+        Dummy::on_finalize(3);
+        assert_eq!(
+            dummy_events().last(),
+            Some(&RawEvent::PotAccountingRestored(0))
+        );
+        assert_eq!(Dummy::reported_discrepancy(0), None);
+        assert_pots_balanced();
+
+        // The accounting of a new asset starts from the balance of its pot.
+        TestAssets::set_balance(2, Dummy::pot_account(2), 700);
+        assert_ok!(Dummy::register_asset(
+            RawOrigin::Root.into(),
+            2,
+            H160::repeat_byte(0xdd)
+        ));
+        assert_eq!(Dummy::total_locked(2), 700);
+        assert_eq!(Dummy::bridged_asset_count(), 3);
+        assert_eq!(Dummy::pot_discrepancy(2), None);
+    });
+}
+
+#[test]
+fn scheduled_mint_can_be_cancelled() {
+    use frame_system::RawOrigin;
+    new_test_ext(1).execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(Dummy::set_mint_limits(
+            RawOrigin::Root.into(),
+            0,
+            MintLimits {
+                delay_threshold: Some(1_000),
+                delay: 5,
+                ..Default::default()
+            }
+        ));
+        assert_ok!(Dummy::mint(RawOrigin::Root.into(), 0, deposit(1), 2, 2_000));
+        let scheduled_at = |n| {
+            pallet_scheduler::Agenda::<Test>::get(n)
+                .iter()
+                .filter(|task| task.is_some())
+                .count()
+        };
+        assert_eq!(scheduled_at(6), 1);
+
+        assert_noop!(
+            Dummy::execute_scheduled_mint(RawOrigin::Signed(2).into(), deposit(1)),
+            sp_runtime::traits::BadOrigin
+        );
+        assert_noop!(
+            Dummy::execute_scheduled_mint(
+                RawOrigin::Signed(Dummy::account_id()).into(),
+                deposit(1)
+            ),
+            sp_runtime::traits::BadOrigin
+        );
+        assert_noop!(
+            Dummy::execute_scheduled_mint(RawOrigin::Root.into(), deposit(1)),
+            Error::<Test>::MintNotDue
+        );
+        assert_noop!(
+            Dummy::cancel_mint(RawOrigin::Signed(100).into(), deposit(1)),
+            sp_runtime::traits::BadOrigin
+        );
+        assert_noop!(
+            Dummy::cancel_mint(RawOrigin::Signed(101).into(), deposit(2)),
+            Error::<Test>::NoScheduledMint
+        );
+        assert_ok!(Dummy::cancel_mint(
+            RawOrigin::Signed(101).into(),
+            deposit(1)
+        ));
+        assert_eq!(
+            dummy_events().last(),
+            Some(&RawEvent::MintCancelled(deposit(1)))
+        );
+        assert!(Dummy::scheduled_mints(deposit(1)).is_none());
+        // The veto removes the task from the scheduler as well.
+        assert_eq!(scheduled_at(6), 0);
+
+        run_to_block(6);
+        assert_eq!(Balances::free_balance(2), 0);
+        assert_noop!(
+            Dummy::cancel_mint(RawOrigin::Signed(101).into(), deposit(1)),
+            Error::<Test>::NoScheduledMint
+        );
+        // A vetoed deposit cannot be minted again.
+        assert_noop!(
+            Dummy::mint(RawOrigin::Root.into(), 0, deposit(1), 2, 2_000),
+            Error::<Test>::DepositAlreadyProcessed
+        );
+        assert_pots_balanced();
+    });
+}
+
+#[test]
+fn transfer_history_is_kept_and_pruned() {
+    use frame_system::RawOrigin;
+    new_test_ext(1).execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(Dummy::mint(RawOrigin::Root.into(), 0, deposit(1), 2, 400));
+        System::set_block_number(2);
+        assert_ok!(Dummy::burn(
+            RawOrigin::Signed(2).into(),
+            0,
+            H160::repeat_byte(0x11),
+            100
+        ));
+
+        assert_eq!(
+            Dummy::transfers(0),
+            Some(TransferRecord {
+                direction: TransferDirection::Inbound,
+                asset_id: 0,
+                external_id: Some(deposit(1)),
+                recipient: None,
+                account: 2,
+                amount: 400,
+                block: 1,
+            })
+        );
+        assert_eq!(
+            Dummy::transfers(1),
+            Some(TransferRecord {
+                direction: TransferDirection::Outbound,
+                asset_id: 0,
+                external_id: None,
+                recipient: Some(H160::repeat_byte(0x11)),
+                account: 2,
+                amount: 100,
+                block: 2,
+            })
+        );
+        assert_eq!(Dummy::account_transfers(2), vec![0, 1]);
+
+        // Relayers confirm the releases of outbound transfers.
+        let tx_hash = H256::repeat_byte(0x22);
+        assert_noop!(
+            Dummy::confirm_release(RawOrigin::Signed(2).into(), 1, tx_hash),
+            Error::<Test>::NotRelayer
+        );
+        assert_noop!(
+            Dummy::confirm_release(RawOrigin::Signed(10).into(), 0, tx_hash),
+            Error::<Test>::NotAwaitingRelease
+        );
+        assert_noop!(
+            Dummy::confirm_release(RawOrigin::Signed(10).into(), 5, tx_hash),
+            Error::<Test>::UnknownTransfer
+        );
+        assert_ok!(Dummy::confirm_release(
+            RawOrigin::Signed(10).into(),
+            1,
+            tx_hash
+        ));
+        assert_eq!(Dummy::transfers(1).unwrap().external_id, Some(tx_hash));
+        assert_noop!(
+            Dummy::confirm_release(RawOrigin::Signed(11).into(), 1, tx_hash),
+            Error::<Test>::NotAwaitingRelease
+        );
+
+        // Only the latest transfers of an account are indexed.
+        System::set_block_number(3);
+        assert_ok!(Dummy::mint(RawOrigin::Root.into(), 0, deposit(2), 2, 10));
+        assert_ok!(Dummy::mint(RawOrigin::Root.into(), 0, deposit(3), 2, 20));
+        assert_ok!(Dummy::mint(RawOrigin::Root.into(), 0, deposit(4), 3, 30));
+        assert_eq!(Dummy::account_transfers(2), vec![1, 2, 3]);
+        assert_eq!(Dummy::account_transfers(3), vec![4]);
+        assert!(Dummy::transfers(0).is_some());
+
+        Dummy::on_initialize(21);
+        assert_eq!(Dummy::transfers(0), None);
+        assert_eq!(Dummy::account_transfers(2), vec![1, 2, 3]);
+
+        Dummy::on_initialize(22);
+        assert_eq!(Dummy::transfers(1), None);
+        assert_eq!(
+            Dummy::account_history(&2)
+                .into_iter()
+                .map(|(index, record)| (index, record.amount))
+                .collect::<Vec<_>>(),
+            vec![(2, 10), (3, 20)]
+        );
+        assert_eq!(Dummy::oldest_transfer_index(), 2);
+        assert_eq!(Dummy::account_history(&3).len(), 1);
+
+        // The records are pruned a bounded number at a time.
+        for i in 0..MAX_PRUNED_TRANSFERS_PER_BLOCK + 10 {
+            Dummy::record_transfer(TransferDirection::Inbound, 0, None, None, &3, i.into());
+        }
+        Dummy::on_initialize(40);
+        assert_eq!(
+            Dummy::oldest_transfer_index(),
+            2 + MAX_PRUNED_TRANSFERS_PER_BLOCK as u64
+        );
+        Dummy::on_initialize(41);
+        assert_eq!(Dummy::oldest_transfer_index(), Dummy::next_transfer_index());
+        assert!(Dummy::account_transfers(3).is_empty());
+    });
+}
+
+#[test]
+fn pots_are_refilled_and_withdrawn_by_governance() {
+    use frame_system::RawOrigin;
+    use mock::TREASURY;
+    new_test_ext(1).execute_with(|| {
+        System::set_block_number(1);
+        let pot = Dummy::account_id();
+        // Tokens paid to the pot directly are not locked.
+        Balances::make_free_balance_be(&pot, 1_005_000);
+        assert_eq!(Dummy::pot_excess(0), 5_000);
+
+        assert_noop!(
+            Dummy::withdraw(RawOrigin::Signed(2).into(), 0, 2, 2_000),
+            sp_runtime::traits::BadOrigin
+        );
+        assert_noop!(
+            Dummy::withdraw(RawOrigin::Root.into(), 2, 7, 2_000),
+            Error::<Test>::UnknownAsset
+        );
+        assert_noop!(
+            Dummy::withdraw(RawOrigin::Root.into(), 0, 7, 5_001),
+            Error::<Test>::AmountAboveExcess
+        );
+        assert_ok!(Dummy::withdraw(RawOrigin::Root.into(), 0, 7, 2_000));
+        assert_eq!(Balances::free_balance(7), 2_000);
+        assert_eq!(Balances::free_balance(pot), 1_003_000);
+
+        // Refills are paid by the treasury.
+        Balances::make_free_balance_be(&TREASURY, 10_000);
+        assert_noop!(
+            Dummy::refill(RawOrigin::Signed(2).into(), 0, 3_000),
+            sp_runtime::traits::BadOrigin
+        );
+        assert_noop!(
+            Dummy::refill(RawOrigin::Root.into(), 2, 3_000),
+            Error::<Test>::UnknownAsset
+        );
+        assert_noop!(
+            Dummy::refill(RawOrigin::Root.into(), 0, 0),
+            Error::<Test>::ZeroAmount
+        );
+        assert_noop!(
+            Dummy::refill(RawOrigin::Root.into(), 0, 10_000),
+            pallet_balances::Error::<Test, _>::KeepAlive
+        );
+        assert_ok!(Dummy::refill(RawOrigin::Root.into(), 0, 3_000));
+        assert_eq!(Balances::free_balance(TREASURY), 7_000);
+        assert_eq!(Balances::free_balance(pot), 1_006_000);
+        assert_eq!(Dummy::total_locked(0), 1_003_000);
+        assert_eq!(Dummy::pot_excess(0), 3_000);
+        // What backs the locked tokens cannot be withdrawn.
+        assert_noop!(
+            Dummy::withdraw(RawOrigin::Root.into(), 0, 7, 3_001),
+            Error::<Test>::AmountAboveExcess
+        );
+
+        TestAssets::set_balance(1, TREASURY, 600);
+        assert_ok!(Dummy::refill(RawOrigin::Root.into(), 1, 600));
+        assert_eq!(TestAssets::free_balance(1, &TREASURY), 0);
+        assert_eq!(TestAssets::free_balance(1, &Dummy::pot_account(1)), 600);
+        assert_noop!(
+            Dummy::withdraw(RawOrigin::Root.into(), 1, 7, 1),
+            Error::<Test>::AmountAboveExcess
+        );
+
+        assert_ok!(Dummy::withdraw(RawOrigin::Root.into(), 0, 7, 3_000));
+
+        assert_eq!(
+            dummy_events(),
+            vec![
+                RawEvent::PotWithdrawn(0, 7, 2_000),
+                RawEvent::PotRefilled(0, 3_000),
+                RawEvent::PotRefilled(1, 600),
+                RawEvent::PotWithdrawn(0, 7, 3_000),
+            ]
+        );
+        assert_pots_balanced();
+    });
+}
+
+#[test]
+fn relayer_votes_are_feeless_and_checked() {
+    use frame_support::weights::{DispatchInfo, GetDispatchInfo};
+    use frame_system::RawOrigin;
+    use mock::OuterCall;
+    use sp_runtime::transaction_validity::InvalidTransaction;
+    new_test_ext(1).execute_with(|| {
+        System::set_block_number(1);
+        let check = CheckRelayerVote::<Test>::new();
+        let info = DispatchInfo::default();
+        let vote: OuterCall = Call::vote_mint(0, deposit(1), 2, 400).into();
+        let vote_info = vote.get_dispatch_info();
+        assert_eq!(vote_info.pays_fee, Pays::No);
+
+        let valid = check.validate(&12, &vote, &info, 0).unwrap();
+        assert_eq!(valid.priority, mock::RelayerVotePriority::get());
+        assert_eq!(
+            check.validate(&2, &vote, &info, 0),
+            Err(InvalidTransaction::Custom(ValidityError::NotRelayer.into()).into())
+        );
+        let transfer: OuterCall = pallet_balances::Call::transfer(3, 10).into();
+        assert_eq!(
+            check.validate(&2, &transfer, &info, 0),
+            Ok(ValidTransaction::default())
+        );
+
+        // Failing votes are free as well, the extension keeps them out of blocks.
+        let failed = vote
+            .clone()
+            .dispatch(RawOrigin::Signed(2).into())
+            .unwrap_err();
+        assert_eq!(failed.error, Error::<Test>::NotRelayer.into());
+        assert_eq!(failed.post_info.pays_fee(&vote_info), Pays::No);
+        let post_info = vote.clone().dispatch(RawOrigin::Signed(12).into()).unwrap();
+        assert_eq!(post_info.pays_fee(&vote_info), Pays::No);
+        assert_eq!(
+            check.validate(&12, &vote, &info, 0),
+            Err(InvalidTransaction::Stale.into())
+        );
+        assert!(check.validate(&10, &vote, &info, 0).is_ok());
+
+        assert_ok!(Dummy::vote_mint(
+            RawOrigin::Signed(10).into(),
+            0,
+            deposit(1),
+            2,
+            400
+        ));
+        assert!(Dummy::is_processed(&deposit(1)));
+        assert_eq!(
+            check.validate(&11, &vote, &info, 0),
+            Err(InvalidTransaction::Stale.into())
+        );
+
+        let vote: OuterCall = Call::vote_mint(0, deposit(2), 2, 400).into();
+        assert_ok!(Dummy::pause(RawOrigin::Signed(100).into()));
+        assert_eq!(
+            check.validate(&11, &vote, &info, 0),
+            Err(InvalidTransaction::Custom(ValidityError::BridgePaused.into()).into())
+        );
     });
 }
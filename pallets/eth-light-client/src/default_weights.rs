@@ -0,0 +1,46 @@
+// This file is part of Curio.
+
+// Copyright (C) 2019-2020 Mixbytes.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Default weights of the light client. These are estimates, not benchmark results.
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::weights::{constants::RocksDbWeight as DbWeight, Weight};
+
+impl crate::WeightInfo for () {
+    fn import_header(d: u32) -> Weight {
+        (2_514_380_000 as Weight)
+            .saturating_add((3_208_000 as Weight).saturating_mul(d as Weight))
+            .saturating_add(DbWeight::get().reads(9 as Weight))
+            .saturating_add(DbWeight::get().reads((1 as Weight).saturating_mul(d as Weight)))
+            .saturating_add(DbWeight::get().writes(9 as Weight))
+    }
+    fn set_checkpoint(h: u32) -> Weight {
+        (27_913_000 as Weight)
+            .saturating_add(DbWeight::get().writes(6 as Weight))
+            .saturating_add(DbWeight::get().writes((2 as Weight).saturating_mul(h as Weight)))
+    }
+    fn set_dag_roots(r: u32) -> Weight {
+        (11_284_000 as Weight)
+            .saturating_add((1_930_000 as Weight).saturating_mul(r as Weight))
+            .saturating_add(DbWeight::get().writes((1 as Weight).saturating_mul(r as Weight)))
+    }
+    fn set_bridge_contract() -> Weight {
+        (12_560_000 as Weight).saturating_add(DbWeight::get().writes(1 as Weight))
+    }
+}
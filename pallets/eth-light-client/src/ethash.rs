@@ -0,0 +1,209 @@
+// This file is part of Curio project.
+
+// Copyright (C) 2021 Mixbytes.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Verification of Ethash seals and of the difficulty of Ethereum headers.
+//!
+//! The dataset of an epoch is too large to be built on chain. Instead, the elements a seal
+//! reads are given with their Merkle proofs against the root of the dataset of the epoch. The
+//! tree is built over the 128 byte elements of the dataset, padded with zero elements to a
+//! power of two. A leaf is the Keccak-256 hash of its element and a node the hash of its two
+//! children.
+
+use sp_core::{H256, H512, U256, U512};
+use sp_io::hashing::keccak_256;
+use sp_std::prelude::*;
+use tiny_keccak::{Hasher, Keccak};
+
+/// Number of blocks in an epoch, which share a dataset.
+pub const EPOCH_LENGTH: u64 = 30_000;
+/// Number of dataset elements read by a seal.
+pub const ACCESSES: usize = 64;
+/// Size of a dataset element.
+pub const MIX_BYTES: usize = 128;
+
+const MIX_WORDS: usize = MIX_BYTES / 4;
+const DATASET_BYTES_INIT: u64 = 1 << 30;
+const DATASET_BYTES_GROWTH: u64 = 1 << 23;
+const DIFFICULTY_BOUND_DIVISOR: u64 = 2048;
+const DURATION_LIMIT: u64 = 9;
+const MAX_DIFFICULTY_DROP: u64 = 99;
+const EXP_DIFFICULTY_PERIOD: u64 = 100_000;
+
+/// An element of the dataset: two of its 64 byte items.
+pub type DagElement = [H512; 2];
+
+fn keccak_512(data: &[u8]) -> [u8; 64] {
+    let mut keccak = Keccak::v512();
+    keccak.update(data);
+    let mut output = [0u8; 64];
+    keccak.finalize(&mut output);
+    output
+}
+
+fn fnv(a: u32, b: u32) -> u32 {
+    a.wrapping_mul(0x0100_0193) ^ b
+}
+
+fn words(bytes: &[u8]) -> impl Iterator<Item = u32> + '_ {
+    bytes
+        .chunks_exact(4)
+        .map(|w| u32::from_le_bytes([w[0], w[1], w[2], w[3]]))
+}
+
+fn is_prime(n: u64) -> bool {
+    if n % 2 == 0 {
+        return n == 2;
+    }
+    let mut divisor = 3;
+    while divisor * divisor <= n {
+        if n % divisor == 0 {
+            return false;
+        }
+        divisor += 2;
+    }
+    n > 1
+}
+
+/// Size in bytes of the dataset of `epoch`.
+pub fn dataset_size(epoch: u64) -> u64 {
+    let mut size = DATASET_BYTES_GROWTH
+        .saturating_mul(epoch)
+        .saturating_add(DATASET_BYTES_INIT - MIX_BYTES as u64);
+    while !is_prime(size / MIX_BYTES as u64) {
+        size -= 2 * MIX_BYTES as u64;
+    }
+    size
+}
+
+/// Depth of the Merkle tree over a dataset of `size` bytes.
+pub fn dag_depth(size: u64) -> u32 {
+    let elements = size / MIX_BYTES as u64;
+    64 - (elements - 1).leading_zeros()
+}
+
+/// The Keccak-256 hash of an element, a leaf of the Merkle tree of its dataset.
+pub fn hash_element(element: &DagElement) -> H256 {
+    let mut data = [0u8; MIX_BYTES];
+    data[..64].copy_from_slice(element[0].as_bytes());
+    data[64..].copy_from_slice(element[1].as_bytes());
+    H256(keccak_256(&data))
+}
+
+/// Whether `path`, the siblings of the nodes from the leaf up, proves `element` at `index` of
+/// the tree of `depth` with `root`.
+pub fn verify_element(
+    root: &H256,
+    depth: u32,
+    index: u64,
+    element: &DagElement,
+    path: &[H256],
+) -> bool {
+    if path.len() != depth as usize {
+        return false;
+    }
+    let mut node = hash_element(element);
+    for (level, sibling) in path.iter().enumerate() {
+        let mut data = [0u8; 64];
+        if (index >> level) & 1 == 0 {
+            data[..32].copy_from_slice(node.as_bytes());
+            data[32..].copy_from_slice(sibling.as_bytes());
+        } else {
+            data[..32].copy_from_slice(sibling.as_bytes());
+            data[32..].copy_from_slice(node.as_bytes());
+        }
+        node = H256(keccak_256(&data));
+    }
+    node == *root
+}
+
+/// Run Ethash over the hash of a header without its seal and the `nonce` of the seal, reading
+/// the dataset of `size` bytes with `lookup`. It is given the number of the access and the
+/// index of the element read.
+///
+/// Returns the mix hash the seal must hold and the result which must be within the target.
+pub fn hashimoto<E>(
+    seal_hash: &H256,
+    nonce: u64,
+    size: u64,
+    mut lookup: impl FnMut(usize, u64) -> Result<DagElement, E>,
+) -> Result<(H256, H256), E> {
+    let mut seed = [0u8; 40];
+    seed[..32].copy_from_slice(seal_hash.as_bytes());
+    seed[32..].copy_from_slice(&nonce.to_le_bytes());
+    let seed = keccak_512(&seed);
+
+    let seed_words = words(&seed).collect::<Vec<_>>();
+    let mut mix = [0u32; MIX_WORDS];
+    for (i, word) in mix.iter_mut().enumerate() {
+        *word = seed_words[i % seed_words.len()];
+    }
+    let elements = size / MIX_BYTES as u64;
+    for access in 0..ACCESSES {
+        let index = u64::from(fnv(access as u32 ^ seed_words[0], mix[access % MIX_WORDS]));
+        let element = lookup(access, index % elements)?;
+        let data = words(element[0].as_bytes()).chain(words(element[1].as_bytes()));
+        for (word, data) in mix.iter_mut().zip(data) {
+            *word = fnv(*word, data);
+        }
+    }
+
+    let mut mix_hash = H256::zero();
+    for (i, chunk) in mix.chunks_exact(4).enumerate() {
+        let word = fnv(fnv(fnv(chunk[0], chunk[1]), chunk[2]), chunk[3]);
+        mix_hash.as_bytes_mut()[i * 4..i * 4 + 4].copy_from_slice(&word.to_le_bytes());
+    }
+    let mut result = [0u8; 96];
+    result[..64].copy_from_slice(&seed);
+    result[64..].copy_from_slice(mix_hash.as_bytes());
+    Ok((mix_hash, H256(keccak_256(&result))))
+}
+
+/// Whether the `result` of a seal is within the target of `difficulty`, `2^256 / difficulty`.
+pub fn meets_target(result: &H256, difficulty: U256) -> bool {
+    U256::from_big_endian(result.as_bytes()).full_mul(difficulty) <= U512::one() << 256
+}
+
+/// The difficulty of a header at `number` with `timestamp`, following the rules of Byzantium
+/// and its successors, with the difficulty bomb delayed by `bomb_delay` blocks.
+///
+/// `timestamp` must be above `parent_timestamp`.
+pub fn difficulty(
+    parent_difficulty: U256,
+    parent_timestamp: u64,
+    parent_has_uncles: bool,
+    timestamp: u64,
+    number: u64,
+    minimum: U256,
+    bomb_delay: u64,
+) -> U256 {
+    let step = parent_difficulty / DIFFICULTY_BOUND_DIVISOR;
+    let uncles = if parent_has_uncles { 2 } else { 1 };
+    let slowness = (timestamp - parent_timestamp) / DURATION_LIMIT;
+    let difficulty = if slowness <= uncles {
+        parent_difficulty.saturating_add(step * (uncles - slowness))
+    } else {
+        parent_difficulty - step * (slowness - uncles).min(MAX_DIFFICULTY_DROP)
+    };
+    let mut difficulty = difficulty.max(minimum);
+
+    let period = number.saturating_sub(bomb_delay) / EXP_DIFFICULTY_PERIOD;
+    if period > 1 {
+        let bomb = U256::one() << (period - 2).min(255) as usize;
+        difficulty = difficulty.saturating_add(bomb);
+    }
+    difficulty
+}
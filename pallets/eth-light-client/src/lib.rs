@@ -0,0 +1,709 @@
+// This file is part of Curio project.
+
+// Copyright (C) 2021 Mixbytes.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Ethereum light client
+//!
+//! Tracks the headers of an Ethereum chain and proves the deposits made into the bridge
+//! contract, so that `pallet_dummy` can mint them without trusting anyone.
+//!
+//! Headers are imported by the `Importers` on top of a checkpoint set at genesis or by
+//! governance. Each header must link to an imported parent, carry the difficulty Ethereum
+//! requires after its parent and hold an Ethash seal meeting that difficulty, so importers
+//! cannot forge a chain without mining it. The seal is checked against the roots of the Ethash
+//! datasets, set by governance for each epoch (see [`ethash`]). The header with the most total
+//! difficulty is the best one, and its ancestor `ConfirmationDepth` blocks back is finalized.
+//! Only the last `HeadersToKeep` finalized headers are kept.
+//!
+//! A deposit is proven by the Merkle-Patricia proofs of its transaction and of the transaction
+//! receipt against the roots of a finalized header. The receipt must hold a
+//! `Deposit(address indexed token, bytes32 indexed recipient, uint256 amount)` event emitted
+//! by the bridge contract. The deposit is identified by the transaction hash and the index of
+//! the event, as `pallet_dummy::deposit_id_of` derives it for the relayers.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Decode, Encode};
+use frame_support::{
+    decl_error, decl_event, decl_module, decl_storage, ensure,
+    storage::StoragePrefixedMap,
+    traits::{Contains, EnsureOrigin, Get},
+    transactional,
+    weights::Weight,
+    RuntimeDebug,
+};
+use frame_system::ensure_signed;
+use pallet_dummy::{deposit_id_of, ExternalAddress, ProvenDeposit, VerifyDeposit};
+use sp_core::{H160, H256, U256};
+use sp_io::hashing::keccak_256;
+use sp_runtime::{DispatchError, DispatchResult};
+use sp_std::{convert::TryFrom, prelude::*};
+
+mod default_weights;
+pub mod ethash;
+#[cfg(test)]
+mod mock;
+pub mod rlp;
+#[cfg(test)]
+mod tests;
+pub mod trie;
+
+use ethash::DagElement;
+use rlp::{DecoderError, Rlp};
+
+/// Signature of the event emitted by the bridge contract for each deposit.
+pub const DEPOSIT_EVENT_SIGNATURE: &[u8] = b"Deposit(address,bytes32,uint256)";
+
+/// Maximum number of headers pruned by each `import_header` call.
+pub const MAX_PRUNED_PER_IMPORT: u32 = 2;
+
+/// Number of blocks beyond `ConfirmationDepth` a header may be above the finalized header. It
+/// bounds the headers finalized, and the ancestors walked, by each import.
+pub const MAX_UNFINALIZED_MARGIN: u32 = 16;
+
+/// Hash of the RLP encoding of an empty list, the uncles hash of a header without uncles.
+const EMPTY_UNCLES_HASH: [u8; 32] = [
+    0x1d, 0xcc, 0x4d, 0xe8, 0xde, 0xc7, 0x5d, 0x7a, 0xab, 0x85, 0xb5, 0x67, 0xb6, 0xcc, 0xd4, 0x1a,
+    0xd3, 0x12, 0x45, 0x1b, 0x94, 0x8a, 0x74, 0x13, 0xf0, 0xa1, 0x42, 0xfd, 0x40, 0xd4, 0x93, 0x47,
+];
+
+/// The fields of an Ethereum header the light client needs.
+#[derive(Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct EthereumHeader {
+    pub hash: H256,
+    pub parent_hash: H256,
+    pub uncles_hash: H256,
+    pub transactions_root: H256,
+    pub receipts_root: H256,
+    pub difficulty: U256,
+    pub number: u64,
+    pub timestamp: u64,
+    /// Hash of the header without its seal, the mix hash and the nonce.
+    pub seal_hash: H256,
+    pub mix_hash: H256,
+    pub nonce: u64,
+}
+
+impl EthereumHeader {
+    /// Decode an RLP encoded header.
+    pub fn decode_rlp(raw: &[u8]) -> Result<Self, DecoderError> {
+        let items = Rlp::new(raw)?.items()?;
+        if items.len() < 15 {
+            return Err(DecoderError);
+        }
+        let nonce = items[14].data()?;
+        if nonce.len() != 8 {
+            return Err(DecoderError);
+        }
+        // Fields added by later forks, such as the base fee, follow the seal and are sealed.
+        let unsealed = items
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| *i != 13 && *i != 14)
+            .map(|(_, item)| item.as_raw().to_vec())
+            .collect::<Vec<_>>();
+        Ok(EthereumHeader {
+            hash: H256(keccak_256(raw)),
+            parent_hash: items[0].as_h256()?,
+            uncles_hash: items[1].as_h256()?,
+            transactions_root: items[4].as_h256()?,
+            receipts_root: items[5].as_h256()?,
+            difficulty: items[7].as_u256()?,
+            number: items[8].as_u64()?,
+            timestamp: items[11].as_u64()?,
+            seal_hash: H256(keccak_256(&rlp::encode_list(&unsealed))),
+            mix_hash: items[13].as_h256()?,
+            nonce: nonce.iter().fold(0, |n, b| (n << 8) | *b as u64),
+        })
+    }
+}
+
+/// An imported header.
+#[derive(Encode, Decode, Clone, Default, PartialEq, Eq, RuntimeDebug)]
+pub struct StoredHeader {
+    pub number: u64,
+    pub parent_hash: H256,
+    pub transactions_root: H256,
+    pub receipts_root: H256,
+    pub timestamp: u64,
+    pub difficulty: U256,
+    pub has_uncles: bool,
+    /// Difficulty of the chain up to and including this header.
+    pub total_difficulty: U256,
+}
+
+impl StoredHeader {
+    fn new(header: &EthereumHeader, total_difficulty: U256) -> Self {
+        StoredHeader {
+            number: header.number,
+            parent_hash: header.parent_hash,
+            transactions_root: header.transactions_root,
+            receipts_root: header.receipts_root,
+            timestamp: header.timestamp,
+            difficulty: header.difficulty,
+            has_uncles: header.uncles_hash != H256(EMPTY_UNCLES_HASH),
+            total_difficulty,
+        }
+    }
+}
+
+#[derive(Encode, Decode, Clone, Copy, Default, PartialEq, Eq, RuntimeDebug)]
+pub struct HeaderId {
+    pub number: u64,
+    pub hash: H256,
+}
+
+/// The dataset elements read by the seal of a header, with their Merkle proofs.
+#[derive(Encode, Decode, Clone, Default, PartialEq, Eq, RuntimeDebug)]
+pub struct DagProof {
+    /// The element read by each access of the seal, in order.
+    pub elements: Vec<DagElement>,
+    /// The path of each element to the root of the dataset, from the leaf up.
+    pub paths: Vec<Vec<H256>>,
+}
+
+/// Proof of a deposit into the bridge contract.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct DepositProof {
+    /// Hash of the block the deposit was made in.
+    pub block_hash: H256,
+    /// Index of the deposit transaction in the block.
+    pub tx_index: u64,
+    /// Index of the deposit event in the transaction receipt.
+    pub log_index: u32,
+    /// Nodes of the transactions trie on the path to the transaction.
+    pub transaction_proof: Vec<Vec<u8>>,
+    /// Nodes of the receipts trie on the path to the receipt.
+    pub receipt_proof: Vec<Vec<u8>>,
+}
+
+pub trait WeightInfo {
+    fn import_header(d: u32) -> Weight;
+    fn set_checkpoint(h: u32) -> Weight;
+    fn set_dag_roots(r: u32) -> Weight;
+    fn set_bridge_contract() -> Weight;
+}
+
+pub trait Trait: frame_system::Trait {
+    /// The overarching event type.
+    type Event: From<Event<Self>> + Into<<Self as frame_system::Trait>::Event>;
+
+    /// The accounts which may import headers. Headers are verified, so importers are only
+    /// trusted to keep the light client up to date.
+    type Importers: Contains<Self::AccountId>;
+
+    /// Lowest difficulty of a header, 131072 on Ethereum.
+    type MinimumDifficulty: Get<U256>;
+
+    /// Number of blocks the difficulty bomb is delayed by, as set by the last Ethereum fork
+    /// which delayed it.
+    type DifficultyBombDelay: Get<u64>;
+
+    /// The origin which may set the checkpoint, the dataset roots and the bridge contract.
+    type GovernanceOrigin: EnsureOrigin<Self::Origin>;
+
+    /// Number of descendants a header needs before it is finalized.
+    type ConfirmationDepth: Get<u32>;
+
+    /// Number of finalized headers kept for proving deposits. Older headers are pruned, along
+    /// with the other headers of their numbers.
+    type HeadersToKeep: Get<u32>;
+
+    /// Weight information for extrinsics in this pallet.
+    type WeightInfo: WeightInfo;
+}
+
+decl_storage! {
+    trait Store for Module<T: Trait> as EthLightClient {
+        /// Imported headers.
+        pub Headers get(fn headers): map hasher(identity) H256 => Option<StoredHeader>;
+
+        /// The imported header with the most total difficulty.
+        pub BestHeader get(fn best_header): HeaderId;
+
+        /// The latest finalized header.
+        pub FinalizedHeader get(fn finalized_header): HeaderId;
+
+        /// Hashes of the finalized headers by number.
+        pub FinalizedHashes get(fn finalized_hash): map hasher(twox_64_concat) u64 => Option<H256>;
+
+        /// Hashes of the headers imported at each number.
+        HeadersByNumber: map hasher(twox_64_concat) u64 => Vec<H256>;
+
+        /// Merkle roots of the Ethash datasets by epoch.
+        pub DagRoots get(fn dag_root): map hasher(twox_64_concat) u64 => Option<H256>;
+
+        /// Lowest number which may still have headers to prune.
+        OldestNumber: u64;
+
+        /// Address of the bridge contract emitting the deposit events.
+        pub BridgeContract get(fn bridge_contract) config(): ExternalAddress;
+    }
+    add_extra_genesis {
+        /// RLP encoded header the light client starts from, with the total difficulty of the
+        /// chain up to it.
+        config(checkpoint): Option<(Vec<u8>, U256)>;
+        /// Merkle roots of the Ethash datasets of the epochs from the checkpoint on.
+        config(dag_roots): Vec<(u64, H256)>;
+        build(|config| {
+            if let Some((header, total_difficulty)) = &config.checkpoint {
+                let header = EthereumHeader::decode_rlp(header).expect("invalid checkpoint header");
+                Module::<T>::set_checkpoint_unchecked(&header, *total_difficulty);
+            }
+            for (epoch, root) in &config.dag_roots {
+                DagRoots::insert(epoch, root);
+            }
+        });
+    }
+}
+
+decl_event!(
+    pub enum Event<T>
+    where
+        AccountId = <T as frame_system::Trait>::AccountId,
+    {
+        /// A header was imported. \[hash, number, importer\]
+        HeaderImported(H256, u64, AccountId),
+        /// A header was finalized. \[hash, number\]
+        HeaderFinalized(H256, u64),
+        /// The light client was reset to a checkpoint. \[hash, number\]
+        CheckpointSet(H256, u64),
+        /// Dataset roots were set for consecutive epochs. \[first_epoch, count\]
+        DagRootsSet(u64, u32),
+        /// The bridge contract changed. \[contract\]
+        BridgeContractChanged(H160),
+    }
+);
+
+decl_error! {
+    pub enum Error for Module<T: Trait> {
+        /// The header is not a valid RLP encoded Ethereum header.
+        InvalidHeader,
+        /// The caller is not an importer.
+        NotImporter,
+        /// The header was already imported.
+        HeaderAlreadyImported,
+        /// No checkpoint was set yet.
+        NoCheckpoint,
+        /// The header is not above the finalized header.
+        AncientHeader,
+        /// The header is too far above the finalized header.
+        TooFarAhead,
+        /// The parent of the header was not imported.
+        UnknownParent,
+        /// The difficulty of the header does not follow from its parent.
+        InvalidDifficulty,
+        /// No dataset root is known for the epoch of the header.
+        UnknownEpoch,
+        /// The dataset elements do not match the root of the epoch.
+        InvalidDagProof,
+        /// The mix hash of the seal is not the one computed from the dataset.
+        InvalidSeal,
+        /// The seal does not meet the difficulty of the header.
+        InsufficientWork,
+        /// The header does not descend from the finalized header.
+        ConflictsWithFinalized,
+        /// The block of the deposit was not imported.
+        UnknownHeader,
+        /// The block of the deposit is not finalized.
+        HeaderNotFinalized,
+        /// The transaction or receipt proof does not match the roots of the block.
+        InvalidProof,
+        /// The proven receipt is malformed or has no such event.
+        InvalidReceipt,
+        /// The event is not a deposit into the bridge contract.
+        NotDepositEvent,
+        /// The deposit recipient is not a valid account.
+        InvalidRecipient,
+        /// The deposited amount does not fit into a balance.
+        AmountOverflow,
+    }
+}
+
+decl_module! {
+    pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+        type Error = Error<T>;
+
+        /// Number of descendants a header needs before it is finalized.
+        const ConfirmationDepth: u32 = T::ConfirmationDepth::get();
+
+        /// Number of finalized headers kept for proving deposits.
+        const HeadersToKeep: u32 = T::HeadersToKeep::get();
+
+        fn deposit_event() = default;
+
+        /// Import an RLP encoded header on top of an imported one, with the dataset elements
+        /// its seal reads.
+        ///
+        /// Finalizes the ancestors of the new best header `ConfirmationDepth` blocks back.
+        #[weight = T::WeightInfo::import_header(
+            T::ConfirmationDepth::get() + MAX_UNFINALIZED_MARGIN
+        )]
+        #[transactional]
+        fn import_header(origin, header: Vec<u8>, dag_proof: DagProof) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            ensure!(T::Importers::contains(&who), Error::<T>::NotImporter);
+            let header =
+                EthereumHeader::decode_rlp(&header).map_err(|_| Error::<T>::InvalidHeader)?;
+            ensure!(
+                !Headers::contains_key(header.hash),
+                Error::<T>::HeaderAlreadyImported
+            );
+            let finalized = Self::finalized_header();
+            ensure!(!finalized.hash.is_zero(), Error::<T>::NoCheckpoint);
+            ensure!(header.number > finalized.number, Error::<T>::AncientHeader);
+            let margin = T::ConfirmationDepth::get() + MAX_UNFINALIZED_MARGIN;
+            ensure!(
+                header.number <= finalized.number.saturating_add(margin.into()),
+                Error::<T>::TooFarAhead
+            );
+            let parent = Self::headers(header.parent_hash).ok_or(Error::<T>::UnknownParent)?;
+            ensure!(
+                header.number == parent.number + 1 && header.timestamp > parent.timestamp,
+                Error::<T>::InvalidHeader
+            );
+            let difficulty = ethash::difficulty(
+                parent.difficulty,
+                parent.timestamp,
+                parent.has_uncles,
+                header.timestamp,
+                header.number,
+                T::MinimumDifficulty::get(),
+                T::DifficultyBombDelay::get(),
+            );
+            ensure!(header.difficulty == difficulty, Error::<T>::InvalidDifficulty);
+            Self::verify_seal(&header, &dag_proof)?;
+
+            HeadersByNumber::append(header.number, header.hash);
+            Self::prune_headers(MAX_PRUNED_PER_IMPORT);
+            let total_difficulty = parent.total_difficulty.saturating_add(header.difficulty);
+            Headers::insert(header.hash, StoredHeader::new(&header, total_difficulty));
+            Self::deposit_event(RawEvent::HeaderImported(header.hash, header.number, who));
+
+            let best = Self::best_header();
+            let best_difficulty = Self::headers(best.hash)
+                .map(|h| h.total_difficulty)
+                .unwrap_or_default();
+            if (total_difficulty, header.number) > (best_difficulty, best.number) {
+                BestHeader::put(HeaderId { number: header.number, hash: header.hash });
+                Self::finalize(header.hash, header.number)?;
+            }
+            Ok(())
+        }
+
+        /// Restart the light client from an RLP encoded header, trusted to be final.
+        ///
+        /// Drops all the headers and approvals of the previous chain.
+        #[weight = T::WeightInfo::set_checkpoint(T::HeadersToKeep::get())]
+        fn set_checkpoint(origin, header: Vec<u8>, total_difficulty: U256) -> DispatchResult {
+            T::GovernanceOrigin::ensure_origin(origin)?;
+            let header =
+                EthereumHeader::decode_rlp(&header).map_err(|_| Error::<T>::InvalidHeader)?;
+
+            Self::set_checkpoint_unchecked(&header, total_difficulty);
+
+            Self::deposit_event(RawEvent::CheckpointSet(header.hash, header.number));
+            Ok(())
+        }
+
+        /// Set the Merkle roots of the Ethash datasets of the epochs from `first_epoch` on.
+        ///
+        /// The roots are computed off chain from the datasets, which anyone can generate.
+        #[weight = T::WeightInfo::set_dag_roots(roots.len() as u32)]
+        fn set_dag_roots(origin, first_epoch: u64, roots: Vec<H256>) -> DispatchResult {
+            T::GovernanceOrigin::ensure_origin(origin)?;
+
+            for (epoch, root) in (first_epoch..).zip(&roots) {
+                DagRoots::insert(epoch, root);
+            }
+
+            Self::deposit_event(RawEvent::DagRootsSet(first_epoch, roots.len() as u32));
+            Ok(())
+        }
+
+        /// Set the contract whose deposit events are accepted.
+        #[weight = T::WeightInfo::set_bridge_contract()]
+        fn set_bridge_contract(origin, contract: ExternalAddress) -> DispatchResult {
+            T::GovernanceOrigin::ensure_origin(origin)?;
+
+            BridgeContract::put(contract);
+
+            Self::deposit_event(RawEvent::BridgeContractChanged(contract));
+            Ok(())
+        }
+    }
+}
+
+impl<T: Trait> Module<T> {
+    fn set_checkpoint_unchecked(header: &EthereumHeader, total_difficulty: U256) {
+        Headers::remove_all();
+        HeadersByNumber::remove_all();
+        FinalizedHashes::remove_all();
+
+        Headers::insert(header.hash, StoredHeader::new(header, total_difficulty));
+        HeadersByNumber::insert(header.number, vec![header.hash]);
+        OldestNumber::put(header.number);
+        let id = HeaderId {
+            number: header.number,
+            hash: header.hash,
+        };
+        BestHeader::put(id);
+        FinalizedHeader::put(id);
+        FinalizedHashes::insert(header.number, header.hash);
+    }
+
+    /// Check the seal of `header` against the dataset of its epoch.
+    fn verify_seal(header: &EthereumHeader, proof: &DagProof) -> DispatchResult {
+        let epoch = header.number / ethash::EPOCH_LENGTH;
+        let root = Self::dag_root(epoch).ok_or(Error::<T>::UnknownEpoch)?;
+        ensure!(
+            proof.elements.len() == ethash::ACCESSES && proof.paths.len() == ethash::ACCESSES,
+            Error::<T>::InvalidDagProof
+        );
+        let size = ethash::dataset_size(epoch);
+        let depth = ethash::dag_depth(size);
+
+        let (mix_hash, result) =
+            ethash::hashimoto(&header.seal_hash, header.nonce, size, |access, index| {
+                let element = &proof.elements[access];
+                if ethash::verify_element(&root, depth, index, element, &proof.paths[access]) {
+                    Ok(*element)
+                } else {
+                    Err(Error::<T>::InvalidDagProof)
+                }
+            })?;
+        ensure!(mix_hash == header.mix_hash, Error::<T>::InvalidSeal);
+        ensure!(
+            ethash::meets_target(&result, header.difficulty),
+            Error::<T>::InsufficientWork
+        );
+        Ok(())
+    }
+
+    /// Finalize the ancestor of the best header `best` `ConfirmationDepth` blocks back, and all
+    /// headers between it and the previously finalized one.
+    ///
+    /// As headers are at most `ConfirmationDepth + MAX_UNFINALIZED_MARGIN` blocks above the
+    /// finalized one, so is the walk back to it.
+    fn finalize(best: H256, best_number: u64) -> DispatchResult {
+        let finalized = Self::finalized_header();
+        let target = best_number.saturating_sub(T::ConfirmationDepth::get().into());
+        if target <= finalized.number {
+            return Ok(());
+        }
+
+        let mut hash = best;
+        let mut header = Self::headers(hash).ok_or(Error::<T>::UnknownParent)?;
+        while header.number > target {
+            hash = header.parent_hash;
+            header = Self::headers(hash).ok_or(Error::<T>::UnknownParent)?;
+        }
+        let new_finalized = HeaderId {
+            number: target,
+            hash,
+        };
+
+        while header.number > finalized.number {
+            FinalizedHashes::insert(header.number, hash);
+            hash = header.parent_hash;
+            header = Self::headers(hash).ok_or(Error::<T>::UnknownParent)?;
+        }
+        ensure!(hash == finalized.hash, Error::<T>::ConflictsWithFinalized);
+
+        FinalizedHeader::put(new_finalized);
+        Self::deposit_event(RawEvent::HeaderFinalized(
+            new_finalized.hash,
+            new_finalized.number,
+        ));
+        Ok(())
+    }
+
+    /// Remove up to `budget` headers from the numbers more than `HeadersToKeep` below the
+    /// finalized header, oldest first.
+    fn prune_headers(mut budget: u32) {
+        let keep_from = Self::finalized_header()
+            .number
+            .saturating_sub(T::HeadersToKeep::get().into());
+        let mut number = OldestNumber::get();
+        while number < keep_from && budget > 0 {
+            let mut hashes = HeadersByNumber::get(number);
+            while budget > 0 {
+                match hashes.pop() {
+                    Some(hash) => {
+                        Headers::remove(hash);
+                        budget -= 1;
+                    }
+                    None => break,
+                }
+            }
+            if !hashes.is_empty() {
+                HeadersByNumber::insert(number, hashes);
+                break;
+            }
+            HeadersByNumber::remove(number);
+            FinalizedHashes::remove(number);
+            number += 1;
+        }
+        OldestNumber::put(number);
+    }
+
+    /// The address, topics and data of the event at `log_index` of an RLP encoded receipt.
+    fn receipt_log(
+        receipt: &[u8],
+        log_index: u32,
+    ) -> Result<(H160, Vec<H256>, Vec<u8>), DecoderError> {
+        // Typed receipts (EIP-2718) are prefixed with their type.
+        let receipt = match receipt.first() {
+            Some(ty) if *ty < 0x80 => &receipt[1..],
+            _ => receipt,
+        };
+        let items = Rlp::new(receipt)?.items()?;
+        let logs = items.get(3).ok_or(DecoderError)?.items()?;
+        let log = logs.get(log_index as usize).ok_or(DecoderError)?.items()?;
+        if log.len() != 3 {
+            return Err(DecoderError);
+        }
+        let topics = log[1]
+            .items()?
+            .iter()
+            .map(Rlp::as_h256)
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok((log[0].as_h160()?, topics, log[2].data()?.to_vec()))
+    }
+}
+
+/// Encode a transaction index as a key of the transactions and receipts tries.
+fn trie_key(tx_index: u64) -> Vec<u8> {
+    let bytes = tx_index.to_be_bytes();
+    let significant = &bytes[bytes.iter().take_while(|b| **b == 0).count()..];
+    match significant {
+        [] => vec![0x80],
+        [b] if *b < 0x80 => vec![*b],
+        _ => {
+            let mut key = vec![0x80 + significant.len() as u8];
+            key.extend_from_slice(significant);
+            key
+        }
+    }
+}
+
+impl<T: Trait, Balance> VerifyDeposit<T::AccountId, Balance> for Module<T>
+where
+    Balance: TryFrom<u128> + Into<u128> + Clone,
+{
+    type Proof = DepositProof;
+
+    fn verify(proof: &DepositProof) -> Result<ProvenDeposit<T::AccountId, Balance>, DispatchError> {
+        let header = Self::headers(proof.block_hash).ok_or(Error::<T>::UnknownHeader)?;
+        ensure!(
+            Self::finalized_hash(header.number) == Some(proof.block_hash),
+            Error::<T>::HeaderNotFinalized
+        );
+
+        let key = trie_key(proof.tx_index);
+        let transaction =
+            trie::verify_proof(&header.transactions_root, &key, &proof.transaction_proof)
+                .map_err(|_| Error::<T>::InvalidProof)?;
+        let receipt = trie::verify_proof(&header.receipts_root, &key, &proof.receipt_proof)
+            .map_err(|_| Error::<T>::InvalidProof)?;
+        let (address, topics, data) =
+            Self::receipt_log(&receipt, proof.log_index).map_err(|_| Error::<T>::InvalidReceipt)?;
+
+        ensure!(
+            address == Self::bridge_contract()
+                && topics.len() == 3
+                && topics[0] == H256(keccak_256(DEPOSIT_EVENT_SIGNATURE))
+                && data.len() == 32,
+            Error::<T>::NotDepositEvent
+        );
+        let token = H160::from_slice(&topics[1][12..]);
+        let account = T::AccountId::decode(&mut topics[2].as_bytes())
+            .map_err(|_| Error::<T>::InvalidRecipient)?;
+        let amount = U256::from_big_endian(&data);
+        ensure!(
+            amount <= U256::from(u128::max_value()),
+            Error::<T>::AmountOverflow
+        );
+        let amount =
+            Balance::try_from(amount.low_u128()).map_err(|_| Error::<T>::AmountOverflow)?;
+
+        Ok(ProvenDeposit {
+            // Typed transactions (EIP-2718) are hashed with their type, as stored in the trie.
+            deposit_id: deposit_id_of(H256(keccak_256(&transaction)), proof.log_index),
+            token,
+            account,
+            amount,
+        })
+    }
+
+    /// Store a finalized header whose tries hold a single transaction and its receipt with the
+    /// deposit.
+    #[cfg(feature = "runtime-benchmarks")]
+    fn successful_proof(deposit: &ProvenDeposit<T::AccountId, Balance>) -> DepositProof {
+        use rlp::{encode_bytes, encode_list};
+
+        let mut token_topic = [0u8; 32];
+        token_topic[12..].copy_from_slice(deposit.token.as_bytes());
+        let mut recipient_topic = [0u8; 32];
+        let recipient = deposit.account.encode();
+        let len = recipient.len().min(32);
+        recipient_topic[..len].copy_from_slice(&recipient[..len]);
+        let mut amount = [0u8; 32];
+        U256::from(deposit.amount.clone().into()).to_big_endian(&mut amount);
+
+        let log = encode_list(&[
+            encode_bytes(Self::bridge_contract().as_bytes()),
+            encode_list(&[
+                encode_bytes(&keccak_256(DEPOSIT_EVENT_SIGNATURE)),
+                encode_bytes(&token_topic),
+                encode_bytes(&recipient_topic),
+            ]),
+            encode_bytes(&amount),
+        ]);
+        let receipt = encode_list(&[
+            encode_bytes(&[1]),
+            encode_bytes(&[]),
+            encode_bytes(&[0; 256]),
+            encode_list(&[log]),
+        ]);
+        // The key of the first transaction and receipt is `rlp(0)`, the nibbles [8, 0].
+        let leaf = |value: &[u8]| encode_list(&[encode_bytes(&[0x20, 0x80]), encode_bytes(value)]);
+        let transaction_leaf = leaf(&deposit.deposit_id.encode());
+        let receipt_leaf = leaf(&receipt);
+
+        let finalized = Self::finalized_header();
+        let block_hash = H256(keccak_256(&receipt_leaf));
+        Headers::insert(
+            block_hash,
+            StoredHeader {
+                number: finalized.number,
+                parent_hash: finalized.hash,
+                transactions_root: H256(keccak_256(&transaction_leaf)),
+                receipts_root: H256(keccak_256(&receipt_leaf)),
+                ..Default::default()
+            },
+        );
+        FinalizedHashes::insert(finalized.number, block_hash);
+
+        DepositProof {
+            block_hash,
+            tx_index: 0,
+            log_index: 0,
+            transaction_proof: vec![transaction_leaf],
+            receipt_proof: vec![receipt_leaf],
+        }
+    }
+}
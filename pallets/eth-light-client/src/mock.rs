@@ -0,0 +1,135 @@
+// This file is part of Curio project.
+
+// Copyright (C) 2021 Mixbytes.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use crate as light_client;
+use frame_support::{
+    impl_outer_event, impl_outer_origin, parameter_types, traits::Contains, weights::Weight,
+};
+use frame_system::EnsureRoot;
+use sp_runtime::{
+    testing::Header,
+    traits::{BlakeTwo256, IdentityLookup},
+    Perbill,
+};
+
+impl_outer_origin! {
+    pub enum Origin for Test where system = frame_system {}
+}
+
+impl_outer_event! {
+    pub enum TestEvent for Test {
+        frame_system<T>,
+        light_client<T>,
+    }
+}
+
+#[derive(Clone, Eq, PartialEq)]
+pub struct Test;
+
+parameter_types! {
+    pub const BlockHashCount: u64 = 250;
+    pub const MaximumBlockWeight: Weight = 1024;
+    pub const MaximumBlockLength: u32 = 2 * 1024;
+    pub const AvailableBlockRatio: Perbill = Perbill::one();
+}
+
+impl frame_system::Trait for Test {
+    type BaseCallFilter = ();
+    type Origin = Origin;
+    type Call = ();
+    type Index = u64;
+    type BlockNumber = u64;
+    type Hash = H256;
+    type Hashing = BlakeTwo256;
+    type AccountId = u64;
+    type Lookup = IdentityLookup<Self::AccountId>;
+    type Header = Header;
+    type Event = TestEvent;
+    type BlockHashCount = BlockHashCount;
+    type MaximumBlockWeight = MaximumBlockWeight;
+    type DbWeight = ();
+    type BlockExecutionWeight = ();
+    type ExtrinsicBaseWeight = ();
+    type MaximumExtrinsicWeight = MaximumBlockWeight;
+    type MaximumBlockLength = MaximumBlockLength;
+    type AvailableBlockRatio = AvailableBlockRatio;
+    type Version = ();
+    type PalletInfo = ();
+    type AccountData = ();
+    type OnNewAccount = ();
+    type OnKilledAccount = ();
+    type SystemWeightInfo = ();
+}
+
+pub struct Importers;
+impl Contains<u64> for Importers {
+    fn sorted_members() -> Vec<u64> {
+        vec![1, 2, 3]
+    }
+}
+
+parameter_types! {
+    // Low enough for any seal to meet the difficulty of the test headers.
+    pub const MinimumDifficulty: U256 = U256::one();
+    pub const DifficultyBombDelay: u64 = 1_000_000_000;
+    pub const ConfirmationDepth: u32 = 2;
+    pub const HeadersToKeep: u32 = 3;
+}
+
+impl Trait for Test {
+    type Event = TestEvent;
+    type Importers = Importers;
+    type MinimumDifficulty = MinimumDifficulty;
+    type DifficultyBombDelay = DifficultyBombDelay;
+    type GovernanceOrigin = EnsureRoot<u64>;
+    type ConfirmationDepth = ConfirmationDepth;
+    type HeadersToKeep = HeadersToKeep;
+    type WeightInfo = ();
+}
+
+pub type System = frame_system::Module<Test>;
+pub type LightClient = Module<Test>;
+
+/// The root of the dataset of the first epoch, taken to be all zeros, and the path of any of its
+/// elements.
+pub fn zero_dag() -> (H256, Vec<H256>) {
+    let mut node = ethash::hash_element(&Default::default());
+    let mut path = Vec::new();
+    for _ in 0..ethash::dag_depth(ethash::dataset_size(0)) {
+        path.push(node);
+        let mut data = [0u8; 64];
+        data[..32].copy_from_slice(node.as_bytes());
+        data[32..].copy_from_slice(node.as_bytes());
+        node = H256(keccak_256(&data));
+    }
+    (node, path)
+}
+
+pub fn new_test_ext(checkpoint: Vec<u8>) -> sp_io::TestExternalities {
+    let mut t = frame_system::GenesisConfig::default()
+        .build_storage::<Test>()
+        .unwrap();
+    GenesisConfig {
+        bridge_contract: H160::repeat_byte(0xbb),
+        checkpoint: Some((checkpoint, U256::from(1_000))),
+        dag_roots: vec![(0, zero_dag().0)],
+    }
+    .assimilate_storage::<Test>(&mut t)
+    .unwrap();
+    t.into()
+}
@@ -0,0 +1,177 @@
+// This file is part of Curio project.
+
+// Copyright (C) 2021 Mixbytes.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Minimal decoder of Ethereum's Recursive Length Prefix encoding, and the encoder used to
+//! hash headers without their seal and to build test and benchmark fixtures.
+
+use sp_core::{H160, H256, U256};
+use sp_std::prelude::*;
+
+/// The data is not valid RLP, or not of the expected shape.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct DecoderError;
+
+/// A single RLP item: either a byte string or a list of items.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Rlp<'a> {
+    raw: &'a [u8],
+    is_list: bool,
+    payload_offset: usize,
+}
+
+impl<'a> Rlp<'a> {
+    /// Decode `raw`, which must hold exactly one item.
+    pub fn new(raw: &'a [u8]) -> Result<Self, DecoderError> {
+        let (item, rest) = Self::split(raw)?;
+        if !rest.is_empty() {
+            return Err(DecoderError);
+        }
+        Ok(item)
+    }
+
+    /// Split the first item off `data`.
+    fn split(data: &'a [u8]) -> Result<(Self, &'a [u8]), DecoderError> {
+        let prefix = *data.first().ok_or(DecoderError)?;
+        let (is_list, payload_offset, payload_len) = match prefix {
+            0x00..=0x7f => (false, 0, 1),
+            0x80..=0xb7 => (false, 1, (prefix - 0x80) as usize),
+            0xb8..=0xbf => {
+                let len_of_len = (prefix - 0xb7) as usize;
+                (false, 1 + len_of_len, Self::long_length(data, len_of_len)?)
+            }
+            0xc0..=0xf7 => (true, 1, (prefix - 0xc0) as usize),
+            0xf8..=0xff => {
+                let len_of_len = (prefix - 0xf7) as usize;
+                (true, 1 + len_of_len, Self::long_length(data, len_of_len)?)
+            }
+        };
+        let end = payload_offset
+            .checked_add(payload_len)
+            .ok_or(DecoderError)?;
+        if end > data.len() {
+            return Err(DecoderError);
+        }
+        let item = Rlp {
+            raw: &data[..end],
+            is_list,
+            payload_offset,
+        };
+        Ok((item, &data[end..]))
+    }
+
+    fn long_length(data: &[u8], len_of_len: usize) -> Result<usize, DecoderError> {
+        let bytes = data.get(1..1 + len_of_len).ok_or(DecoderError)?;
+        if len_of_len > sp_std::mem::size_of::<usize>() || bytes[0] == 0 {
+            return Err(DecoderError);
+        }
+        Ok(bytes.iter().fold(0, |len, b| (len << 8) | *b as usize))
+    }
+
+    /// The encoded item, prefix included.
+    pub fn as_raw(&self) -> &'a [u8] {
+        self.raw
+    }
+
+    pub fn is_list(&self) -> bool {
+        self.is_list
+    }
+
+    /// The content of a byte string.
+    pub fn data(&self) -> Result<&'a [u8], DecoderError> {
+        if self.is_list {
+            return Err(DecoderError);
+        }
+        Ok(&self.raw[self.payload_offset..])
+    }
+
+    /// The items of a list.
+    pub fn items(&self) -> Result<Vec<Rlp<'a>>, DecoderError> {
+        if !self.is_list {
+            return Err(DecoderError);
+        }
+        let mut items = Vec::new();
+        let mut rest = &self.raw[self.payload_offset..];
+        while !rest.is_empty() {
+            let (item, tail) = Self::split(rest)?;
+            items.push(item);
+            rest = tail;
+        }
+        Ok(items)
+    }
+
+    pub fn as_u64(&self) -> Result<u64, DecoderError> {
+        let data = self.data()?;
+        if data.len() > 8 {
+            return Err(DecoderError);
+        }
+        Ok(data.iter().fold(0, |n, b| (n << 8) | *b as u64))
+    }
+
+    pub fn as_u256(&self) -> Result<U256, DecoderError> {
+        let data = self.data()?;
+        if data.len() > 32 {
+            return Err(DecoderError);
+        }
+        Ok(U256::from_big_endian(data))
+    }
+
+    pub fn as_h160(&self) -> Result<H160, DecoderError> {
+        let data = self.data()?;
+        if data.len() != 20 {
+            return Err(DecoderError);
+        }
+        Ok(H160::from_slice(data))
+    }
+
+    pub fn as_h256(&self) -> Result<H256, DecoderError> {
+        let data = self.data()?;
+        if data.len() != 32 {
+            return Err(DecoderError);
+        }
+        Ok(H256::from_slice(data))
+    }
+}
+
+fn length_prefix(len: usize, offset: u8) -> Vec<u8> {
+    if len < 56 {
+        return vec![offset + len as u8];
+    }
+    let bytes = (len as u64).to_be_bytes();
+    let significant = &bytes[bytes.iter().take_while(|b| **b == 0).count()..];
+    let mut prefix = vec![offset + 55 + significant.len() as u8];
+    prefix.extend_from_slice(significant);
+    prefix
+}
+
+/// Encode a byte string.
+#[cfg(any(test, feature = "runtime-benchmarks"))]
+pub fn encode_bytes(data: &[u8]) -> Vec<u8> {
+    if data.len() == 1 && data[0] < 0x80 {
+        return data.to_vec();
+    }
+    let mut out = length_prefix(data.len(), 0x80);
+    out.extend_from_slice(data);
+    out
+}
+
+/// Encode a list of already encoded items.
+pub fn encode_list(items: &[Vec<u8>]) -> Vec<u8> {
+    let payload = items.concat();
+    let mut out = length_prefix(payload.len(), 0xc0);
+    out.extend(payload);
+    out
+}
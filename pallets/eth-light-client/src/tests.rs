@@ -0,0 +1,689 @@
+// This file is part of Curio project.
+
+// Copyright (C) 2021 Mixbytes.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use frame_support::{assert_noop, assert_ok};
+use frame_system::RawOrigin;
+use mock::{new_test_ext, ConfirmationDepth, LightClient, Test};
+use pallet_dummy::deposit_id_of;
+use rlp::{encode_bytes, encode_list};
+use sp_core::H512;
+use std::collections::BTreeMap;
+
+/// The RLP encoded block 1 of the Ethereum mainnet.
+const MAINNET_BLOCK_1: &str = include_str!("../res/mainnet_block_1.hex");
+/// The index of each element of the dataset read by the seal of mainnet block 1, and the
+/// element.
+const MAINNET_BLOCK_1_DAG: &str = include_str!("../res/mainnet_block_1_dag.txt");
+
+fn encode_u64(n: u64) -> Vec<u8> {
+    let bytes = n.to_be_bytes();
+    encode_bytes(&bytes[bytes.iter().take_while(|b| **b == 0).count()..])
+}
+
+fn hash(data: &[u8]) -> H256 {
+    H256(keccak_256(data))
+}
+
+fn from_hex(hex: &str) -> Vec<u8> {
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+        .collect()
+}
+
+fn timestamp_of(number: u64) -> u64 {
+    1_600_000_000 + number * 13
+}
+
+/// The fields of a header up to its seal.
+fn unsealed(
+    parent: H256,
+    number: u64,
+    timestamp: u64,
+    difficulty: u64,
+    transactions_root: H256,
+    receipts_root: H256,
+) -> Vec<Vec<u8>> {
+    vec![
+        encode_bytes(parent.as_bytes()),
+        encode_bytes(&EMPTY_UNCLES_HASH),
+        encode_bytes(&[0; 20]),
+        encode_bytes(&[0; 32]),
+        encode_bytes(transactions_root.as_bytes()),
+        encode_bytes(receipts_root.as_bytes()),
+        encode_bytes(&[0; 256]),
+        encode_u64(difficulty),
+        encode_u64(number),
+        encode_u64(8_000_000),
+        encode_u64(21_000),
+        encode_u64(timestamp),
+        encode_bytes(&[]),
+    ]
+}
+
+fn with_seal(mut fields: Vec<Vec<u8>>, mix_hash: H256, nonce: u64) -> Vec<u8> {
+    fields.push(encode_bytes(mix_hash.as_bytes()));
+    fields.push(encode_bytes(&nonce.to_be_bytes()));
+    encode_list(&fields)
+}
+
+/// Run Ethash over `fields` with the dataset of zeros, from the nonce 0 on until the seal meets
+/// `difficulty` if `mine`.
+fn seal(fields: Vec<Vec<u8>>, difficulty: u64, mine: bool) -> Vec<u8> {
+    let seal_hash = hash(&encode_list(&fields));
+    let size = ethash::dataset_size(0);
+    for nonce in 0.. {
+        let (mix_hash, result) = ethash::hashimoto(&seal_hash, nonce, size, |_, _| {
+            Ok::<_, ()>(Default::default())
+        })
+        .unwrap();
+        if !mine || ethash::meets_target(&result, difficulty.into()) {
+            return with_seal(fields, mix_hash, nonce);
+        }
+    }
+    unreachable!()
+}
+
+fn header_with_roots(
+    parent: H256,
+    number: u64,
+    transactions_root: H256,
+    receipts_root: H256,
+) -> Vec<u8> {
+    let fields = unsealed(
+        parent,
+        number,
+        timestamp_of(number),
+        1,
+        transactions_root,
+        receipts_root,
+    );
+    seal(fields, 1, true)
+}
+
+fn header(parent: H256, number: u64) -> Vec<u8> {
+    header_with_roots(parent, number, H256::zero(), H256::zero())
+}
+
+/// A header forking off at `number`, with a later timestamp than the header of the main chain.
+fn fork_header(parent: H256, number: u64) -> Vec<u8> {
+    let fields = unsealed(
+        parent,
+        number,
+        timestamp_of(number) + 1,
+        1,
+        H256::zero(),
+        H256::zero(),
+    );
+    seal(fields, 1, true)
+}
+
+/// The elements of the dataset of zeros read by any seal.
+fn dag_proof() -> DagProof {
+    DagProof {
+        elements: vec![Default::default(); ethash::ACCESSES],
+        paths: vec![mock::zero_dag().1; ethash::ACCESSES],
+    }
+}
+
+fn import(header: &[u8]) {
+    assert_ok!(LightClient::import_header(
+        RawOrigin::Signed(1).into(),
+        header.to_vec(),
+        dag_proof()
+    ));
+}
+
+fn deposit_receipt(contract: H160, token: H160, recipient: u64, amount: u64) -> Vec<u8> {
+    let mut token_topic = [0u8; 32];
+    token_topic[12..].copy_from_slice(token.as_bytes());
+    let mut recipient_topic = [0u8; 32];
+    recipient_topic[..8].copy_from_slice(&recipient.encode());
+    let log = encode_list(&[
+        encode_bytes(contract.as_bytes()),
+        encode_list(&[
+            encode_bytes(&keccak_256(DEPOSIT_EVENT_SIGNATURE)),
+            encode_bytes(&token_topic),
+            encode_bytes(&recipient_topic),
+        ]),
+        encode_bytes(H256::from_low_u64_be(amount).as_bytes()),
+    ]);
+    encode_list(&[
+        encode_u64(1),
+        encode_u64(50_000),
+        encode_bytes(&[0; 256]),
+        encode_list(&[log]),
+    ])
+}
+
+/// A trie holding `values[0]` under key `rlp(0)` and `values[1]` under `rlp(1)`, as its root and
+/// the proofs of both values.
+fn trie_of(values: [Vec<u8>; 2]) -> (H256, [Vec<Vec<u8>>; 2]) {
+    // The keys are the nibbles [8, 0] and [0, 1]: a branch with two leaves.
+    let leaf0 = encode_list(&[encode_bytes(&[0x30]), encode_bytes(&values[0])]);
+    let leaf1 = encode_list(&[encode_bytes(&[0x31]), encode_bytes(&values[1])]);
+    let mut children = vec![encode_bytes(&[]); 17];
+    children[8] = encode_bytes(hash(&leaf0).as_bytes());
+    children[0] = encode_bytes(hash(&leaf1).as_bytes());
+    let branch = encode_list(&children);
+    (
+        hash(&branch),
+        [vec![branch.clone(), leaf0], vec![branch, leaf1]],
+    )
+}
+
+#[test]
+fn proof_verification_works() {
+    let receipt0 = b"first receipt, long enough not to be inlined".to_vec();
+    let receipt1 = b"second receipt, long enough not to be inlined".to_vec();
+    let (root, [proof0, proof1]) = trie_of([receipt0.clone(), receipt1.clone()]);
+
+    assert_eq!(trie::verify_proof(&root, &[0x80], &proof0), Ok(receipt0));
+    assert_eq!(trie::verify_proof(&root, &[0x01], &proof1), Ok(receipt1));
+    assert!(trie::verify_proof(&root, &[0x80], &proof1).is_err());
+    assert!(trie::verify_proof(&root, &[0x02], &proof1).is_err());
+    assert!(trie::verify_proof(&H256::zero(), &[0x80], &proof0).is_err());
+}
+
+#[test]
+fn ethash_parameters_follow_ethereum() {
+    assert_eq!(ethash::dataset_size(0), 1_073_739_904);
+    assert_eq!(ethash::dataset_size(1), 1_082_130_304);
+    assert_eq!(ethash::dag_depth(ethash::dataset_size(0)), 23);
+    assert_eq!(ethash::dag_depth(ethash::dataset_size(1)), 24);
+    assert_eq!(H256(EMPTY_UNCLES_HASH), hash(&encode_list(&[])));
+
+    let parent = U256::from(2048 * 1_000);
+    let step = U256::from(1_000);
+    let difficulty = |elapsed: u64, uncles, number, minimum: u64| {
+        ethash::difficulty(
+            parent,
+            100,
+            uncles,
+            100 + elapsed,
+            number,
+            minimum.into(),
+            0,
+        )
+    };
+    assert_eq!(difficulty(8, false, 1, 1), parent + step);
+    assert_eq!(difficulty(9, false, 1, 1), parent);
+    assert_eq!(difficulty(20, false, 1, 1), parent - step);
+    assert_eq!(difficulty(20, true, 1, 1), parent);
+    assert_eq!(difficulty(5, true, 1, 1), parent + step * U256::from(2));
+    assert_eq!(
+        difficulty(10_000, false, 1, 1),
+        parent - step * U256::from(99)
+    );
+    assert_eq!(
+        difficulty(10_000, false, 1, 3_000_000),
+        U256::from(3_000_000)
+    );
+    // The bomb adds 2^(period - 2) from the second period of 100000 blocks on.
+    assert_eq!(difficulty(9, false, 199_999, 1), parent);
+    assert_eq!(difficulty(9, false, 300_000, 1), parent + U256::from(2));
+}
+
+/// The root of a tree holding `leaves` at their indices and zero elements everywhere else, and
+/// the path of each leaf.
+fn sparse_dag(depth: u32, leaves: &[(u64, DagElement)]) -> (H256, Vec<Vec<H256>>) {
+    let mut empty = ethash::hash_element(&Default::default());
+    let mut level = leaves
+        .iter()
+        .map(|(index, element)| (*index, ethash::hash_element(element)))
+        .collect::<BTreeMap<_, _>>();
+    let mut paths = vec![Vec::new(); leaves.len()];
+    for _ in 0..depth {
+        for (path, (index, _)) in paths.iter_mut().zip(leaves) {
+            let sibling = (index >> path.len()) ^ 1;
+            path.push(*level.get(&sibling).unwrap_or(&empty));
+        }
+        let node = |index| *level.get(&index).unwrap_or(&empty);
+        level = level
+            .keys()
+            .map(|index| {
+                let left = node(index & !1);
+                let right = node(index | 1);
+                (
+                    index >> 1,
+                    hash(&[left.as_bytes(), right.as_bytes()].concat()),
+                )
+            })
+            .collect();
+        empty = hash(&[empty.as_bytes(), empty.as_bytes()].concat());
+    }
+    (level[&0], paths)
+}
+
+#[test]
+fn mainnet_seals_are_verified() {
+    let block = EthereumHeader::decode_rlp(&from_hex(MAINNET_BLOCK_1.trim())).unwrap();
+    assert_eq!(
+        block.hash,
+        H256::from_slice(&from_hex(
+            "88e96d4537bea4d9c05d12549907b32561d3bf31f45aae734cdc119f13406cb6"
+        ))
+    );
+    let accesses = MAINNET_BLOCK_1_DAG
+        .lines()
+        .map(|line| {
+            let mut fields = line.split(' ');
+            let index = fields.next().unwrap().parse::<u64>().unwrap();
+            let element = from_hex(fields.next().unwrap());
+            let element = [
+                H512::from_slice(&element[..64]),
+                H512::from_slice(&element[64..]),
+            ];
+            (index, element)
+        })
+        .collect::<Vec<_>>();
+    // The dataset is too large to build here, so the tree only holds the elements read.
+    let (root, paths) = sparse_dag(ethash::dag_depth(ethash::dataset_size(0)), &accesses);
+    let proof = DagProof {
+        elements: accesses.iter().map(|(_, element)| *element).collect(),
+        paths,
+    };
+
+    new_test_ext(header(H256::zero(), 100)).execute_with(|| {
+        assert_ok!(LightClient::set_dag_roots(
+            RawOrigin::Root.into(),
+            0,
+            vec![root]
+        ));
+        assert_ok!(LightClient::verify_seal(&block, &proof));
+
+        // Another nonce reads other elements.
+        let mut forged = block.clone();
+        forged.nonce += 1;
+        assert_eq!(
+            LightClient::verify_seal(&forged, &proof),
+            Err(Error::<Test>::InvalidDagProof.into())
+        );
+        let mut forged = block.clone();
+        forged.mix_hash = H256::zero();
+        assert_eq!(
+            LightClient::verify_seal(&forged, &proof),
+            Err(Error::<Test>::InvalidSeal.into())
+        );
+        let mut forged = block.clone();
+        forged.difficulty = forged.difficulty * U256::from(1u64 << 40);
+        assert_eq!(
+            LightClient::verify_seal(&forged, &proof),
+            Err(Error::<Test>::InsufficientWork.into())
+        );
+    });
+}
+
+#[test]
+fn headers_are_imported_by_importers() {
+    let checkpoint = header(H256::zero(), 100);
+    new_test_ext(checkpoint.clone()).execute_with(|| {
+        let h1 = header(hash(&checkpoint), 101);
+
+        assert_noop!(
+            LightClient::import_header(RawOrigin::Root.into(), h1.clone(), dag_proof()),
+            sp_runtime::traits::BadOrigin
+        );
+        assert_noop!(
+            LightClient::import_header(RawOrigin::Signed(4).into(), h1.clone(), dag_proof()),
+            Error::<Test>::NotImporter
+        );
+
+        assert_ok!(LightClient::import_header(
+            RawOrigin::Signed(3).into(),
+            h1.clone(),
+            dag_proof()
+        ));
+        assert_eq!(
+            LightClient::best_header(),
+            HeaderId {
+                number: 101,
+                hash: hash(&h1)
+            }
+        );
+        assert_noop!(
+            LightClient::import_header(RawOrigin::Signed(2).into(), h1, dag_proof()),
+            Error::<Test>::HeaderAlreadyImported
+        );
+    });
+}
+
+#[test]
+fn forged_headers_are_rejected() {
+    let checkpoint = header(H256::zero(), 100);
+    new_test_ext(checkpoint.clone()).execute_with(|| {
+        let parent = hash(&checkpoint);
+        let import = |header: Vec<u8>, proof: DagProof| {
+            LightClient::import_header(RawOrigin::Signed(1).into(), header, proof)
+        };
+        let fields = |timestamp, difficulty| {
+            unsealed(
+                parent,
+                101,
+                timestamp,
+                difficulty,
+                H256::zero(),
+                H256::zero(),
+            )
+        };
+
+        assert_noop!(
+            import(vec![0xc0], dag_proof()),
+            Error::<Test>::InvalidHeader
+        );
+        assert_noop!(
+            import(header(parent, 102), dag_proof()),
+            Error::<Test>::InvalidHeader
+        );
+        assert_noop!(
+            import(seal(fields(timestamp_of(100), 1), 1, true), dag_proof()),
+            Error::<Test>::InvalidHeader
+        );
+        assert_noop!(
+            import(seal(fields(timestamp_of(101), 2), 2, true), dag_proof()),
+            Error::<Test>::InvalidDifficulty
+        );
+
+        let h1 = header(parent, 101);
+        assert_noop!(
+            import(
+                with_seal(fields(timestamp_of(101), 1), H256::zero(), 0),
+                dag_proof()
+            ),
+            Error::<Test>::InvalidSeal
+        );
+        let mut proof = dag_proof();
+        proof.elements[7][1] = H512::repeat_byte(1);
+        assert_noop!(import(h1.clone(), proof), Error::<Test>::InvalidDagProof);
+        let mut proof = dag_proof();
+        proof.paths.pop();
+        assert_noop!(import(h1.clone(), proof), Error::<Test>::InvalidDagProof);
+        assert_ok!(import(h1.clone(), dag_proof()));
+
+        // The seal must meet the difficulty the parent requires.
+        let difficulty = 1 << 40;
+        let fields = unsealed(
+            H256::zero(),
+            29_999,
+            timestamp_of(29_999),
+            difficulty,
+            H256::zero(),
+            H256::zero(),
+        );
+        let checkpoint = seal(fields, difficulty, false);
+        assert_ok!(LightClient::set_checkpoint(
+            RawOrigin::Root.into(),
+            checkpoint.clone(),
+            U256::from(1_000)
+        ));
+        let fields = unsealed(
+            hash(&checkpoint),
+            30_000,
+            timestamp_of(30_000),
+            difficulty,
+            H256::zero(),
+            H256::zero(),
+        );
+        let h1 = seal(fields, difficulty, false);
+        assert_noop!(import(h1.clone(), dag_proof()), Error::<Test>::UnknownEpoch);
+        assert_ok!(LightClient::set_dag_roots(
+            RawOrigin::Root.into(),
+            1,
+            vec![mock::zero_dag().0]
+        ));
+        assert_eq!(LightClient::dag_root(1), Some(mock::zero_dag().0));
+        // The dataset of the second epoch is deeper than the one of the first.
+        assert_noop!(
+            import(h1.clone(), dag_proof()),
+            Error::<Test>::InvalidDagProof
+        );
+        let mut proof = dag_proof();
+        let (_, path) = mock::zero_dag();
+        let mut top = [0u8; 64];
+        top[..32].copy_from_slice(mock::zero_dag().0.as_bytes());
+        top[32..].copy_from_slice(mock::zero_dag().0.as_bytes());
+        for p in proof.paths.iter_mut() {
+            *p = path.clone();
+            p.push(mock::zero_dag().0);
+        }
+        assert_ok!(LightClient::set_dag_roots(
+            RawOrigin::Root.into(),
+            1,
+            vec![hash(&top)]
+        ));
+        assert_noop!(import(h1, proof), Error::<Test>::InsufficientWork);
+    });
+}
+
+#[test]
+fn headers_are_finalized_at_depth() {
+    let checkpoint = header(H256::zero(), 100);
+    new_test_ext(checkpoint.clone()).execute_with(|| {
+        let h0 = hash(&checkpoint);
+        assert_eq!(
+            LightClient::finalized_header(),
+            HeaderId {
+                number: 100,
+                hash: h0
+            }
+        );
+
+        let h1 = header(h0, 101);
+        let h2 = header(hash(&h1), 102);
+        let h3 = header(hash(&h2), 103);
+
+        assert_noop!(
+            LightClient::import_header(RawOrigin::Signed(1).into(), h2.clone(), dag_proof()),
+            Error::<Test>::UnknownParent
+        );
+
+        import(&h1);
+        import(&h2);
+        assert_eq!(
+            LightClient::best_header(),
+            HeaderId {
+                number: 102,
+                hash: hash(&h2)
+            }
+        );
+        assert_eq!(LightClient::finalized_header().number, 100);
+
+        import(&h3);
+        assert_eq!(
+            LightClient::finalized_header(),
+            HeaderId {
+                number: 101,
+                hash: hash(&h1)
+            }
+        );
+        assert_eq!(LightClient::finalized_hash(101), Some(hash(&h1)));
+
+        // A heavier fork becomes the best chain, but cannot revert finalized headers.
+        let f2 = fork_header(hash(&h1), 102);
+        let f3 = header(hash(&f2), 103);
+        let f4 = header(hash(&f3), 104);
+        import(&f2);
+        import(&f3);
+        assert_eq!(LightClient::best_header().hash, hash(&h3));
+        import(&f4);
+        assert_eq!(
+            LightClient::best_header(),
+            HeaderId {
+                number: 104,
+                hash: hash(&f4)
+            }
+        );
+        assert_eq!(LightClient::finalized_hash(102), Some(hash(&f2)));
+        assert_noop!(
+            LightClient::import_header(
+                RawOrigin::Signed(1).into(),
+                fork_header(h0, 101),
+                dag_proof()
+            ),
+            Error::<Test>::AncientHeader
+        );
+
+        // Headers may only be so far above the finalized header, which bounds the ancestors
+        // walked to finalize them.
+        let margin = u64::from(ConfirmationDepth::get() + MAX_UNFINALIZED_MARGIN);
+        assert_noop!(
+            LightClient::import_header(
+                RawOrigin::Signed(1).into(),
+                header(H256::repeat_byte(1), 102 + margin + 1),
+                dag_proof()
+            ),
+            Error::<Test>::TooFarAhead
+        );
+        assert_noop!(
+            LightClient::import_header(
+                RawOrigin::Signed(1).into(),
+                header(H256::repeat_byte(1), 102 + margin),
+                dag_proof()
+            ),
+            Error::<Test>::UnknownParent
+        );
+    });
+}
+
+#[test]
+fn old_headers_are_pruned() {
+    let checkpoint = header(H256::zero(), 100);
+    new_test_ext(checkpoint.clone()).execute_with(|| {
+        let mut chain = vec![checkpoint.clone()];
+        let fork = fork_header(hash(&checkpoint), 101);
+        import(&fork);
+        for number in 101..=111 {
+            let next = header(hash(chain.last().unwrap()), number);
+            import(&next);
+            chain.push(next);
+        }
+
+        // 109 is finalized. Headers are pruned before finalizing, so the three finalized headers
+        // before 108 are kept.
+        assert_eq!(LightClient::finalized_header().number, 109);
+        for number in 100..=104 {
+            assert_eq!(LightClient::headers(hash(&chain[number - 100])), None);
+            assert_eq!(LightClient::finalized_hash(number as u64), None);
+        }
+        assert_eq!(LightClient::headers(hash(&fork)), None);
+        for number in 105..=109 {
+            assert!(LightClient::headers(hash(&chain[number - 100])).is_some());
+            assert_eq!(
+                LightClient::finalized_hash(number as u64),
+                Some(hash(&chain[number - 100]))
+            );
+        }
+
+        // A new checkpoint drops the previous chain.
+        let checkpoint = header(H256::zero(), 50);
+        assert_ok!(LightClient::set_checkpoint(
+            RawOrigin::Root.into(),
+            checkpoint.clone(),
+            U256::from(1_000)
+        ));
+        for header in &chain[5..] {
+            assert_eq!(LightClient::headers(hash(header)), None);
+        }
+        assert_eq!(LightClient::finalized_hash(109), None);
+        assert!(LightClient::headers(hash(&checkpoint)).is_some());
+        assert_eq!(LightClient::finalized_hash(50), Some(hash(&checkpoint)));
+    });
+}
+
+#[test]
+fn finalized_deposits_are_proven() {
+    let checkpoint = header(H256::zero(), 100);
+    new_test_ext(checkpoint.clone()).execute_with(|| {
+        let contract = H160::repeat_byte(0xbb);
+        let token = H160::repeat_byte(0xcc);
+        let deposit = deposit_receipt(contract, token, 7, 500);
+        // The second transaction and receipt are typed (EIP-2718), and the deposit is made into
+        // another contract.
+        let mut typed = vec![0x02];
+        typed.extend(deposit_receipt(H160::repeat_byte(0xdd), token, 7, 500));
+        let (receipts_root, [receipt0, receipt1]) = trie_of([deposit, typed]);
+        let tx0 = b"first transaction, long enough not to be inlined".to_vec();
+        let tx1 = b"\x02second transaction, long enough not to be inlined".to_vec();
+        let (transactions_root, [tx_proof0, tx_proof1]) = trie_of([tx0.clone(), tx1.clone()]);
+
+        let h1 = header_with_roots(hash(&checkpoint), 101, transactions_root, receipts_root);
+        let block_hash = hash(&h1);
+        let proof = |tx_index, transaction_proof, receipt_proof| DepositProof {
+            block_hash,
+            tx_index,
+            log_index: 0,
+            transaction_proof,
+            receipt_proof,
+        };
+        let verify = |p: &DepositProof| <LightClient as VerifyDeposit<u64, u64>>::verify(p);
+
+        assert_eq!(
+            verify(&proof(0, tx_proof0.clone(), receipt0.clone())),
+            Err(Error::<Test>::UnknownHeader.into())
+        );
+        import(&h1);
+        assert_eq!(
+            verify(&proof(0, tx_proof0.clone(), receipt0.clone())),
+            Err(Error::<Test>::HeaderNotFinalized.into())
+        );
+
+        let h2 = header(block_hash, 102);
+        let h3 = header(hash(&h2), 103);
+        import(&h2);
+        import(&h3);
+
+        // The deposit is identified the way relayers identify it.
+        assert_eq!(
+            verify(&proof(0, tx_proof0.clone(), receipt0.clone())),
+            Ok(ProvenDeposit {
+                deposit_id: deposit_id_of(hash(&tx0), 0),
+                token,
+                account: 7,
+                amount: 500,
+            })
+        );
+        assert_eq!(
+            verify(&proof(0, tx_proof1.clone(), receipt0.clone())),
+            Err(Error::<Test>::InvalidProof.into())
+        );
+        assert_eq!(
+            verify(&proof(1, tx_proof1.clone(), receipt0.clone())),
+            Err(Error::<Test>::InvalidProof.into())
+        );
+        assert_eq!(
+            verify(&proof(1, tx_proof1.clone(), receipt1.clone())),
+            Err(Error::<Test>::NotDepositEvent.into())
+        );
+
+        assert_ok!(LightClient::set_bridge_contract(
+            RawOrigin::Root.into(),
+            H160::repeat_byte(0xdd)
+        ));
+        assert_eq!(
+            verify(&proof(1, tx_proof1, receipt1)).map(|d| d.deposit_id),
+            Ok(deposit_id_of(hash(&tx1), 0))
+        );
+        assert_eq!(
+            verify(&DepositProof {
+                log_index: 1,
+                ..proof(0, tx_proof0, receipt0)
+            }),
+            Err(Error::<Test>::InvalidReceipt.into())
+        );
+    });
+}
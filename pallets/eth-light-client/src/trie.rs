@@ -0,0 +1,110 @@
+// This file is part of Curio project.
+
+// Copyright (C) 2021 Mixbytes.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Verification of Ethereum Merkle-Patricia trie proofs.
+
+use crate::rlp::Rlp;
+use sp_core::H256;
+use sp_io::hashing::keccak_256;
+use sp_std::prelude::*;
+
+/// The proof does not prove any value under the key.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct InvalidProof;
+
+impl From<crate::rlp::DecoderError> for InvalidProof {
+    fn from(_: crate::rlp::DecoderError) -> Self {
+        InvalidProof
+    }
+}
+
+fn nibbles(key: &[u8]) -> Vec<u8> {
+    key.iter().flat_map(|b| vec![b >> 4, b & 0x0f]).collect()
+}
+
+/// Decode the hex-prefix encoded path of a leaf or extension node into its nibbles and
+/// whether the node is a leaf.
+fn decode_path(encoded: &[u8]) -> Result<(Vec<u8>, bool), InvalidProof> {
+    let first = *encoded.first().ok_or(InvalidProof)?;
+    let flag = first >> 4;
+    if flag > 3 {
+        return Err(InvalidProof);
+    }
+    let mut path = nibbles(&encoded[1..]);
+    if flag & 1 == 1 {
+        path.insert(0, first & 0x0f);
+    }
+    Ok((path, flag & 2 == 2))
+}
+
+/// Verify `proof`, the nodes on the path from `root` to `key`, and return the value stored
+/// under `key`.
+pub fn verify_proof(root: &H256, key: &[u8], proof: &[Vec<u8>]) -> Result<Vec<u8>, InvalidProof> {
+    let key = nibbles(key);
+    let mut position = 0;
+    let mut proof = proof.iter();
+    let mut node = proof.next().ok_or(InvalidProof)?.as_slice();
+    if H256(keccak_256(node)) != *root {
+        return Err(InvalidProof);
+    }
+
+    loop {
+        let items = Rlp::new(node)?.items()?;
+        let child = match items.len() {
+            17 => {
+                if position == key.len() {
+                    let value = items[16].data()?;
+                    if value.is_empty() {
+                        return Err(InvalidProof);
+                    }
+                    return Ok(value.to_vec());
+                }
+                let child = items[key[position] as usize];
+                position += 1;
+                child
+            }
+            2 => {
+                let (path, is_leaf) = decode_path(items[0].data()?)?;
+                if !key[position..].starts_with(&path) {
+                    return Err(InvalidProof);
+                }
+                position += path.len();
+                if is_leaf {
+                    if position != key.len() {
+                        return Err(InvalidProof);
+                    }
+                    return Ok(items[1].data()?.to_vec());
+                }
+                items[1]
+            }
+            _ => return Err(InvalidProof),
+        };
+
+        // Nodes shorter than a hash are embedded in their parent, others are referenced by
+        // their hash.
+        node = if child.is_list() {
+            child.as_raw()
+        } else {
+            let hash = child.as_h256()?;
+            let next = proof.next().ok_or(InvalidProof)?.as_slice();
+            if H256(keccak_256(next)) != hash {
+                return Err(InvalidProof);
+            }
+            next
+        };
+    }
+}
@@ -0,0 +1,38 @@
+// This file is part of Curio project.
+
+// Copyright (C) 2021 Mixbytes.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Runtime API definition for the investor whitelist.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+
+pub use pallet_kyc::{Investor, KycStatus};
+
+sp_api::decl_runtime_apis! {
+    /// The API to query the investor whitelist.
+    pub trait KycApi<AccountId, BlockNumber> where
+        AccountId: Codec,
+        BlockNumber: Codec,
+    {
+        /// Whitelist entry of `account`, if any.
+        fn investor(account: AccountId) -> Option<Investor<BlockNumber>>;
+
+        /// Whether `account` is verified and its verification has not expired.
+        fn is_verified(account: AccountId) -> bool;
+    }
+}
@@ -0,0 +1,390 @@
+// This file is part of Curio project.
+
+// Copyright (C) 2021 Mixbytes.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # KYC
+//!
+//! Keeps the whitelist of the investors verified by KYC providers. The set of providers is
+//! managed by `ManagerOrigin`, and any provider may add, update or remove whitelist entries.
+//!
+//! An entry records the status of the investor and the block its verification expires at. An
+//! investor is verified while its status is `Verified` and the expiry is not reached.
+//!
+//! `CheckKyc` rejects the transactions whose accounts are not verified. The runtime tells which
+//! accounts of a call must be verified through `KycGate`, separately for the calls which are
+//! always gated and for the transfers, which are gated only while `RestrictTransfers` is set.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Decode, Encode};
+use frame_support::{
+    decl_error, decl_event, decl_module, decl_storage, ensure,
+    traits::{EnsureOrigin, Get},
+    weights::Weight,
+    RuntimeDebug,
+};
+use sp_runtime::{
+    traits::{DispatchInfoOf, SignedExtension},
+    transaction_validity::{
+        InvalidTransaction, TransactionValidity, TransactionValidityError, ValidTransaction,
+    },
+    DispatchResult,
+};
+use sp_std::{marker::PhantomData, prelude::*};
+
+mod default_weights;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+/// Status of a whitelisted investor.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum KycStatus {
+    /// The investor passed the checks of the provider.
+    Verified,
+    /// The verification is suspended until the provider reviews it.
+    Suspended,
+}
+
+/// An entry of the whitelist.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub struct Investor<BlockNumber> {
+    pub status: KycStatus,
+    /// Block the verification expires at.
+    pub expiry: BlockNumber,
+}
+
+/// Tells which accounts must be verified for a call to be submitted.
+///
+/// `None` rejects the call, for the calls whose accounts cannot be told.
+pub trait KycGate<AccountId, Call> {
+    /// Accounts which must be verified for `who` to submit `call`.
+    fn required(who: &AccountId, call: &Call) -> Option<Vec<AccountId>>;
+
+    /// Accounts which must be verified for `who` to submit `call` while transfers are
+    /// restricted.
+    fn required_for_transfer(who: &AccountId, call: &Call) -> Option<Vec<AccountId>>;
+}
+
+impl<AccountId, Call> KycGate<AccountId, Call> for () {
+    fn required(_: &AccountId, _: &Call) -> Option<Vec<AccountId>> {
+        Some(Vec::new())
+    }
+
+    fn required_for_transfer(_: &AccountId, _: &Call) -> Option<Vec<AccountId>> {
+        Some(Vec::new())
+    }
+}
+
+pub trait WeightInfo {
+    fn add_provider(p: u32) -> Weight;
+    fn remove_provider(p: u32) -> Weight;
+    fn set_investor() -> Weight;
+    fn remove_investor() -> Weight;
+    fn set_transfers_restricted() -> Weight;
+}
+
+pub trait Trait: frame_system::Trait {
+    /// The overarching event type.
+    type Event: From<Event<Self>> + Into<<Self as frame_system::Trait>::Event>;
+
+    /// The origin which may add or remove KYC providers.
+    type ManagerOrigin: EnsureOrigin<Self::Origin>;
+
+    /// Maximum number of KYC providers.
+    type MaxProviders: Get<u32>;
+
+    /// The accounts of the calls which must be verified.
+    type Gate: KycGate<Self::AccountId, <Self as frame_system::Trait>::Call>;
+
+    /// Weight information for extrinsics in this pallet.
+    type WeightInfo: WeightInfo;
+}
+
+decl_storage! {
+    trait Store for Module<T: Trait> as Kyc {
+        /// The current set of KYC providers, sorted.
+        pub Providers get(fn providers): Vec<T::AccountId>;
+
+        /// The whitelist of investors.
+        pub Investors get(fn investor):
+            map hasher(blake2_128_concat) T::AccountId => Option<Investor<T::BlockNumber>>;
+
+        /// Whether transfers between accounts are gated by the whitelist too.
+        pub RestrictTransfers get(fn transfers_restricted) config(): bool;
+    }
+    add_extra_genesis {
+        config(providers): Vec<T::AccountId>;
+        /// Investors verified at genesis, with the block their verification expires at.
+        config(investors): Vec<(T::AccountId, T::BlockNumber)>;
+        build(|config| {
+            let mut providers = config.providers.clone();
+            providers.sort();
+            providers.dedup();
+            assert!(
+                providers.len() <= T::MaxProviders::get() as usize,
+                "too many providers in genesis config",
+            );
+            <Providers<T>>::put(providers);
+
+            for (who, expiry) in &config.investors {
+                let investor = Investor {
+                    status: KycStatus::Verified,
+                    expiry: *expiry,
+                };
+                <Investors<T>>::insert(who, investor);
+            }
+        });
+    }
+}
+
+decl_event!(
+    pub enum Event<T>
+    where
+        AccountId = <T as frame_system::Trait>::AccountId,
+        BlockNumber = <T as frame_system::Trait>::BlockNumber,
+    {
+        /// A KYC provider was added. \[provider\]
+        ProviderAdded(AccountId),
+        /// A KYC provider was removed. \[provider\]
+        ProviderRemoved(AccountId),
+        /// A provider set the whitelist entry of an investor.
+        /// \[provider, investor, status, expiry\]
+        InvestorSet(AccountId, AccountId, KycStatus, BlockNumber),
+        /// A provider removed an investor from the whitelist. \[provider, investor\]
+        InvestorRemoved(AccountId, AccountId),
+        /// Transfers were gated or released. \[restricted\]
+        TransferRestrictionChanged(bool),
+    }
+);
+
+decl_error! {
+    pub enum Error for Module<T: Trait> {
+        /// The account is already a provider.
+        AlreadyProvider,
+        /// The account is not a provider.
+        NotProvider,
+        /// The provider set is full.
+        TooManyProviders,
+        /// The expiry is not in the future.
+        ExpiryInPast,
+        /// The account is not whitelisted.
+        UnknownInvestor,
+    }
+}
+
+decl_module! {
+    pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+        type Error = Error<T>;
+
+        /// Maximum number of KYC providers.
+        const MaxProviders: u32 = T::MaxProviders::get();
+
+        fn deposit_event() = default;
+
+        /// Add `who` to the KYC providers.
+        #[weight = T::WeightInfo::add_provider(T::MaxProviders::get())]
+        fn add_provider(origin, who: T::AccountId) -> DispatchResult {
+            T::ManagerOrigin::ensure_origin(origin)?;
+
+            let mut providers = Self::providers();
+            let location = providers.binary_search(&who).err().ok_or(Error::<T>::AlreadyProvider)?;
+            ensure!(
+                providers.len() < T::MaxProviders::get() as usize,
+                Error::<T>::TooManyProviders
+            );
+            providers.insert(location, who.clone());
+            <Providers<T>>::put(providers);
+
+            Self::deposit_event(RawEvent::ProviderAdded(who));
+            Ok(())
+        }
+
+        /// Remove `who` from the KYC providers.
+        ///
+        /// The entries set by `who` are kept.
+        #[weight = T::WeightInfo::remove_provider(T::MaxProviders::get())]
+        fn remove_provider(origin, who: T::AccountId) -> DispatchResult {
+            T::ManagerOrigin::ensure_origin(origin)?;
+
+            let mut providers = Self::providers();
+            let location = providers.binary_search(&who).ok().ok_or(Error::<T>::NotProvider)?;
+            providers.remove(location);
+            <Providers<T>>::put(providers);
+
+            Self::deposit_event(RawEvent::ProviderRemoved(who));
+            Ok(())
+        }
+
+        /// Set the status of `who` in the whitelist and the block its verification expires at.
+        #[weight = T::WeightInfo::set_investor()]
+        fn set_investor(
+            origin,
+            who: T::AccountId,
+            status: KycStatus,
+            expiry: T::BlockNumber
+        ) -> DispatchResult {
+            let provider = EnsureProvider::<T>::ensure_origin(origin)?;
+            ensure!(
+                expiry > <frame_system::Module<T>>::block_number(),
+                Error::<T>::ExpiryInPast
+            );
+
+            <Investors<T>>::insert(&who, Investor { status, expiry });
+
+            Self::deposit_event(RawEvent::InvestorSet(provider, who, status, expiry));
+            Ok(())
+        }
+
+        /// Remove `who` from the whitelist.
+        #[weight = T::WeightInfo::remove_investor()]
+        fn remove_investor(origin, who: T::AccountId) -> DispatchResult {
+            let provider = EnsureProvider::<T>::ensure_origin(origin)?;
+            ensure!(<Investors<T>>::contains_key(&who), Error::<T>::UnknownInvestor);
+
+            <Investors<T>>::remove(&who);
+
+            Self::deposit_event(RawEvent::InvestorRemoved(provider, who));
+            Ok(())
+        }
+
+        /// Set whether transfers between accounts are gated by the whitelist.
+        #[weight = T::WeightInfo::set_transfers_restricted()]
+        fn set_transfers_restricted(origin, restricted: bool) -> DispatchResult {
+            T::ManagerOrigin::ensure_origin(origin)?;
+
+            RestrictTransfers::put(restricted);
+
+            Self::deposit_event(RawEvent::TransferRestrictionChanged(restricted));
+            Ok(())
+        }
+    }
+}
+
+impl<T: Trait> Module<T> {
+    /// Whether `who` is a KYC provider.
+    pub fn is_provider(who: &T::AccountId) -> bool {
+        Self::providers().binary_search(who).is_ok()
+    }
+
+    /// Whether `who` is verified and its verification has not expired.
+    pub fn is_verified(who: &T::AccountId) -> bool {
+        Self::investor(who).map_or(false, |investor| {
+            investor.status == KycStatus::Verified
+                && investor.expiry > <frame_system::Module<T>>::block_number()
+        })
+    }
+}
+
+/// Ensure the origin is a signed KYC provider.
+pub struct EnsureProvider<T>(PhantomData<T>);
+impl<T: Trait> EnsureOrigin<T::Origin> for EnsureProvider<T> {
+    type Success = T::AccountId;
+
+    fn try_origin(o: T::Origin) -> Result<Self::Success, T::Origin> {
+        o.into().and_then(|o| match o {
+            frame_system::RawOrigin::Signed(who) if Module::<T>::is_provider(&who) => Ok(who),
+            r => Err(T::Origin::from(r)),
+        })
+    }
+
+    #[cfg(feature = "runtime-benchmarks")]
+    fn successful_origin() -> T::Origin {
+        let who = Module::<T>::providers()
+            .first()
+            .cloned()
+            .unwrap_or_default();
+        T::Origin::from(frame_system::RawOrigin::Signed(who))
+    }
+}
+
+/// Custom validity errors of the KYC checks.
+#[repr(u8)]
+pub enum ValidityError {
+    /// An account of the call is not verified.
+    NotVerified = 0,
+}
+
+impl From<ValidityError> for u8 {
+    fn from(err: ValidityError) -> Self {
+        err as u8
+    }
+}
+
+/// Signed extension rejecting the calls whose accounts, as told by `Gate`, are not verified.
+#[derive(Encode, Decode, Clone, Eq, PartialEq)]
+pub struct CheckKyc<T: Trait + Send + Sync>(PhantomData<T>);
+
+impl<T: Trait + Send + Sync> CheckKyc<T> {
+    /// Create new `SignedExtension` to check the whitelist.
+    pub fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<T: Trait + Send + Sync> Default for CheckKyc<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Trait + Send + Sync> sp_std::fmt::Debug for CheckKyc<T> {
+    #[cfg(feature = "std")]
+    fn fmt(&self, f: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+        write!(f, "CheckKyc")
+    }
+
+    #[cfg(not(feature = "std"))]
+    fn fmt(&self, _: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+        Ok(())
+    }
+}
+
+impl<T: Trait + Send + Sync> SignedExtension for CheckKyc<T> {
+    const IDENTIFIER: &'static str = "CheckKyc";
+    type AccountId = T::AccountId;
+    type Call = <T as frame_system::Trait>::Call;
+    type AdditionalSigned = ();
+    type Pre = ();
+
+    fn additional_signed(&self) -> Result<(), TransactionValidityError> {
+        Ok(())
+    }
+
+    fn validate(
+        &self,
+        who: &Self::AccountId,
+        call: &Self::Call,
+        _info: &DispatchInfoOf<Self::Call>,
+        _len: usize,
+    ) -> TransactionValidity {
+        let mut required = T::Gate::required(who, call);
+        if Module::<T>::transfers_restricted() {
+            required = required.and_then(|mut required| {
+                required.extend(T::Gate::required_for_transfer(who, call)?);
+                Some(required)
+            });
+        }
+        let verified = required.map_or(false, |required| {
+            required.iter().all(Module::<T>::is_verified)
+        });
+        if !verified {
+            return Err(InvalidTransaction::Custom(ValidityError::NotVerified.into()).into());
+        }
+        Ok(ValidTransaction::default())
+    }
+}
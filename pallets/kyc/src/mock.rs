@@ -0,0 +1,151 @@
+// This file is part of Curio project.
+
+// Copyright (C) 2021 Mixbytes.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use crate as kyc;
+use frame_support::{
+    impl_outer_dispatch, impl_outer_event, impl_outer_origin, parameter_types, weights::Weight,
+};
+use frame_system::EnsureRoot;
+use pallet_balances as balances;
+use sp_core::H256;
+use sp_runtime::{
+    testing::Header,
+    traits::{BlakeTwo256, IdentityLookup},
+    Perbill,
+};
+
+impl_outer_origin! {
+    pub enum Origin for Test where system = frame_system {}
+}
+
+impl_outer_event! {
+    pub enum TestEvent for Test {
+        frame_system<T>,
+        kyc<T>,
+        balances<T>,
+    }
+}
+
+impl_outer_dispatch! {
+    pub enum OuterCall for Test where origin: Origin {
+        frame_system::System,
+        balances::Balances,
+    }
+}
+
+#[derive(Clone, Eq, PartialEq)]
+pub struct Test;
+
+parameter_types! {
+    pub const BlockHashCount: u64 = 250;
+    pub const MaximumBlockWeight: Weight = 1024;
+    pub const MaximumBlockLength: u32 = 2 * 1024;
+    pub const AvailableBlockRatio: Perbill = Perbill::one();
+}
+
+impl frame_system::Trait for Test {
+    type BaseCallFilter = ();
+    type Origin = Origin;
+    type Call = OuterCall;
+    type Index = u64;
+    type BlockNumber = u64;
+    type Hash = H256;
+    type Hashing = BlakeTwo256;
+    type AccountId = u64;
+    type Lookup = IdentityLookup<Self::AccountId>;
+    type Header = Header;
+    type Event = TestEvent;
+    type BlockHashCount = BlockHashCount;
+    type MaximumBlockWeight = MaximumBlockWeight;
+    type DbWeight = ();
+    type BlockExecutionWeight = ();
+    type ExtrinsicBaseWeight = ();
+    type MaximumExtrinsicWeight = MaximumBlockWeight;
+    type MaximumBlockLength = MaximumBlockLength;
+    type AvailableBlockRatio = AvailableBlockRatio;
+    type Version = ();
+    type PalletInfo = ();
+    type AccountData = balances::AccountData<u64>;
+    type OnNewAccount = ();
+    type OnKilledAccount = ();
+    type SystemWeightInfo = ();
+}
+
+parameter_types! {
+    pub const ExistentialDeposit: u64 = 1;
+}
+
+impl balances::Trait for Test {
+    type MaxLocks = ();
+    type Balance = u64;
+    type Event = TestEvent;
+    type DustRemoval = ();
+    type ExistentialDeposit = ExistentialDeposit;
+    type AccountStore = System;
+    type WeightInfo = ();
+}
+
+/// Gates remarks behind the verification of their signer, and transfers behind the
+/// verification of both parties. The recipient of transfers to account 0 cannot be told.
+pub struct TestGate;
+impl KycGate<u64, OuterCall> for TestGate {
+    fn required(who: &u64, call: &OuterCall) -> Option<Vec<u64>> {
+        match call {
+            OuterCall::System(frame_system::Call::remark(..)) => Some(vec![*who]),
+            _ => Some(Vec::new()),
+        }
+    }
+
+    fn required_for_transfer(who: &u64, call: &OuterCall) -> Option<Vec<u64>> {
+        match call {
+            OuterCall::Balances(balances::Call::transfer(0, _)) => None,
+            OuterCall::Balances(balances::Call::transfer(dest, _)) => Some(vec![*who, *dest]),
+            _ => Some(Vec::new()),
+        }
+    }
+}
+
+parameter_types! {
+    pub const MaxProviders: u32 = 2;
+}
+
+impl Trait for Test {
+    type Event = TestEvent;
+    type ManagerOrigin = EnsureRoot<u64>;
+    type MaxProviders = MaxProviders;
+    type Gate = TestGate;
+    type WeightInfo = ();
+}
+
+pub type System = frame_system::Module<Test>;
+pub type Balances = balances::Module<Test>;
+pub type Kyc = Module<Test>;
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+    let mut t = frame_system::GenesisConfig::default()
+        .build_storage::<Test>()
+        .unwrap();
+    GenesisConfig::<Test> {
+        providers: vec![10],
+        investors: vec![(1, 100)],
+        restrict_transfers: false,
+    }
+    .assimilate_storage(&mut t)
+    .unwrap();
+    t.into()
+}
@@ -0,0 +1,177 @@
+// This file is part of Curio project.
+
+// Copyright (C) 2021 Mixbytes.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use frame_support::{assert_noop, assert_ok, weights::DispatchInfo};
+use frame_system::RawOrigin;
+use mock::{new_test_ext, Kyc, OuterCall, System, Test, TestEvent};
+use sp_runtime::traits::BadOrigin;
+
+fn kyc_events() -> Vec<RawEvent<u64, u64>> {
+    System::events()
+        .into_iter()
+        .filter_map(|record| match record.event {
+            TestEvent::kyc(event) => Some(event),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn providers_are_managed_by_governance() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_eq!(Kyc::providers(), vec![10]);
+
+        assert_noop!(
+            Kyc::add_provider(RawOrigin::Signed(10).into(), 11),
+            BadOrigin
+        );
+        assert_noop!(
+            Kyc::add_provider(RawOrigin::Root.into(), 10),
+            Error::<Test>::AlreadyProvider
+        );
+        assert_ok!(Kyc::add_provider(RawOrigin::Root.into(), 11));
+        assert_noop!(
+            Kyc::add_provider(RawOrigin::Root.into(), 12),
+            Error::<Test>::TooManyProviders
+        );
+        assert_eq!(Kyc::providers(), vec![10, 11]);
+
+        assert_noop!(
+            Kyc::remove_provider(RawOrigin::Signed(11).into(), 10),
+            BadOrigin
+        );
+        assert_ok!(Kyc::remove_provider(RawOrigin::Root.into(), 10));
+        assert_noop!(
+            Kyc::remove_provider(RawOrigin::Root.into(), 10),
+            Error::<Test>::NotProvider
+        );
+        assert_eq!(Kyc::providers(), vec![11]);
+        assert!(!Kyc::is_provider(&10));
+
+        assert_eq!(
+            kyc_events(),
+            vec![RawEvent::ProviderAdded(11), RawEvent::ProviderRemoved(10)]
+        );
+    });
+}
+
+#[test]
+fn providers_manage_the_whitelist() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert!(Kyc::is_verified(&1));
+        assert!(!Kyc::is_verified(&2));
+
+        assert_noop!(
+            Kyc::set_investor(RawOrigin::Signed(2).into(), 2, KycStatus::Verified, 50),
+            BadOrigin
+        );
+        assert_noop!(
+            Kyc::set_investor(RawOrigin::Root.into(), 2, KycStatus::Verified, 50),
+            BadOrigin
+        );
+        assert_noop!(
+            Kyc::set_investor(RawOrigin::Signed(10).into(), 2, KycStatus::Verified, 1),
+            Error::<Test>::ExpiryInPast
+        );
+        assert_ok!(Kyc::set_investor(
+            RawOrigin::Signed(10).into(),
+            2,
+            KycStatus::Verified,
+            50
+        ));
+        assert!(Kyc::is_verified(&2));
+
+        assert_ok!(Kyc::set_investor(
+            RawOrigin::Signed(10).into(),
+            1,
+            KycStatus::Suspended,
+            100
+        ));
+        assert!(!Kyc::is_verified(&1));
+        assert_eq!(
+            Kyc::investor(1),
+            Some(Investor {
+                status: KycStatus::Suspended,
+                expiry: 100
+            })
+        );
+
+        System::set_block_number(50);
+        assert!(!Kyc::is_verified(&2));
+
+        assert_ok!(Kyc::remove_investor(RawOrigin::Signed(10).into(), 2));
+        assert_noop!(
+            Kyc::remove_investor(RawOrigin::Signed(10).into(), 2),
+            Error::<Test>::UnknownInvestor
+        );
+        assert_eq!(Kyc::investor(2), None);
+
+        assert_eq!(
+            kyc_events(),
+            vec![
+                RawEvent::InvestorSet(10, 2, KycStatus::Verified, 50),
+                RawEvent::InvestorSet(10, 1, KycStatus::Suspended, 100),
+                RawEvent::InvestorRemoved(10, 2),
+            ]
+        );
+    });
+}
+
+#[test]
+fn check_kyc_rejects_unverified_accounts() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let check = CheckKyc::<Test>::new();
+        let info = DispatchInfo::default();
+        let not_verified =
+            Err(InvalidTransaction::Custom(ValidityError::NotVerified.into()).into());
+
+        let remark: OuterCall = frame_system::Call::remark(vec![]).into();
+        assert_eq!(
+            check.validate(&1, &remark, &info, 0),
+            Ok(Default::default())
+        );
+        assert_eq!(check.validate(&2, &remark, &info, 0), not_verified);
+
+        let transfer: OuterCall = pallet_balances::Call::transfer(2, 10).into();
+        assert!(check.validate(&1, &transfer, &info, 0).is_ok());
+
+        assert_noop!(
+            Kyc::set_transfers_restricted(RawOrigin::Signed(10).into(), true),
+            BadOrigin
+        );
+        assert_ok!(Kyc::set_transfers_restricted(RawOrigin::Root.into(), true));
+        assert_eq!(check.validate(&1, &transfer, &info, 0), not_verified);
+
+        assert_ok!(Kyc::set_investor(
+            RawOrigin::Signed(10).into(),
+            2,
+            KycStatus::Verified,
+            50
+        ));
+        assert!(check.validate(&1, &transfer, &info, 0).is_ok());
+        let unknown: OuterCall = pallet_balances::Call::transfer(0, 10).into();
+        assert_eq!(check.validate(&1, &unknown, &info, 0), not_verified);
+        assert_eq!(
+            kyc_events().first(),
+            Some(&RawEvent::TransferRestrictionChanged(true))
+        );
+    });
+}
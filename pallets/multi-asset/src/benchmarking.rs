@@ -0,0 +1,202 @@
+// This file is part of Curio project.
+
+// Copyright (C) 2021 Mixbytes.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Benchmarks for the multi-asset pallet.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use frame_benchmarking::{account, benchmarks, whitelisted_caller};
+use frame_support::traits::UnfilteredDispatchable;
+use frame_system::RawOrigin;
+
+const SEED: u32 = 0;
+
+/// Create an asset issued by the `issuer` account, returning its id and issuer.
+fn create_asset<T: Trait>() -> (T::AssetId, T::AccountId) {
+    let issuer: T::AccountId = account("issuer", 0, SEED);
+    let id = Module::<T>::next_asset_id();
+    NextAssetId::<T>::put(id.saturating_add(One::one()));
+    Assets::<T>::insert(
+        id,
+        AssetDetails {
+            issuer: issuer.clone(),
+            supply: Zero::zero(),
+            min_balance: 10u32.into(),
+            accounts: 0,
+            is_frozen: false,
+        },
+    );
+    (id, issuer)
+}
+
+/// Mint some of asset `id` to the whitelisted caller, returning the caller and the amount.
+fn fund_caller<T: Trait>(id: T::AssetId) -> Result<(T::AccountId, T::Balance), &'static str> {
+    let caller: T::AccountId = whitelisted_caller();
+    let amount: T::Balance = 1_000u32.into();
+    Module::<T>::do_mint(id, &caller, amount)?;
+    Ok((caller, amount))
+}
+
+benchmarks! {
+    _ { }
+
+    create {
+        let issuer: T::AccountId = account("issuer", 0, SEED);
+        let id = Module::<T>::next_asset_id();
+        let call = Call::<T>::create(T::Lookup::unlookup(issuer), 10u32.into());
+        let origin = T::CreateOrigin::successful_origin();
+    }: { call.dispatch_bypass_filter(origin)? }
+    verify {
+        assert!(Module::<T>::asset(id).is_some());
+    }
+
+    mint {
+        let (id, issuer) = create_asset::<T>();
+        let beneficiary: T::AccountId = account("beneficiary", 0, SEED);
+        let amount: T::Balance = 1_000u32.into();
+    }: _(RawOrigin::Signed(issuer), id, T::Lookup::unlookup(beneficiary.clone()), amount)
+    verify {
+        assert_eq!(Module::<T>::balance(id, &beneficiary), amount);
+    }
+
+    burn {
+        let (id, issuer) = create_asset::<T>();
+        let (caller, amount) = fund_caller::<T>(id)?;
+        // The remainder is below the minimum balance and gets burned too.
+        let burned = amount - 1u32.into();
+    }: _(RawOrigin::Signed(issuer), id, T::Lookup::unlookup(caller.clone()), burned)
+    verify {
+        assert!(Module::<T>::balance(id, &caller).is_zero());
+    }
+
+    transfer {
+        let (id, _) = create_asset::<T>();
+        let (caller, amount) = fund_caller::<T>(id)?;
+        let dest: T::AccountId = account("dest", 0, SEED);
+        // The remainder is below the minimum balance and gets transferred too.
+        let transferred = amount - 1u32.into();
+    }: _(RawOrigin::Signed(caller), id, T::Lookup::unlookup(dest.clone()), transferred)
+    verify {
+        assert_eq!(Module::<T>::balance(id, &dest), amount);
+    }
+
+    transfer_keep_alive {
+        let (id, _) = create_asset::<T>();
+        let (caller, amount) = fund_caller::<T>(id)?;
+        let dest: T::AccountId = account("dest", 0, SEED);
+        let transferred = amount / 2u32.into();
+    }: _(RawOrigin::Signed(caller), id, T::Lookup::unlookup(dest.clone()), transferred)
+    verify {
+        assert_eq!(Module::<T>::balance(id, &dest), transferred);
+    }
+
+    freeze {
+        let (id, issuer) = create_asset::<T>();
+        let (caller, _) = fund_caller::<T>(id)?;
+    }: _(RawOrigin::Signed(issuer), id, T::Lookup::unlookup(caller.clone()))
+    verify {
+        assert!(Module::<T>::account(id, &caller).is_frozen);
+    }
+
+    thaw {
+        let (id, issuer) = create_asset::<T>();
+        let (caller, _) = fund_caller::<T>(id)?;
+        Account::<T>::mutate(id, &caller, |account| account.is_frozen = true);
+    }: _(RawOrigin::Signed(issuer), id, T::Lookup::unlookup(caller.clone()))
+    verify {
+        assert!(!Module::<T>::account(id, &caller).is_frozen);
+    }
+
+    freeze_asset {
+        let (id, issuer) = create_asset::<T>();
+    }: _(RawOrigin::Signed(issuer), id)
+    verify {
+        assert!(Module::<T>::asset(id).map_or(false, |details| details.is_frozen));
+    }
+
+    thaw_asset {
+        let (id, issuer) = create_asset::<T>();
+        Assets::<T>::mutate(id, |details| {
+            if let Some(details) = details {
+                details.is_frozen = true;
+            }
+        });
+    }: _(RawOrigin::Signed(issuer), id)
+    verify {
+        assert!(Module::<T>::asset(id).map_or(false, |details| !details.is_frozen));
+    }
+
+    set_issuer {
+        let (id, issuer) = create_asset::<T>();
+        let new_issuer: T::AccountId = account("issuer", 1, SEED);
+    }: _(RawOrigin::Signed(issuer), id, T::Lookup::unlookup(new_issuer.clone()))
+    verify {
+        assert_eq!(Module::<T>::asset(id).map(|details| details.issuer), Some(new_issuer));
+    }
+
+    set_metadata {
+        let n in 0 .. T::StringLimit::get();
+        let s in 0 .. T::StringLimit::get();
+
+        let (id, issuer) = create_asset::<T>();
+        let name = vec![0u8; n as usize];
+        let symbol = vec![0u8; s as usize];
+    }: _(RawOrigin::Signed(issuer), id, name.clone(), symbol, 12)
+    verify {
+        assert_eq!(Module::<T>::metadata(id).name, name);
+    }
+
+    destroy {
+        let (id, issuer) = create_asset::<T>();
+        let limit = T::StringLimit::get() as usize;
+        Metadata::<T>::insert(id, AssetMetadata {
+            name: vec![0u8; limit],
+            symbol: vec![0u8; limit],
+            decimals: 12,
+        });
+    }: _(RawOrigin::Signed(issuer), id)
+    verify {
+        assert!(Module::<T>::asset(id).is_none());
+        assert!(!Metadata::<T>::contains_key(id));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{new_test_ext, Test};
+    use frame_support::assert_ok;
+
+    #[test]
+    fn test_benchmarks() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(test_benchmark_create::<Test>());
+            assert_ok!(test_benchmark_mint::<Test>());
+            assert_ok!(test_benchmark_burn::<Test>());
+            assert_ok!(test_benchmark_transfer::<Test>());
+            assert_ok!(test_benchmark_transfer_keep_alive::<Test>());
+            assert_ok!(test_benchmark_freeze::<Test>());
+            assert_ok!(test_benchmark_thaw::<Test>());
+            assert_ok!(test_benchmark_freeze_asset::<Test>());
+            assert_ok!(test_benchmark_thaw_asset::<Test>());
+            assert_ok!(test_benchmark_set_issuer::<Test>());
+            assert_ok!(test_benchmark_set_metadata::<Test>());
+            assert_ok!(test_benchmark_destroy::<Test>());
+        });
+    }
+}
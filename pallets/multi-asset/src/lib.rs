@@ -0,0 +1,614 @@
+// This file is part of Curio project.
+
+// Copyright (C) 2021 Mixbytes.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Multi-asset
+//!
+//! Fungible assets issued besides the native currency, such as tokenized real-world assets.
+//!
+//! Assets are created by `CreateOrigin`, which names the issuer and the minimum balance of the
+//! asset. The issuer mints and burns the asset, freezes accounts or the whole asset, and sets
+//! its metadata. Holders transfer the asset unless it or their account is frozen. Once no
+//! account holds the asset, the issuer may destroy it along with its metadata.
+//!
+//! The minimum balance plays the part of the existential deposit of the asset: an account may
+//! not hold less than it. A transfer which would leave less in the source sends the whole
+//! balance, unless made with `transfer_keep_alive`, and a burn which would leave less burns the
+//! whole balance. An account which no longer holds the asset is removed, and with it the freeze
+//! of its transfers.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Decode, Encode};
+use frame_support::{
+    decl_error, decl_event, decl_module, decl_storage, ensure,
+    traits::{EnsureOrigin, Get},
+    weights::Weight,
+    Parameter, RuntimeDebug,
+};
+use frame_system::ensure_signed;
+use sp_runtime::{
+    traits::{
+        AtLeast32BitUnsigned, CheckedAdd, CheckedSub, MaybeSerializeDeserialize, Member, One,
+        Saturating, StaticLookup, Zero,
+    },
+    DispatchError, DispatchResult,
+};
+use sp_std::prelude::*;
+
+mod benchmarking;
+mod default_weights;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+type AssetDetailsOf<T> = AssetDetails<<T as frame_system::Trait>::AccountId, <T as Trait>::Balance>;
+
+/// Details of an asset.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct AssetDetails<AccountId, Balance> {
+    /// The account which may mint, burn and freeze the asset and set its metadata.
+    pub issuer: AccountId,
+    /// Amount of the asset in circulation.
+    pub supply: Balance,
+    /// Minimum balance of an account holding the asset.
+    pub min_balance: Balance,
+    /// Number of accounts holding the asset.
+    pub accounts: u32,
+    /// Whether all transfers of the asset are frozen.
+    pub is_frozen: bool,
+}
+
+/// Holding of an account in an asset.
+#[derive(Encode, Decode, Clone, Default, PartialEq, Eq, RuntimeDebug)]
+pub struct AssetBalance<Balance> {
+    pub balance: Balance,
+    /// Whether the transfers from the account are frozen.
+    pub is_frozen: bool,
+}
+
+/// Metadata of an asset.
+#[derive(Encode, Decode, Clone, Default, PartialEq, Eq, RuntimeDebug)]
+pub struct AssetMetadata {
+    pub name: Vec<u8>,
+    pub symbol: Vec<u8>,
+    pub decimals: u8,
+}
+
+pub trait WeightInfo {
+    fn create() -> Weight;
+    fn mint() -> Weight;
+    fn burn() -> Weight;
+    fn transfer() -> Weight;
+    fn transfer_keep_alive() -> Weight;
+    fn freeze() -> Weight;
+    fn thaw() -> Weight;
+    fn freeze_asset() -> Weight;
+    fn thaw_asset() -> Weight;
+    fn set_issuer() -> Weight;
+    fn set_metadata(n: u32, s: u32) -> Weight;
+    fn destroy() -> Weight;
+}
+
+pub trait Trait: frame_system::Trait {
+    /// The overarching event type.
+    type Event: From<Event<Self>> + Into<<Self as frame_system::Trait>::Event>;
+
+    /// The balance of an account in an asset.
+    type Balance: Parameter
+        + Member
+        + AtLeast32BitUnsigned
+        + Default
+        + Copy
+        + MaybeSerializeDeserialize;
+
+    /// Identifier of an asset.
+    type AssetId: Parameter
+        + Member
+        + AtLeast32BitUnsigned
+        + Default
+        + Copy
+        + MaybeSerializeDeserialize;
+
+    /// The origin which may create assets.
+    type CreateOrigin: EnsureOrigin<Self::Origin>;
+
+    /// Maximum length of the name and of the symbol of an asset.
+    type StringLimit: Get<u32>;
+
+    /// Weight information for extrinsics in this pallet.
+    type WeightInfo: WeightInfo;
+}
+
+decl_storage! {
+    trait Store for Module<T: Trait> as MultiAsset {
+        /// Identifier of the next created asset.
+        pub NextAssetId get(fn next_asset_id) config(): T::AssetId;
+
+        /// Details of the assets.
+        pub Assets get(fn asset):
+            map hasher(blake2_128_concat) T::AssetId => Option<AssetDetailsOf<T>>;
+
+        /// Holdings of the accounts in the assets.
+        pub Account get(fn account):
+            double_map hasher(blake2_128_concat) T::AssetId, hasher(blake2_128_concat) T::AccountId
+            => AssetBalance<T::Balance>;
+
+        /// Metadata of the assets.
+        pub Metadata get(fn metadata):
+            map hasher(blake2_128_concat) T::AssetId => AssetMetadata;
+    }
+    add_extra_genesis {
+        /// Assets created at genesis, with their issuer and minimum balance.
+        config(assets): Vec<(T::AssetId, T::AccountId, T::Balance)>;
+        /// Holdings of the accounts at genesis.
+        config(balances): Vec<(T::AssetId, T::AccountId, T::Balance)>;
+        build(|config| {
+            for (id, issuer, min_balance) in &config.assets {
+                assert!(!Assets::<T>::contains_key(id), "duplicate asset in genesis config");
+                assert!(
+                    !min_balance.is_zero(),
+                    "the minimum balance of an asset should be positive",
+                );
+                Assets::<T>::insert(id, AssetDetails {
+                    issuer: issuer.clone(),
+                    supply: Zero::zero(),
+                    min_balance: *min_balance,
+                    accounts: 0,
+                    is_frozen: false,
+                });
+                if *id >= NextAssetId::<T>::get() {
+                    NextAssetId::<T>::put(id.saturating_add(One::one()));
+                }
+            }
+            for (id, who, amount) in &config.balances {
+                Module::<T>::do_mint(*id, who, *amount).expect("invalid balance in genesis config");
+            }
+        });
+    }
+}
+
+decl_event!(
+    pub enum Event<T>
+    where
+        AccountId = <T as frame_system::Trait>::AccountId,
+        AssetId = <T as Trait>::AssetId,
+        Balance = <T as Trait>::Balance,
+    {
+        /// An asset was created. \[asset, issuer, min_balance\]
+        Created(AssetId, AccountId, Balance),
+        /// Some amount of an asset was minted. \[asset, beneficiary, amount\]
+        Minted(AssetId, AccountId, Balance),
+        /// Some amount of an asset was burned. \[asset, who, amount\]
+        Burned(AssetId, AccountId, Balance),
+        /// Some amount of an asset was transferred. \[asset, from, to, amount\]
+        Transferred(AssetId, AccountId, AccountId, Balance),
+        /// The transfers from an account were frozen. \[asset, who\]
+        Frozen(AssetId, AccountId),
+        /// The transfers from an account were thawed. \[asset, who\]
+        Thawed(AssetId, AccountId),
+        /// All transfers of an asset were frozen. \[asset\]
+        AssetFrozen(AssetId),
+        /// The transfers of an asset were thawed. \[asset\]
+        AssetThawed(AssetId),
+        /// The issuer of an asset changed. \[asset, issuer\]
+        IssuerChanged(AssetId, AccountId),
+        /// The metadata of an asset was set. \[asset, name, symbol, decimals\]
+        MetadataSet(AssetId, Vec<u8>, Vec<u8>, u8),
+        /// An asset was destroyed. \[asset\]
+        Destroyed(AssetId),
+    }
+);
+
+decl_error! {
+    pub enum Error for Module<T: Trait> {
+        /// The asset does not exist.
+        UnknownAsset,
+        /// The origin is not the issuer of the asset.
+        NoPermission,
+        /// The amount must be greater than zero.
+        ZeroAmount,
+        /// The minimum balance must be greater than zero.
+        MinBalanceZero,
+        /// The account holds less than the amount.
+        BalanceLow,
+        /// The account would hold less than the minimum balance.
+        BelowMinimum,
+        /// The transfer would leave less than the minimum balance in the source.
+        WouldDie,
+        /// The asset or the account is frozen.
+        Frozen,
+        /// The supply of the asset would overflow.
+        Overflow,
+        /// The name or the symbol is too long.
+        BadMetadata,
+        /// The account holds none of the asset.
+        UnknownAccount,
+        /// Some accounts still hold the asset.
+        InUse,
+    }
+}
+
+decl_module! {
+    pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+        type Error = Error<T>;
+
+        /// Maximum length of the name and of the symbol of an asset.
+        const StringLimit: u32 = T::StringLimit::get();
+
+        fn deposit_event() = default;
+
+        /// Create an asset issued by `issuer`, which accounts may not hold less than
+        /// `min_balance` of.
+        ///
+        /// The asset gets the next free identifier.
+        #[weight = T::WeightInfo::create()]
+        fn create(
+            origin,
+            issuer: <T::Lookup as StaticLookup>::Source,
+            #[compact] min_balance: T::Balance
+        ) -> DispatchResult {
+            T::CreateOrigin::ensure_origin(origin)?;
+            let issuer = T::Lookup::lookup(issuer)?;
+            ensure!(!min_balance.is_zero(), Error::<T>::MinBalanceZero);
+
+            let id = Self::next_asset_id();
+            NextAssetId::<T>::put(id.saturating_add(One::one()));
+            Assets::<T>::insert(id, AssetDetails {
+                issuer: issuer.clone(),
+                supply: Zero::zero(),
+                min_balance,
+                accounts: 0,
+                is_frozen: false,
+            });
+
+            Self::deposit_event(RawEvent::Created(id, issuer, min_balance));
+            Ok(())
+        }
+
+        /// Mint `amount` of asset `id` to `beneficiary`.
+        #[weight = T::WeightInfo::mint()]
+        fn mint(
+            origin,
+            #[compact] id: T::AssetId,
+            beneficiary: <T::Lookup as StaticLookup>::Source,
+            #[compact] amount: T::Balance
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let beneficiary = T::Lookup::lookup(beneficiary)?;
+            Self::ensure_issuer(id, &who)?;
+
+            Self::do_mint(id, &beneficiary, amount)?;
+
+            Self::deposit_event(RawEvent::Minted(id, beneficiary, amount));
+            Ok(())
+        }
+
+        /// Burn `amount` of asset `id` from `who`.
+        ///
+        /// A remainder below the minimum balance is burned too.
+        #[weight = T::WeightInfo::burn()]
+        fn burn(
+            origin,
+            #[compact] id: T::AssetId,
+            who: <T::Lookup as StaticLookup>::Source,
+            #[compact] amount: T::Balance
+        ) -> DispatchResult {
+            let issuer = ensure_signed(origin)?;
+            let who = T::Lookup::lookup(who)?;
+            Self::ensure_issuer(id, &issuer)?;
+
+            let burned = Self::do_burn(id, &who, amount)?;
+
+            Self::deposit_event(RawEvent::Burned(id, who, burned));
+            Ok(())
+        }
+
+        /// Transfer `amount` of asset `id` to `dest`.
+        ///
+        /// A remainder below the minimum balance is transferred too.
+        #[weight = T::WeightInfo::transfer()]
+        fn transfer(
+            origin,
+            #[compact] id: T::AssetId,
+            dest: <T::Lookup as StaticLookup>::Source,
+            #[compact] amount: T::Balance
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let dest = T::Lookup::lookup(dest)?;
+
+            let transferred = Self::do_transfer(id, &who, &dest, amount, false)?;
+
+            Self::deposit_event(RawEvent::Transferred(id, who, dest, transferred));
+            Ok(())
+        }
+
+        /// Transfer `amount` of asset `id` to `dest`, failing if less than the minimum balance
+        /// would be left.
+        #[weight = T::WeightInfo::transfer_keep_alive()]
+        fn transfer_keep_alive(
+            origin,
+            #[compact] id: T::AssetId,
+            dest: <T::Lookup as StaticLookup>::Source,
+            #[compact] amount: T::Balance
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let dest = T::Lookup::lookup(dest)?;
+
+            let transferred = Self::do_transfer(id, &who, &dest, amount, true)?;
+
+            Self::deposit_event(RawEvent::Transferred(id, who, dest, transferred));
+            Ok(())
+        }
+
+        /// Freeze the transfers of asset `id` from `who`, which must hold the asset.
+        ///
+        /// The freeze ends when `who` no longer holds the asset.
+        #[weight = T::WeightInfo::freeze()]
+        fn freeze(
+            origin,
+            #[compact] id: T::AssetId,
+            who: <T::Lookup as StaticLookup>::Source
+        ) -> DispatchResult {
+            let issuer = ensure_signed(origin)?;
+            let who = T::Lookup::lookup(who)?;
+            Self::ensure_issuer(id, &issuer)?;
+            ensure!(Account::<T>::contains_key(id, &who), Error::<T>::UnknownAccount);
+
+            let mut account = Self::account(id, &who);
+            account.is_frozen = true;
+            Self::store_account(id, &who, account);
+
+            Self::deposit_event(RawEvent::Frozen(id, who));
+            Ok(())
+        }
+
+        /// Thaw the transfers of asset `id` from `who`.
+        #[weight = T::WeightInfo::thaw()]
+        fn thaw(
+            origin,
+            #[compact] id: T::AssetId,
+            who: <T::Lookup as StaticLookup>::Source
+        ) -> DispatchResult {
+            let issuer = ensure_signed(origin)?;
+            let who = T::Lookup::lookup(who)?;
+            Self::ensure_issuer(id, &issuer)?;
+
+            let mut account = Self::account(id, &who);
+            account.is_frozen = false;
+            Self::store_account(id, &who, account);
+
+            Self::deposit_event(RawEvent::Thawed(id, who));
+            Ok(())
+        }
+
+        /// Freeze all transfers of asset `id`.
+        #[weight = T::WeightInfo::freeze_asset()]
+        fn freeze_asset(origin, #[compact] id: T::AssetId) -> DispatchResult {
+            let issuer = ensure_signed(origin)?;
+            let mut details = Self::ensure_issuer(id, &issuer)?;
+
+            details.is_frozen = true;
+            Assets::<T>::insert(id, details);
+
+            Self::deposit_event(RawEvent::AssetFrozen(id));
+            Ok(())
+        }
+
+        /// Thaw the transfers of asset `id`.
+        #[weight = T::WeightInfo::thaw_asset()]
+        fn thaw_asset(origin, #[compact] id: T::AssetId) -> DispatchResult {
+            let issuer = ensure_signed(origin)?;
+            let mut details = Self::ensure_issuer(id, &issuer)?;
+
+            details.is_frozen = false;
+            Assets::<T>::insert(id, details);
+
+            Self::deposit_event(RawEvent::AssetThawed(id));
+            Ok(())
+        }
+
+        /// Hand the issuance of asset `id` over to `issuer`.
+        #[weight = T::WeightInfo::set_issuer()]
+        fn set_issuer(
+            origin,
+            #[compact] id: T::AssetId,
+            issuer: <T::Lookup as StaticLookup>::Source
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let issuer = T::Lookup::lookup(issuer)?;
+            let mut details = Self::ensure_issuer(id, &who)?;
+
+            details.issuer = issuer.clone();
+            Assets::<T>::insert(id, details);
+
+            Self::deposit_event(RawEvent::IssuerChanged(id, issuer));
+            Ok(())
+        }
+
+        /// Set the name, the symbol and the number of decimals of asset `id`.
+        #[weight = T::WeightInfo::set_metadata(name.len() as u32, symbol.len() as u32)]
+        fn set_metadata(
+            origin,
+            #[compact] id: T::AssetId,
+            name: Vec<u8>,
+            symbol: Vec<u8>,
+            decimals: u8
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::ensure_issuer(id, &who)?;
+            let limit = T::StringLimit::get() as usize;
+            ensure!(
+                name.len() <= limit && symbol.len() <= limit,
+                Error::<T>::BadMetadata
+            );
+
+            Metadata::<T>::insert(id, AssetMetadata {
+                name: name.clone(),
+                symbol: symbol.clone(),
+                decimals,
+            });
+
+            Self::deposit_event(RawEvent::MetadataSet(id, name, symbol, decimals));
+            Ok(())
+        }
+
+        /// Destroy asset `id`, which no account may hold, and remove its metadata.
+        ///
+        /// The identifier of the asset is not reused.
+        #[weight = T::WeightInfo::destroy()]
+        fn destroy(origin, #[compact] id: T::AssetId) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let details = Self::ensure_issuer(id, &who)?;
+            ensure!(details.accounts == 0, Error::<T>::InUse);
+
+            Assets::<T>::remove(id);
+            Metadata::<T>::remove(id);
+
+            Self::deposit_event(RawEvent::Destroyed(id));
+            Ok(())
+        }
+    }
+}
+
+impl<T: Trait> Module<T> {
+    /// The balance of `who` in asset `id`.
+    pub fn balance(id: T::AssetId, who: &T::AccountId) -> T::Balance {
+        Self::account(id, who).balance
+    }
+
+    /// The amount of asset `id` in circulation.
+    pub fn total_supply(id: T::AssetId) -> T::Balance {
+        Self::asset(id)
+            .map(|details| details.supply)
+            .unwrap_or_default()
+    }
+
+    /// Add `amount` of asset `id` to the balance of `who`.
+    pub fn do_mint(id: T::AssetId, who: &T::AccountId, amount: T::Balance) -> DispatchResult {
+        ensure!(!amount.is_zero(), Error::<T>::ZeroAmount);
+        let mut details = Self::asset(id).ok_or(Error::<T>::UnknownAsset)?;
+        details.supply = details
+            .supply
+            .checked_add(&amount)
+            .ok_or(Error::<T>::Overflow)?;
+        // Cannot overflow, the balance is part of the supply.
+        let balance = Self::balance(id, who) + amount;
+        ensure!(balance >= details.min_balance, Error::<T>::BelowMinimum);
+
+        Self::set_balance(id, who, &mut details, balance);
+        Assets::<T>::insert(id, details);
+        Ok(())
+    }
+
+    /// Remove `amount` of asset `id` from the balance of `who`, and a remainder below the
+    /// minimum balance. Returns the burned amount.
+    pub fn do_burn(
+        id: T::AssetId,
+        who: &T::AccountId,
+        amount: T::Balance,
+    ) -> Result<T::Balance, DispatchError> {
+        ensure!(!amount.is_zero(), Error::<T>::ZeroAmount);
+        let mut details = Self::asset(id).ok_or(Error::<T>::UnknownAsset)?;
+        let mut balance = Self::balance(id, who)
+            .checked_sub(&amount)
+            .ok_or(Error::<T>::BalanceLow)?;
+        let mut burned = amount;
+        if balance < details.min_balance {
+            burned = burned.saturating_add(balance);
+            balance = Zero::zero();
+        }
+        details.supply = details.supply.saturating_sub(burned);
+
+        Self::set_balance(id, who, &mut details, balance);
+        Assets::<T>::insert(id, details);
+        Ok(burned)
+    }
+
+    /// Move `amount` of asset `id` from `source` to `dest`. A remainder below the minimum
+    /// balance is moved too, or the transfer fails if `keep_alive`. Returns the moved amount.
+    pub fn do_transfer(
+        id: T::AssetId,
+        source: &T::AccountId,
+        dest: &T::AccountId,
+        amount: T::Balance,
+        keep_alive: bool,
+    ) -> Result<T::Balance, DispatchError> {
+        ensure!(!amount.is_zero(), Error::<T>::ZeroAmount);
+        let mut details = Self::asset(id).ok_or(Error::<T>::UnknownAsset)?;
+        let from = Self::account(id, source);
+        ensure!(!details.is_frozen && !from.is_frozen, Error::<T>::Frozen);
+        let mut remainder = from
+            .balance
+            .checked_sub(&amount)
+            .ok_or(Error::<T>::BalanceLow)?;
+        let mut amount = amount;
+        if remainder < details.min_balance {
+            ensure!(!keep_alive, Error::<T>::WouldDie);
+            amount = from.balance;
+            remainder = Zero::zero();
+        }
+        if source == dest {
+            return Ok(amount);
+        }
+        // Cannot overflow, both balances are part of the supply.
+        let to = Self::balance(id, dest) + amount;
+        ensure!(to >= details.min_balance, Error::<T>::BelowMinimum);
+
+        Self::set_balance(id, source, &mut details, remainder);
+        Self::set_balance(id, dest, &mut details, to);
+        Assets::<T>::insert(id, details);
+        Ok(amount)
+    }
+
+    /// The details of asset `id`, if `who` is its issuer.
+    fn ensure_issuer(
+        id: T::AssetId,
+        who: &T::AccountId,
+    ) -> Result<AssetDetailsOf<T>, DispatchError> {
+        let details = Self::asset(id).ok_or(Error::<T>::UnknownAsset)?;
+        ensure!(&details.issuer == who, Error::<T>::NoPermission);
+        Ok(details)
+    }
+
+    /// Set the balance of `who` in asset `id`, counting the accounts holding the asset in
+    /// `details`.
+    fn set_balance(
+        id: T::AssetId,
+        who: &T::AccountId,
+        details: &mut AssetDetailsOf<T>,
+        balance: T::Balance,
+    ) {
+        let mut account = Self::account(id, who);
+        match (account.balance.is_zero(), balance.is_zero()) {
+            (true, false) => details.accounts = details.accounts.saturating_add(1),
+            (false, true) => details.accounts = details.accounts.saturating_sub(1),
+            _ => {}
+        }
+        account.balance = balance;
+        Self::store_account(id, who, account);
+    }
+
+    /// Store the holding of `who` in asset `id`, removing it if `who` holds none of the asset.
+    fn store_account(id: T::AssetId, who: &T::AccountId, account: AssetBalance<T::Balance>) {
+        if account.balance.is_zero() {
+            Account::<T>::remove(id, who);
+        } else {
+            Account::<T>::insert(id, who, account);
+        }
+    }
+}
@@ -0,0 +1,256 @@
+// This file is part of Curio project.
+
+// Copyright (C) 2021 Mixbytes.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests for the module.
+
+use super::*;
+use frame_support::{assert_noop, assert_ok};
+use frame_system::RawOrigin;
+use mock::{new_test_ext, Assets, System, Test, TestEvent};
+use sp_runtime::traits::BadOrigin;
+
+fn asset_events() -> Vec<RawEvent<u64, u32, u64>> {
+    System::events()
+        .into_iter()
+        .filter_map(|record| match record.event {
+            TestEvent::multi_asset(event) => Some(event),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn genesis_config_works() {
+    new_test_ext().execute_with(|| {
+        assert_eq!(Assets::next_asset_id(), 2);
+        let details = Assets::asset(1).unwrap();
+        assert_eq!(details.issuer, 1);
+        assert_eq!(details.min_balance, 10);
+        assert_eq!(details.accounts, 2);
+        assert_eq!(Assets::total_supply(1), 150);
+        assert_eq!(Assets::balance(1, &2), 100);
+        assert_eq!(Assets::balance(1, &3), 50);
+    });
+}
+
+#[test]
+fn issuer_mints_and_burns() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(Assets::create(RawOrigin::Signed(1).into(), 4, 5), BadOrigin);
+        assert_noop!(
+            Assets::create(RawOrigin::Root.into(), 4, 0),
+            Error::<Test>::MinBalanceZero
+        );
+        assert_ok!(Assets::create(RawOrigin::Root.into(), 4, 5));
+        assert_eq!(Assets::next_asset_id(), 3);
+
+        assert_noop!(
+            Assets::mint(RawOrigin::Signed(1).into(), 2, 5, 100),
+            Error::<Test>::NoPermission
+        );
+        assert_noop!(
+            Assets::mint(RawOrigin::Signed(4).into(), 3, 5, 100),
+            Error::<Test>::UnknownAsset
+        );
+        assert_noop!(
+            Assets::mint(RawOrigin::Signed(4).into(), 2, 5, 4),
+            Error::<Test>::BelowMinimum
+        );
+        assert_ok!(Assets::mint(RawOrigin::Signed(4).into(), 2, 5, 100));
+        assert_eq!(Assets::total_supply(2), 100);
+
+        // The remainder below the minimum balance is burned too.
+        assert_ok!(Assets::burn(RawOrigin::Signed(4).into(), 2, 5, 97));
+        assert_eq!(Assets::balance(2, &5), 0);
+        assert_eq!(Assets::total_supply(2), 0);
+        assert_eq!(Assets::asset(2).unwrap().accounts, 0);
+        assert!(!Account::<Test>::contains_key(2, 5));
+
+        assert_eq!(
+            asset_events(),
+            vec![
+                RawEvent::Created(2, 4, 5),
+                RawEvent::Minted(2, 5, 100),
+                RawEvent::Burned(2, 5, 100),
+            ]
+        );
+    });
+}
+
+#[test]
+fn transfers_respect_min_balance() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            Assets::transfer(RawOrigin::Signed(2).into(), 1, 4, 5),
+            Error::<Test>::BelowMinimum
+        );
+        assert_noop!(
+            Assets::transfer(RawOrigin::Signed(2).into(), 1, 4, 101),
+            Error::<Test>::BalanceLow
+        );
+        assert_noop!(
+            Assets::transfer_keep_alive(RawOrigin::Signed(3).into(), 1, 4, 45),
+            Error::<Test>::WouldDie
+        );
+
+        assert_ok!(Assets::transfer_keep_alive(
+            RawOrigin::Signed(2).into(),
+            1,
+            4,
+            20
+        ));
+        assert_eq!(Assets::balance(1, &2), 80);
+        assert_eq!(Assets::balance(1, &4), 20);
+
+        // The remainder below the minimum balance goes along.
+        assert_ok!(Assets::transfer(RawOrigin::Signed(3).into(), 1, 4, 45));
+        assert_eq!(Assets::balance(1, &3), 0);
+        assert_eq!(Assets::balance(1, &4), 70);
+        assert_eq!(Assets::asset(1).unwrap().accounts, 2);
+        assert_eq!(Assets::total_supply(1), 150);
+
+        assert_eq!(
+            asset_events(),
+            vec![
+                RawEvent::Transferred(1, 2, 4, 20),
+                RawEvent::Transferred(1, 3, 4, 50),
+            ]
+        );
+    });
+}
+
+#[test]
+fn issuer_freezes_accounts_and_assets() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            Assets::freeze(RawOrigin::Signed(2).into(), 1, 3),
+            Error::<Test>::NoPermission
+        );
+        // Only the holders of the asset are frozen, no empty holding is stored.
+        assert_noop!(
+            Assets::freeze(RawOrigin::Signed(1).into(), 1, 4),
+            Error::<Test>::UnknownAccount
+        );
+        assert!(!Account::<Test>::contains_key(1, 4));
+        assert_ok!(Assets::freeze(RawOrigin::Signed(1).into(), 1, 2));
+        assert_noop!(
+            Assets::transfer(RawOrigin::Signed(2).into(), 1, 3, 10),
+            Error::<Test>::Frozen
+        );
+        // A frozen account still receives the asset.
+        assert_ok!(Assets::transfer(RawOrigin::Signed(3).into(), 1, 2, 10));
+        assert_ok!(Assets::thaw(RawOrigin::Signed(1).into(), 1, 2));
+        assert_ok!(Assets::transfer(RawOrigin::Signed(2).into(), 1, 3, 10));
+
+        assert_ok!(Assets::freeze_asset(RawOrigin::Signed(1).into(), 1));
+        assert_noop!(
+            Assets::transfer(RawOrigin::Signed(3).into(), 1, 2, 10),
+            Error::<Test>::Frozen
+        );
+        assert_ok!(Assets::thaw_asset(RawOrigin::Signed(1).into(), 1));
+        assert_ok!(Assets::transfer(RawOrigin::Signed(3).into(), 1, 2, 10));
+
+        assert_eq!(Assets::balance(1, &2), 110);
+        assert_eq!(Assets::balance(1, &3), 40);
+
+        // The freeze ends with the holding.
+        assert_ok!(Assets::freeze(RawOrigin::Signed(1).into(), 1, 3));
+        assert_ok!(Assets::burn(RawOrigin::Signed(1).into(), 1, 3, 40));
+        assert!(!Account::<Test>::contains_key(1, 3));
+        assert_ok!(Assets::mint(RawOrigin::Signed(1).into(), 1, 3, 10));
+        assert!(!Assets::account(1, &3).is_frozen);
+    });
+}
+
+#[test]
+fn issuer_destroys_unheld_assets() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::set_metadata(
+            RawOrigin::Signed(1).into(),
+            1,
+            b"Token".to_vec(),
+            b"TKN".to_vec(),
+            2
+        ));
+        assert_noop!(
+            Assets::destroy(RawOrigin::Signed(2).into(), 1),
+            Error::<Test>::NoPermission
+        );
+        assert_noop!(
+            Assets::destroy(RawOrigin::Signed(1).into(), 1),
+            Error::<Test>::InUse
+        );
+
+        assert_ok!(Assets::burn(RawOrigin::Signed(1).into(), 1, 2, 100));
+        assert_ok!(Assets::burn(RawOrigin::Signed(1).into(), 1, 3, 50));
+        assert_ok!(Assets::destroy(RawOrigin::Signed(1).into(), 1));
+        assert!(Assets::asset(1).is_none());
+        assert!(!Metadata::<Test>::contains_key(1));
+        assert_noop!(
+            Assets::destroy(RawOrigin::Signed(1).into(), 1),
+            Error::<Test>::UnknownAsset
+        );
+        assert_noop!(
+            Assets::mint(RawOrigin::Signed(1).into(), 1, 2, 10),
+            Error::<Test>::UnknownAsset
+        );
+        // The identifier is not reused.
+        assert_ok!(Assets::create(RawOrigin::Root.into(), 1, 5));
+        assert!(Assets::asset(2).is_some());
+        assert_eq!(asset_events().last(), Some(&RawEvent::Created(2, 1, 5)));
+        assert!(asset_events().contains(&RawEvent::Destroyed(1)));
+    });
+}
+
+#[test]
+fn issuer_sets_metadata_and_hands_over() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            Assets::set_metadata(
+                RawOrigin::Signed(1).into(),
+                1,
+                b"Too long name".to_vec(),
+                b"T".to_vec(),
+                2
+            ),
+            Error::<Test>::BadMetadata
+        );
+        assert_ok!(Assets::set_metadata(
+            RawOrigin::Signed(1).into(),
+            1,
+            b"Token".to_vec(),
+            b"TKN".to_vec(),
+            2
+        ));
+        assert_eq!(
+            Assets::metadata(1),
+            AssetMetadata {
+                name: b"Token".to_vec(),
+                symbol: b"TKN".to_vec(),
+                decimals: 2,
+            }
+        );
+
+        assert_ok!(Assets::set_issuer(RawOrigin::Signed(1).into(), 1, 4));
+        assert_noop!(
+            Assets::mint(RawOrigin::Signed(1).into(), 1, 2, 10),
+            Error::<Test>::NoPermission
+        );
+        assert_ok!(Assets::mint(RawOrigin::Signed(4).into(), 1, 2, 10));
+        assert_eq!(Assets::total_supply(1), 160);
+    });
+}
@@ -0,0 +1,166 @@
+// This file is part of Curio project.
+
+// Copyright (C) 2021 Mixbytes.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Parameters
+//!
+//! Keeps the runtime parameters which governance tunes without a runtime upgrade.
+//!
+//! The parameters are the splits of the transaction fees and of the tips between the treasury,
+//! the block author and the burn. They are changed by `ParameterOrigin`.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Decode, Encode};
+use frame_support::{
+    decl_error, decl_event, decl_module, decl_storage, ensure,
+    traits::{EnsureOrigin, Imbalance},
+    weights::Weight,
+    RuntimeDebug,
+};
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_runtime::{traits::AtLeast32BitUnsigned, DispatchResult, PerThing, Percent};
+
+mod default_weights;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+/// Shares of a payment going to the treasury, to the block author and to the burn.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct Split {
+    pub treasury: Percent,
+    pub author: Percent,
+    pub burn: Percent,
+}
+
+impl Split {
+    /// Whether the shares add up to the whole payment.
+    pub fn is_valid(&self) -> bool {
+        let total = self.treasury.deconstruct() as u16
+            + self.author.deconstruct() as u16
+            + self.burn.deconstruct() as u16;
+        total == 100
+    }
+
+    /// Split `payment` into the shares of the treasury, the author and the burn.
+    ///
+    /// The rounding remainder goes to the author.
+    pub fn apply<Balance, I>(&self, payment: I) -> (I, I, I)
+    where
+        Balance: AtLeast32BitUnsigned,
+        I: Imbalance<Balance>,
+    {
+        let total = payment.peek();
+        let (treasury, rest) = payment.split(self.treasury * total);
+        let (burn, author) = rest.split(self.burn * total);
+        (treasury, author, burn)
+    }
+}
+
+impl Default for Split {
+    /// 80% to the treasury and 20% to the author.
+    fn default() -> Self {
+        Split {
+            treasury: Percent::from_percent(80),
+            author: Percent::from_percent(20),
+            burn: Percent::zero(),
+        }
+    }
+}
+
+pub trait WeightInfo {
+    fn set_fee_split() -> Weight;
+    fn set_tip_split() -> Weight;
+}
+
+pub trait Trait: frame_system::Trait {
+    /// The overarching event type.
+    type Event: From<Event> + Into<<Self as frame_system::Trait>::Event>;
+
+    /// The origin which may change the parameters.
+    type ParameterOrigin: EnsureOrigin<Self::Origin>;
+
+    /// Weight information for extrinsics in this pallet.
+    type WeightInfo: WeightInfo;
+}
+
+decl_storage! {
+    trait Store for Module<T: Trait> as Parameters {
+        /// Split of the transaction fees.
+        pub FeeSplit get(fn fee_split) config(): Split;
+
+        /// Split of the tips.
+        pub TipSplit get(fn tip_split) config(): Split;
+    }
+    add_extra_genesis {
+        build(|config| {
+            assert!(config.fee_split.is_valid(), "the fee split should add up to 100%");
+            assert!(config.tip_split.is_valid(), "the tip split should add up to 100%");
+        });
+    }
+}
+
+decl_event!(
+    pub enum Event {
+        /// The split of the transaction fees changed. \[split\]
+        FeeSplitChanged(Split),
+        /// The split of the tips changed. \[split\]
+        TipSplitChanged(Split),
+    }
+);
+
+decl_error! {
+    pub enum Error for Module<T: Trait> {
+        /// The shares of the split do not add up to 100%.
+        InvalidSplit,
+    }
+}
+
+decl_module! {
+    pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+        type Error = Error<T>;
+
+        fn deposit_event() = default;
+
+        /// Set the split of the transaction fees.
+        #[weight = T::WeightInfo::set_fee_split()]
+        fn set_fee_split(origin, split: Split) -> DispatchResult {
+            T::ParameterOrigin::ensure_origin(origin)?;
+            ensure!(split.is_valid(), Error::<T>::InvalidSplit);
+
+            FeeSplit::put(split);
+
+            Self::deposit_event(Event::FeeSplitChanged(split));
+            Ok(())
+        }
+
+        /// Set the split of the tips.
+        #[weight = T::WeightInfo::set_tip_split()]
+        fn set_tip_split(origin, split: Split) -> DispatchResult {
+            T::ParameterOrigin::ensure_origin(origin)?;
+            ensure!(split.is_valid(), Error::<T>::InvalidSplit);
+
+            TipSplit::put(split);
+
+            Self::deposit_event(Event::TipSplitChanged(split));
+            Ok(())
+        }
+    }
+}
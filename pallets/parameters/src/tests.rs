@@ -0,0 +1,96 @@
+// This file is part of Curio project.
+
+// Copyright (C) 2021 Mixbytes.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests for the module.
+
+use super::*;
+use frame_support::{assert_noop, assert_ok, traits::Currency};
+use frame_system::RawOrigin;
+use mock::{new_test_ext, Balances, Parameters, System, TestEvent};
+use sp_runtime::traits::BadOrigin;
+
+fn split(treasury: u8, author: u8, burn: u8) -> Split {
+    Split {
+        treasury: Percent::from_percent(treasury),
+        author: Percent::from_percent(author),
+        burn: Percent::from_percent(burn),
+    }
+}
+
+#[test]
+fn splits_are_set_by_governance() {
+    new_test_ext().execute_with(|| {
+        assert_eq!(Parameters::fee_split(), split(80, 20, 0));
+        assert_eq!(Parameters::tip_split(), split(0, 100, 0));
+
+        assert_noop!(
+            Parameters::set_fee_split(RawOrigin::Signed(1).into(), split(50, 30, 20)),
+            BadOrigin
+        );
+        assert_noop!(
+            Parameters::set_fee_split(RawOrigin::Root.into(), split(50, 30, 30)),
+            Error::<mock::Test>::InvalidSplit
+        );
+        assert_noop!(
+            Parameters::set_tip_split(RawOrigin::Root.into(), split(50, 30, 10)),
+            Error::<mock::Test>::InvalidSplit
+        );
+        assert_ok!(Parameters::set_fee_split(
+            RawOrigin::Root.into(),
+            split(50, 30, 20)
+        ));
+        assert_ok!(Parameters::set_tip_split(
+            RawOrigin::Root.into(),
+            split(10, 90, 0)
+        ));
+        assert_eq!(Parameters::fee_split(), split(50, 30, 20));
+        assert_eq!(Parameters::tip_split(), split(10, 90, 0));
+
+        let events: Vec<_> = System::events()
+            .into_iter()
+            .filter_map(|record| match record.event {
+                TestEvent::parameters(event) => Some(event),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            events,
+            vec![
+                Event::FeeSplitChanged(split(50, 30, 20)),
+                Event::TipSplitChanged(split(10, 90, 0)),
+            ]
+        );
+    });
+}
+
+#[test]
+fn split_applies_to_payments() {
+    new_test_ext().execute_with(|| {
+        let (treasury, author, burn) = split(50, 30, 20).apply(Balances::issue(1_000));
+        assert_eq!(
+            (treasury.peek(), author.peek(), burn.peek()),
+            (500, 300, 200)
+        );
+
+        // The rounding remainder goes to the author.
+        let (treasury, author, burn) = split(33, 33, 34).apply(Balances::issue(10));
+        assert_eq!((treasury.peek(), author.peek(), burn.peek()), (3, 4, 3));
+
+        let (treasury, author, burn) = split(0, 100, 0).apply(Balances::issue(7));
+        assert_eq!((treasury.peek(), author.peek(), burn.peek()), (0, 7, 0));
+    });
+}
@@ -0,0 +1,25 @@
+#!/usr/bin/env bash
+
+set -e
+
+echo "*** Benchmarking the pallets of the runtime"
+
+cd $(dirname ${BASH_SOURCE[0]})/..
+
+pallets=${@:-pallet_dummy pallet_multi_asset}
+
+cargo build --release --features runtime-benchmarks --manifest-path node/cli/Cargo.toml
+
+for pallet in $pallets ; do
+  ./target/release/curio benchmark \
+    --chain dev \
+    --execution wasm \
+    --wasm-execution compiled \
+    --pallet $pallet \
+    --extrinsic '*' \
+    --steps 50 \
+    --repeat 20 \
+    --output
+  mv $pallet.rs node/runtime/src/weights/$pallet.rs
+  rustfmt --edition 2018 node/runtime/src/weights/$pallet.rs
+done
@@ -21,9 +21,9 @@
 use crate::keyring::*;
 use node_runtime::constants::currency::*;
 use node_runtime::{
-    wasm_binary_unwrap, AccountId, BalancesConfig, ContractsConfig, DummyConfig, GenesisConfig,
-    GrandpaConfig, IndicesConfig, SessionConfig, SocietyConfig, StakerStatus, StakingConfig,
-    SystemConfig,
+    wasm_binary_unwrap, AccountId, AssetTxPaymentConfig, AssetsConfig, BalancesConfig,
+    ContractsConfig, DummyConfig, GenesisConfig, GrandpaConfig, IndicesConfig, KycConfig,
+    ParametersConfig, SessionConfig, SocietyConfig, StakerStatus, StakingConfig, SystemConfig,
 };
 use sp_core::ChangesTrieConfiguration;
 use sp_keyring::{Ed25519Keyring, Sr25519Keyring};
@@ -117,7 +117,6 @@ pub fn config_endowed(
         pallet_collective_Instance2: Some(Default::default()),
         pallet_membership_Instance1: Some(Default::default()),
         pallet_elections_phragmen: Some(Default::default()),
-        pallet_sudo: Some(Default::default()),
         pallet_treasury: Some(Default::default()),
         pallet_society: Some(SocietyConfig {
             members: vec![alice(), bob()],
@@ -127,6 +126,25 @@ pub fn config_endowed(
         pallet_vesting: Some(Default::default()),
         pallet_dummy: Some(DummyConfig {
             balance: 100 * DOLLARS,
+            relayers: vec![],
+            relayer_threshold: 1,
+            assets: vec![(0, Default::default())],
+        }),
+        pallet_eth_light_client: Some(Default::default()),
+        pallet_kyc: Some(KycConfig {
+            providers: vec![],
+            investors: vec![],
+            restrict_transfers: false,
+        }),
+        pallet_multi_asset: Some(AssetsConfig {
+            next_asset_id: 1,
+            assets: vec![],
+            balances: vec![],
+        }),
+        pallet_asset_tx_payment: Some(AssetTxPaymentConfig { fee_rates: vec![] }),
+        pallet_parameters: Some(ParametersConfig {
+            fee_split: Default::default(),
+            tip_split: Default::default(),
         }),
     }
 }
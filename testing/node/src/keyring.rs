@@ -76,7 +76,9 @@ pub fn signed_extra(nonce: Index, extra_fee: Balance) -> SignedExtra {
         frame_system::CheckEra::from(Era::mortal(256, 0)),
         frame_system::CheckNonce::from(nonce),
         frame_system::CheckWeight::new(),
-        pallet_transaction_payment::ChargeTransactionPayment::from(extra_fee),
+        pallet_asset_tx_payment::ChargeAssetTxPayment::from(extra_fee, None),
+        pallet_dummy::CheckRelayerVote::new(),
+        pallet_kyc::CheckKyc::new(),
     )
 }
 